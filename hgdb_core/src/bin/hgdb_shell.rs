@@ -0,0 +1,17 @@
+use hgdb_core::db_config;
+use hgdb_core::hyper_edge::controller::shell_controller::ShellController;
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use std::env;
+use std::error::Error;
+
+// Usage: hgdb_shell [db_path] [graph_name]
+// Without arguments the database configured in Config.toml is opened
+fn main() -> Result<(), Box<dyn Error>> {
+    let args: Vec<String> = env::args().collect();
+    let db_path = args.get(1).cloned().unwrap_or_else(db_config::get_db_path);
+    let graph_name = args.get(2).map_or("default", String::as_str);
+
+    let repository = SimpleHyperEdgeRepository::new(&db_path)?;
+    let mut shell = ShellController::new(&repository, graph_name)?;
+    shell.run()
+}
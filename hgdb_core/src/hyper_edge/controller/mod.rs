@@ -0,0 +1 @@
+pub mod shell_controller;
\ No newline at end of file
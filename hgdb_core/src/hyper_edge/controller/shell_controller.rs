@@ -0,0 +1,283 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use crate::hyper_edge::services::h_graph_service::{edge_members, HyperGraphService};
+use crate::hyper_edge::services::simple_h_edge_service::DualHyperEdgeService;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::error::Error;
+
+const COMMANDS: [&str; 11] = [
+    "help", "nodes", "edges", "neighbors", "edges-of", "dual", "path", "matrix", "reload", "quit", "exit",
+];
+
+const HELP: &str = "\
+Commands:
+  nodes                 list the nodes of the current graph
+  edges                 list the hyperedges of the current graph
+  neighbors <node>      nodes sharing a hyperedge with <node>
+  edges-of <node>       hyperedges containing <node>
+  dual <edge>           incidence matrix of <edge> and its transpose
+  path <from> <to>      shortest path between two nodes
+  matrix                incidence matrix of the whole graph
+  reload                reload the graph from the database
+  quit | exit           leave the shell
+";
+
+/// What the shell should do after a command was executed
+#[derive(Debug, PartialEq)]
+pub enum ShellResponse {
+    Output(String),
+    Quit,
+}
+
+/// Tab completion over the command names and the node and edge ids of the current graph
+#[derive(Default)]
+pub struct ShellCompleter {
+    node_ids: Vec<String>,
+    edge_ids: Vec<String>,
+}
+
+impl ShellCompleter {
+    pub fn new(graph: &HyperGraph<String, String, String>) -> Self {
+        ShellCompleter {
+            node_ids: graph.hyper_nodes.iter().map(|node| node.id.clone()).collect(),
+            edge_ids: graph.hyper_edges.iter().map(|edge| edge.id.clone()).collect(),
+        }
+    }
+
+    /// Returns the start of the word under the cursor and the candidates that complete it
+    pub fn candidates(&self, line: &str, pos: usize) -> (usize, Vec<String>) {
+        let before_cursor = &line[..pos];
+        let start = before_cursor.rfind(' ').map_or(0, |index| index + 1);
+        let word = &before_cursor[start..];
+
+        if start == 0 {
+            let commands = COMMANDS.iter().filter(|command| command.starts_with(word)).map(|command| command.to_string());
+            return (start, commands.collect());
+        }
+
+        let ids = match before_cursor.split_whitespace().next() {
+            Some("neighbors") | Some("edges-of") | Some("path") => &self.node_ids,
+            Some("dual") => &self.edge_ids,
+            _ => return (start, Vec::new()),
+        };
+
+        (start, ids.iter().filter(|id| id.starts_with(word)).cloned().collect())
+    }
+}
+
+impl Completer for ShellCompleter {
+    type Candidate = Pair;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, candidates) = self.candidates(line, pos);
+        let pairs = candidates.into_iter()
+            .map(|candidate| Pair { display: candidate.clone(), replacement: candidate })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+impl Hinter for ShellCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for ShellCompleter {}
+
+impl Validator for ShellCompleter {}
+
+impl Helper for ShellCompleter {}
+
+/// Interactive shell over the hyperedges of a repository, keeping the loaded graph as its context
+pub struct ShellController<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+    graph_name: String,
+    graph: HyperGraph<String, String, String>,
+}
+
+impl<'a> ShellController<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository, graph_name: &str) -> Result<Self, Box<dyn Error>> {
+        let graph = HyperGraphService::new(repository).load_graph(graph_name)?;
+        Ok(ShellController { repository, graph_name: graph_name.to_string(), graph })
+    }
+
+    // method to get the graph the shell is currently working on
+    pub fn graph(&self) -> &HyperGraph<String, String, String> {
+        &self.graph
+    }
+
+    /// Runs the read-eval-print loop until `quit`, `exit`, Ctrl-C or Ctrl-D
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut editor: Editor<ShellCompleter, DefaultHistory> = Editor::new()?;
+        editor.set_helper(Some(ShellCompleter::new(&self.graph)));
+
+        println!("🔍 Hypergraph '{}': {} nodes, {} hyperedges. Type `help` for commands.",
+            self.graph_name, self.graph.hyper_nodes.len(), self.graph.hyper_edges.len());
+
+        loop {
+            match editor.readline("hgdb> ") {
+                Ok(line) => {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    editor.add_history_entry(line.as_str())?;
+
+                    match self.execute(&line) {
+                        Ok(ShellResponse::Output(output)) => print!("{}", output),
+                        Ok(ShellResponse::Quit) => break,
+                        Err(e) => eprintln!("❌ {}", e),
+                    }
+                    // The graph may have been reloaded, so refresh the completion candidates
+                    editor.set_helper(Some(ShellCompleter::new(&self.graph)));
+                }
+                Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+                Err(e) => return Err(Box::new(e)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Executes one command line against the current graph
+    pub fn execute(&mut self, line: &str) -> Result<ShellResponse, Box<dyn Error>> {
+        let args: Vec<&str> = line.split_whitespace().collect();
+        let service = HyperGraphService::new(self.repository);
+
+        let output = match args.as_slice() {
+            [] => String::new(),
+            ["help"] => HELP.to_string(),
+            ["quit"] | ["exit"] => return Ok(ShellResponse::Quit),
+            ["reload"] => {
+                self.graph = service.load_graph(&self.graph_name)?;
+                format!("✅ Reloaded '{}': {} nodes, {} hyperedges\n",
+                    self.graph_name, self.graph.hyper_nodes.len(), self.graph.hyper_edges.len())
+            }
+            ["nodes"] => {
+                let rows = self.graph.hyper_nodes.iter()
+                    .map(|node| vec![node.id.clone(), service.edges_of(&self.graph, &node.id).len().to_string()])
+                    .collect();
+                format_table(&["node", "degree"], rows)
+            }
+            ["edges"] => {
+                let edges: Vec<&SimpleHyperEdge<String, String, String>> = self.graph.hyper_edges.iter().collect();
+                format_edge_table(&edges)
+            }
+            ["neighbors", node] => {
+                self.require_node(node)?;
+                let rows = service.neighbors(&self.graph, node).into_iter()
+                    .map(|(neighbor, shared)| vec![neighbor, shared.join(", ")])
+                    .collect();
+                format_table(&["neighbor", "shared edges"], rows)
+            }
+            ["edges-of", node] => {
+                self.require_node(node)?;
+                format_edge_table(&service.edges_of(&self.graph, node))
+            }
+            ["dual", edge] => {
+                let edge = self.find_edge(edge)?;
+                let dual_service = DualHyperEdgeService::new(self.repository);
+                let nodes = edge_members(edge);
+                let incidence_matrix = dual_service.create_incidence_matrix(&nodes, edge);
+                let transposed_matrix = dual_service.transpose_matrix(&incidence_matrix);
+
+                format!("Nodes: {}\nIncidence matrix of {}:\n{}Dual (transposed) matrix:\n{}",
+                    nodes.join(", "), edge.id,
+                    dual_service.format_matrix(&incidence_matrix),
+                    dual_service.format_matrix(&transposed_matrix))
+            }
+            ["path", from, to] => {
+                self.require_node(from)?;
+                self.require_node(to)?;
+                match service.shortest_path(&self.graph, from, to) {
+                    Some(path) => {
+                        let mut output = path.nodes[0].clone();
+                        for (edge, node) in path.edges.iter().zip(path.nodes.iter().skip(1)) {
+                            output.push_str(&format!(" -[{}]- {}", edge, node));
+                        }
+                        format!("{}\n({} hops)\n", output, path.edges.len())
+                    }
+                    None => format!("No path between {} and {}\n", from, to),
+                }
+            }
+            ["matrix"] => {
+                let dual_service = DualHyperEdgeService::new(self.repository);
+                let matrix: Vec<Vec<bool>> = self.graph.hyper_nodes.iter()
+                    .map(|node| self.graph.hyper_edges.iter()
+                        .map(|edge| edge_members(edge).contains(&node.id))
+                        .collect())
+                    .collect();
+                let node_ids: Vec<&str> = self.graph.hyper_nodes.iter().map(|node| node.id.as_str()).collect();
+                let edge_ids: Vec<&str> = self.graph.hyper_edges.iter().map(|edge| edge.id.as_str()).collect();
+
+                format!("Rows: {}\nColumns: {}\n{}", node_ids.join(", "), edge_ids.join(", "), dual_service.format_matrix(&matrix))
+            }
+            [command, ..] if COMMANDS.contains(command) => {
+                return Err(format!("Wrong number of arguments for '{}', type `help` for usage", command).into());
+            }
+            [command, ..] => return Err(format!("Unknown command '{}', type `help` for commands", command).into()),
+        };
+
+        Ok(ShellResponse::Output(output))
+    }
+
+    fn require_node(&self, node: &str) -> Result<(), Box<dyn Error>> {
+        if self.graph.hyper_nodes.iter().any(|hyper_node| hyper_node.id == node) {
+            Ok(())
+        } else {
+            Err(format!("Node '{}' not found in '{}'", node, self.graph_name).into())
+        }
+    }
+
+    // Edges are looked up by id first and by name second
+    fn find_edge(&self, edge: &str) -> Result<&SimpleHyperEdge<String, String, String>, Box<dyn Error>> {
+        self.graph.hyper_edges.iter().find(|hyper_edge| hyper_edge.id == edge)
+            .or_else(|| self.graph.hyper_edges.iter().find(|hyper_edge| hyper_edge.name == edge))
+            .ok_or_else(|| format!("Hyperedge '{}' not found in '{}'", edge, self.graph_name).into())
+    }
+}
+
+fn format_edge_table(edges: &[&SimpleHyperEdge<String, String, String>]) -> String {
+    let rows = edges.iter()
+        .map(|edge| vec![
+            edge.id.clone(),
+            edge.name.clone(),
+            edge.directed.to_string(),
+            edge.traversable.to_string(),
+            edge.head_hyper_nodes.join(", "),
+            edge.tail_hyper_nodes.as_ref().map_or(String::new(), |tail| tail.join(", ")),
+        ])
+        .collect();
+    format_table(&["id", "name", "directed", "traversable", "head", "tail"], rows)
+}
+
+/// Renders rows as a left-aligned text table with a header line
+pub fn format_table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let render = |cells: Vec<&str>| -> String {
+        let padded: Vec<String> = cells.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect();
+        format!("| {} |\n", padded.join(" | "))
+    };
+
+    let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    let mut output = render(headers.to_vec());
+    output.push_str(&format!("|-{}-|\n", separator.join("-|-")));
+    for row in &rows {
+        output.push_str(&render(row.iter().map(String::as_str).collect()));
+    }
+    output.push_str(&format!("({} rows)\n", rows.len()));
+    output
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+
+// A node of a hypergraph; nodes are referenced by id from the head and tail sets of the edges
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperNode<T: Eq + Hash, K: Eq + Hash, V: Eq + Hash> {
+    pub id: T, // The unique ID for the node
+    pub properties: Vec<Property<K, V>>, // A list of properties associated with the node
+}
+
+// A whole hypergraph: its nodes together with the hyperedges connecting them
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperGraph<T: Eq + Hash + ToString, K: Eq + Hash, V: Eq + Hash> {
+    pub id: T, // The unique ID for the hypergraph
+    pub name: T, // The name of the hypergraph
+    pub properties: Vec<Property<K, V>>, // A list of properties associated with the hypergraph
+    pub hyper_nodes: Vec<HyperNode<T, K, V>>, // Every node of the hypergraph, including isolated ones
+    pub hyper_edges: Vec<SimpleHyperEdge<T, K, V>>, // Every hyperedge of the hypergraph
+}
@@ -0,0 +1 @@
+pub mod h_graph;
\ No newline at end of file
@@ -2,5 +2,6 @@ pub mod simple_h_edge;
 pub mod light_h_edge;
 pub mod dual_h_edge;
 pub mod h_edge;
+pub mod h_graph;
 pub mod relationship;
 pub mod structure;
\ No newline at end of file
@@ -0,0 +1,179 @@
+use rocksdb::{DB, Direction, IteratorMode, Options, WriteBatch};
+use serde_json::{self, to_string_pretty};
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use std::error::Error;
+
+type Graph = HyperGraph<String, String, String>;
+// Raw key/value pairs read from the database
+type Entries = Vec<(String, Box<[u8]>)>;
+
+/// Stores named hypergraphs. A graph is kept as a header entry under its name (without nodes and edges)
+/// plus one entry per node under `<name>/node/<id>` and one per hyperedge under `<name>/edge/<id>`
+#[allow(dead_code)]
+pub struct HyperGraphRepository {
+    pub db: DB,
+    db_path: String,
+}
+
+impl HyperGraphRepository {
+    /// Constructor for creating a new repository
+    pub fn new(db_path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+
+        let db = DB::open(&opts, db_path)?;
+
+        Ok(HyperGraphRepository {
+            db,
+            db_path: db_path.to_string(),
+        })
+    }
+
+    /// Method to create (insert) a whole hypergraph, replacing any graph stored under the same key
+    pub fn create(&self, key: &str, graph: &Graph) -> Result<(), Box<dyn Error>> {
+        check_key(key)?;
+        let mut batch = WriteBatch::default();
+        for entry_key in self.entry_keys(key)? {
+            batch.delete(entry_key);
+        }
+
+        let header = HyperGraph {
+            id: graph.id.clone(),
+            name: graph.name.clone(),
+            properties: graph.properties.clone(),
+            hyper_nodes: Vec::new(),
+            hyper_edges: Vec::new(),
+        };
+        batch.put(key, to_string_pretty(&header)?);
+        for node in &graph.hyper_nodes {
+            batch.put(node_key(key, &node.id), to_string_pretty(node)?);
+        }
+        for edge in &graph.hyper_edges {
+            batch.put(edge_key(key, &edge.id), to_string_pretty(edge)?);
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Method to retrieve a hypergraph with all its nodes and hyperedges by key
+    pub fn get_by_key(&self, key: &str) -> Result<Option<Graph>, Box<dyn Error>> {
+        check_key(key)?;
+        let mut graph: Graph = match self.db.get(key)? {
+            Some(serialized_graph) => serde_json::from_slice(&serialized_graph).map_err(|e| {
+                eprintln!("❌ Deserialization error for key '{}': {:?}", key, e);
+                Box::new(e) as Box<dyn Error>
+            })?,
+            None => return Ok(None),
+        };
+
+        let node_prefix = format!("{}/node/", key);
+        let edge_prefix = format!("{}/edge/", key);
+        for (entry_key, value) in self.scan_prefix(&format!("{}/", key))? {
+            if entry_key.starts_with(&node_prefix) {
+                graph.hyper_nodes.push(serde_json::from_slice(&value)?);
+            } else if entry_key.starts_with(&edge_prefix) {
+                graph.hyper_edges.push(serde_json::from_slice(&value)?);
+            }
+        }
+
+        Ok(Some(graph))
+    }
+
+    /// Method to retrieve all hypergraphs in the database
+    pub fn get_all(&self) -> Result<Vec<Graph>, Box<dyn Error>> {
+        let mut graphs = Vec::new();
+        for name in self.get_names()? {
+            if let Some(graph) = self.get_by_key(&name)? {
+                graphs.push(graph);
+            }
+        }
+        Ok(graphs)
+    }
+
+    // method to list the keys of the stored hypergraphs without loading them
+    pub fn get_names(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut names = Vec::new();
+        for item in self.db.iterator(IteratorMode::Start) {
+            let (key, _) = item?;
+            let key = String::from_utf8_lossy(&key).to_string();
+            if !key.contains('/') {
+                names.push(key);
+            }
+        }
+        Ok(names)
+    }
+
+    /// Method to update an existing hypergraph (simply calls `create`)
+    pub fn update(&self, key: &str, graph: &Graph) -> Result<(), Box<dyn Error>> {
+        self.create(key, graph)
+    }
+
+    /// Method to insert or replace a single hyperedge of a stored hypergraph
+    pub fn update_edge(&self, key: &str, edge: &SimpleHyperEdge<String, String, String>) -> Result<(), Box<dyn Error>> {
+        self.require_graph(key)?;
+        self.db.put(edge_key(key, &edge.id), to_string_pretty(edge)?)?;
+        Ok(())
+    }
+
+    /// Method to insert or replace a single node of a stored hypergraph
+    pub fn update_node(&self, key: &str, node: &HyperNode<String, String, String>) -> Result<(), Box<dyn Error>> {
+        self.require_graph(key)?;
+        self.db.put(node_key(key, &node.id), to_string_pretty(node)?)?;
+        Ok(())
+    }
+
+    /// Method to delete a hypergraph with all its nodes and hyperedges
+    pub fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        check_key(key)?;
+        let mut batch = WriteBatch::default();
+        for entry_key in self.entry_keys(key)? {
+            batch.delete(entry_key);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    fn require_graph(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        check_key(key)?;
+        match self.db.get(key)? {
+            Some(_) => Ok(()),
+            None => Err(format!("Hypergraph '{}' not found", key).into()),
+        }
+    }
+
+    // The header key and every node and edge key of a graph
+    fn entry_keys(&self, key: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut keys = vec![key.to_string()];
+        keys.extend(self.scan_prefix(&format!("{}/", key))?.into_iter().map(|(entry_key, _)| entry_key));
+        Ok(keys)
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Entries, Box<dyn Error>> {
+        let mut entries = Vec::new();
+        for item in self.db.iterator(IteratorMode::From(prefix.as_bytes(), Direction::Forward)) {
+            let (key, value) = item?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+            entries.push((String::from_utf8_lossy(&key).to_string(), value));
+        }
+        Ok(entries)
+    }
+}
+
+fn check_key(key: &str) -> Result<(), Box<dyn Error>> {
+    if key.is_empty() || key.contains('/') {
+        return Err(format!("Invalid hypergraph key '{}': it must be non-empty and must not contain '/'", key).into());
+    }
+    Ok(())
+}
+
+fn node_key(key: &str, node_id: &str) -> String {
+    format!("{}/node/{}", key, node_id)
+}
+
+fn edge_key(key: &str, edge_id: &str) -> String {
+    format!("{}/edge/{}", key, edge_id)
+}
@@ -1,2 +1,4 @@
 pub mod simple_h_edge_repository;
-pub mod light_h_edge_repository;
\ No newline at end of file
+pub mod light_h_edge_repository;
+pub mod h_graph_repository;
+pub mod relationship_repository;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use rocksdb::{DB, Options, WriteBatch};
+use serde_json::{self, to_string_pretty};
+use crate::hyper_edge::entity::simple_h_edge::Property;
+use crate::hyper_edge::entity::relationship::relationship::Relationship;
+use std::error::Error;
+
+/// A pairwise relationship whose edge properties are key/value properties
+pub type PropertyRelationship = Relationship<String, String, Property<String, String>>;
+
+/// Key under which a relationship is stored: `a->b` when directed, `a--b` otherwise
+pub fn relationship_key(relationship: &PropertyRelationship) -> String {
+    let arrow = if relationship.directed { "->" } else { "--" };
+    format!("{}{}{}", relationship.node_1, arrow, relationship.node_2)
+}
+
+#[allow(dead_code)]
+pub struct RelationshipRepository {
+    db: DB,
+    db_path: String
+}
+
+impl RelationshipRepository {
+    pub fn new(db_path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+
+        let db = DB::open(&opts, db_path)?;
+
+        Ok(RelationshipRepository {
+            db,
+            db_path: db_path.to_string()
+        })
+    }
+
+    pub fn create(&self, key: &str, relationship: &PropertyRelationship) -> Result<(), Box<dyn Error>> {
+        let serialized_relationship = to_string_pretty(relationship).map_err(|e| {
+            eprintln!("❌ Serialization error for relationship with key '{}': {:?}", key, e);
+            Box::new(e) as Box<dyn Error>
+        })?;
+
+        self.db.put(key, serialized_relationship)?;
+        Ok(())
+    }
+
+    /// Stores many relationships in one write batch, each under its `relationship_key`
+    pub fn create_all(&self, relationships: &[PropertyRelationship]) -> Result<(), Box<dyn Error>> {
+        let mut batch = WriteBatch::default();
+        for relationship in relationships {
+            batch.put(relationship_key(relationship), to_string_pretty(relationship)?);
+        }
+
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    pub fn get_by_key(&self, key: &str) -> Result<Option<PropertyRelationship>, Box<dyn Error>> {
+        match self.db.get(key)? {
+            Some(serialized_relationship) => {
+                let relationship: PropertyRelationship = serde_json::from_slice(&serialized_relationship).map_err(|e| {
+                    eprintln!("❌ Deserialization error for key '{}': {:?}", key, e);
+                    Box::new(e) as Box<dyn Error>
+                })?;
+                Ok(Some(relationship))
+            }
+            None => Ok(None)
+        }
+    }
+
+    pub fn get_all(&self) -> Result<Vec<PropertyRelationship>, Box<dyn Error>> {
+        let mut relationships = Vec::new();
+
+        for item in self.db.iterator(rocksdb::IteratorMode::Start) {
+            match item {
+                Ok((_key, value)) => {
+                    match serde_json::from_slice(&value) {
+                        Ok(relationship) => relationships.push(relationship),
+                        Err(e) => {
+                            eprintln!("❌ Skipping entry due to the deserialization error: {:?}", e);
+                            continue;
+                        }
+                    }
+                }
+
+                Err(e) => {
+                    eprintln!("❌ Error iterating over database: {:?}", e);
+                    return Err(Box::new(e));
+                }
+            }
+        }
+
+        Ok(relationships)
+    }
+
+    pub fn update(&self, key: &str, relationship: &PropertyRelationship) -> Result<(), Box<dyn Error>> {
+        self.create(key, relationship)
+    }
+
+    pub fn delete(&self, key: &str) -> Result<(), Box<dyn Error>> {
+        self.db.delete(key)?;
+        Ok(())
+    }
+}
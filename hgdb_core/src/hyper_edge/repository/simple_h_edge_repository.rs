@@ -1,4 +1,4 @@
-use rocksdb::{DB, Options};
+use rocksdb::{DB, Options, WriteBatch};
 use serde_json::{self, to_string_pretty};
 use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
 use crate::hyper_edge::entity::dual_h_edge::DualHyperEdge;
@@ -38,6 +38,30 @@ impl SimpleHyperEdgeRepository {
         Ok(())
     }
 
+    /// Method to create many SimpleHyperEdges in one write batch, each stored under its id
+    pub fn create_many(&self, edges: &[SimpleHyperEdge<String, String, String>]) -> Result<(), Box<dyn Error>> {
+        let mut batch = WriteBatch::default();
+        for edge in edges {
+            batch.put(&edge.id, to_string_pretty(edge)?);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
+    /// Method to store many SimpleHyperEdges under their ids and delete the `deleted` keys in one write batch,
+    /// so either every change is written or none is
+    pub fn update_many(&self, edges: &[&SimpleHyperEdge<String, String, String>], deleted: &[&str]) -> Result<(), Box<dyn Error>> {
+        let mut batch = WriteBatch::default();
+        for key in deleted {
+            batch.delete(key);
+        }
+        for edge in edges {
+            batch.put(&edge.id, to_string_pretty(edge)?);
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+
     /// Method to retrieve a SimpleHyperEdge by key
     pub fn get_by_key(&self, key: &str) -> Result<Option<SimpleHyperEdge<String, String, String>>, Box<dyn Error>> {
         match self.db.get(key)? {
@@ -90,6 +114,34 @@ impl SimpleHyperEdgeRepository {
         Ok(edges)
     }    
     
+    /// Method to find the first key (in key order) starting with `prefix` for which `accept` holds
+    pub fn find_key<F: Fn(&str) -> bool>(&self, prefix: &str, accept: F) -> Result<Option<String>, Box<dyn Error>> {
+        for item in self.db.iterator(rocksdb::IteratorMode::From(prefix.as_bytes(), rocksdb::Direction::Forward)) {
+            let (key, _value) = item?;
+            let key = String::from_utf8_lossy(&key);
+            if !key.starts_with(prefix) {
+                break;
+            }
+            if accept(&key) {
+                return Ok(Some(key.into_owned()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Method to visit every SimpleHyperEdge in the database without collecting them first
+    pub fn scan<F: FnMut(SimpleHyperEdge<String, String, String>)>(&self, mut visit: F) -> Result<(), Box<dyn Error>> {
+        for item in self.db.iterator(rocksdb::IteratorMode::Start) {
+            let (_key, value) = item?;
+            // Entries of other types (such as dual edges) are skipped like in `get_all`
+            if let Ok(edge) = serde_json::from_slice::<SimpleHyperEdge<String, String, String>>(&value) {
+                visit(edge);
+            }
+        }
+
+        Ok(())
+    }
+
     // method to get the dual edge by key
     pub fn get_dual_by_key(&self, key: &str) -> Result<Option<DualHyperEdge<String, String, String>>, Box<dyn Error>> {
         match self.db.get(key)? {
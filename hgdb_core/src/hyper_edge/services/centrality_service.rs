@@ -0,0 +1,325 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::h_graph_service::{set_node_properties, set_property};
+use crate::hyper_edge::services::sparse::adjacency::WeightedAdjacency;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use crate::hyper_edge::services::sparse::line_graph::{dual_s_line_graph, s_line_graph};
+use crate::hyper_edge::services::sparse::matrix::SparseMatrix;
+use rayon::prelude::*;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+
+/// Whether scores are computed for the nodes or for the hyperedges
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CentralityTarget {
+    Nodes,
+    Edges,
+}
+
+/// Centrality scores sorted from the highest to the lowest (ties by id)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreTable {
+    pub measure: String,
+    pub scores: Vec<(String, f64)>,
+    // position of every id in `scores`
+    positions: HashMap<String, usize>,
+}
+
+impl ScoreTable {
+    pub fn new(measure: &str, mut scores: Vec<(String, f64)>) -> Self {
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let positions = scores.iter().enumerate().map(|(position, (id, _))| (id.clone(), position)).collect();
+        ScoreTable { measure: measure.to_string(), scores, positions }
+    }
+
+    pub fn get(&self, id: &str) -> Option<f64> {
+        self.positions.get(id).map(|&position| self.scores[position].1)
+    }
+
+    // method to get the ids with the highest scores
+    pub fn top(&self, count: usize) -> Vec<&str> {
+        self.scores.iter().take(count).map(|(id, _)| id.as_str()).collect()
+    }
+}
+
+/// Centrality measures over the incidence of a hypergraph. The s-measures run on the s-line graph of the target
+/// (the dual s-line graph for nodes) and, like their graph counterparts, are normalized by the number of vertices.
+/// The per-vertex searches, the matrix products and the matrix-vector products of the power iterations run in
+/// parallel
+pub struct Centrality<'g> {
+    incidence: &'g IncidenceMatrix,
+}
+
+impl<'g> Centrality<'g> {
+    pub fn new(incidence: &'g IncidenceMatrix) -> Self {
+        Centrality { incidence }
+    }
+
+    /// Number of hyperedges of every node, or number of members of every hyperedge
+    pub fn degree(&self, target: CentralityTarget) -> ScoreTable {
+        let (ids, degrees) = match target {
+            CentralityTarget::Nodes => (self.incidence.nodes.ids(), self.incidence.node_degrees()),
+            CentralityTarget::Edges => (self.incidence.edges.ids(), self.incidence.edge_sizes()),
+        };
+        ScoreTable::new("degree", ids.iter().cloned().zip(degrees.into_iter().map(|degree| degree as f64)).collect())
+    }
+
+    /// Brandes betweenness on the s-line graph, normalized by (n - 1)(n - 2) ordered pairs
+    pub fn s_betweenness(&self, target: CentralityTarget, s: usize) -> ScoreTable {
+        let graph = self.line_graph(target, s);
+        let count = graph.index.len();
+
+        let raw = (0..count).into_par_iter()
+            .map(|source| dependencies(&graph.matrix, source))
+            .reduce(|| vec![0.0; count], |mut total, partial| {
+                total.iter_mut().zip(partial).for_each(|(total, value)| *total += value);
+                total
+            });
+        let scale = if count > 2 { 1.0 / ((count - 1) * (count - 2)) as f64 } else { 1.0 };
+
+        self.table(&format!("s{}_betweenness", s), &graph, raw.into_iter().map(|value| value * scale).collect())
+    }
+
+    /// Closeness on the s-line graph: (r - 1) / (sum of distances), scaled by (r - 1) / (n - 1) where r counts the
+    /// vertices reachable from a vertex, so vertices of small components are not favored
+    pub fn s_closeness(&self, target: CentralityTarget, s: usize) -> ScoreTable {
+        let graph = self.line_graph(target, s);
+        let count = graph.index.len();
+
+        let scores = (0..count).into_par_iter()
+            .map(|vertex| {
+                let distances: Vec<usize> = bfs_distances(&graph.matrix, vertex).into_iter().flatten().collect();
+                let (reached, total) = (distances.len(), distances.iter().sum::<usize>());
+                if total == 0 || count < 2 {
+                    return 0.0;
+                }
+                let closeness = (reached - 1) as f64 / total as f64;
+                closeness * (reached - 1) as f64 / (count - 1) as f64
+            })
+            .collect();
+
+        self.table(&format!("s{}_closeness", s), &graph, scores)
+    }
+
+    /// Harmonic centrality on the s-line graph: the sum of 1 / distance over the other vertices, divided by n - 1
+    pub fn s_harmonic(&self, target: CentralityTarget, s: usize) -> ScoreTable {
+        let graph = self.line_graph(target, s);
+        let count = graph.index.len();
+
+        let scores = (0..count).into_par_iter()
+            .map(|vertex| {
+                let total: f64 = bfs_distances(&graph.matrix, vertex).into_iter()
+                    .flatten()
+                    .filter(|&distance| distance > 0)
+                    .map(|distance| 1.0 / distance as f64)
+                    .sum();
+                if count > 1 { total / (count - 1) as f64 } else { 0.0 }
+            })
+            .collect();
+
+        self.table(&format!("s{}_harmonic", s), &graph, scores)
+    }
+
+    /// Linear eigenvector centrality: the Perron vector of the weighted clique expansion (of the dual for edges),
+    /// found by power iteration on A + I. Scores sum to 1
+    pub fn eigenvector(&self, target: CentralityTarget, tolerance: f64, max_iterations: usize) -> Result<ScoreTable, Box<dyn Error>> {
+        let incidence = self.oriented(target);
+        let members = incidence.csc().clone();
+        let unit = members.map_values(|_, _, _| 1.0);
+        let adjacency = unit.transpose().multiply(&members).without_diagonal();
+
+        let scores = power_iteration(incidence.node_count(), tolerance, max_iterations, |x| {
+            let ax = par_mul_vec(&adjacency, x);
+            ax.iter().zip(x).map(|(ax, x)| ax + x).collect()
+        })?;
+
+        Ok(ScoreTable::new("eigenvector", incidence.nodes.ids().iter().cloned().zip(scores).collect()))
+    }
+
+    /// Nonlinear eigenvector centrality after Benson's H-eigenvector centrality: on a k-uniform hypergraph
+    /// x_i^(k-1) is proportional to the sum over the edges of i of the product of the other members' scores. On
+    /// non-uniform hypergraphs every edge contributes the geometric mean of its other members raised to K - 1, with
+    /// K the largest edge size, which keeps the map homogeneous. Iterated as x <- sqrt(x * F(x)) for stability
+    pub fn nonlinear_eigenvector(&self, target: CentralityTarget, tolerance: f64, max_iterations: usize) -> Result<ScoreTable, Box<dyn Error>> {
+        let incidence = self.oriented(target);
+        let order = incidence.edge_sizes().into_iter().max().unwrap_or(2).max(2) as f64 - 1.0;
+
+        let scores = power_iteration(incidence.node_count(), tolerance, max_iterations, |x| {
+            let logs: Vec<f64> = x.iter().map(|value| value.ln()).collect();
+            let log_sums: Vec<f64> = (0..incidence.edge_count())
+                .map(|edge| incidence.edge_nodes(edge).iter().map(|&node| logs[node]).sum())
+                .collect();
+
+            (0..incidence.node_count()).into_par_iter()
+                .map(|node| {
+                    let total: f64 = incidence.node_edges(node).iter()
+                        .filter_map(|&edge| {
+                            let others = incidence.edge_nodes(edge).len() - 1;
+                            if others == 0 {
+                                return None;
+                            }
+                            let mean_log = (log_sums[edge] - logs[node]) / others as f64;
+                            if mean_log.is_nan() {
+                                return None;
+                            }
+                            Some(incidence.edge_weight(edge) * (mean_log * order).exp())
+                        })
+                        .sum();
+                    (x[node] * total.powf(1.0 / order)).sqrt()
+                })
+                .collect()
+        })?;
+
+        Ok(ScoreTable::new("nonlinear_eigenvector", incidence.nodes.ids().iter().cloned().zip(scores).collect()))
+    }
+
+    // incidence whose rows are the target vertices
+    fn oriented(&self, target: CentralityTarget) -> Cow<'g, IncidenceMatrix> {
+        match target {
+            CentralityTarget::Nodes => Cow::Borrowed(self.incidence),
+            CentralityTarget::Edges => Cow::Owned(self.incidence.transpose()),
+        }
+    }
+
+    fn line_graph(&self, target: CentralityTarget, s: usize) -> WeightedAdjacency {
+        match target {
+            CentralityTarget::Nodes => dual_s_line_graph(self.incidence, s),
+            CentralityTarget::Edges => s_line_graph(self.incidence, s),
+        }
+    }
+
+    fn table(&self, measure: &str, graph: &WeightedAdjacency, scores: Vec<f64>) -> ScoreTable {
+        ScoreTable::new(measure, graph.index.ids().iter().cloned().zip(scores).collect())
+    }
+}
+
+// BFS distances from a vertex of an unweighted graph
+fn bfs_distances(graph: &SparseMatrix, start: usize) -> Vec<Option<usize>> {
+    let mut distances = vec![None; graph.rows()];
+    let mut queue = VecDeque::from([start]);
+    distances[start] = Some(0);
+    while let Some(vertex) = queue.pop_front() {
+        let distance = distances[vertex].unwrap_or(0);
+        for &next in graph.row(vertex).0 {
+            if distances[next].is_none() {
+                distances[next] = Some(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    distances
+}
+
+// Brandes: the dependency of `source` on every vertex, from one BFS and a reverse accumulation
+fn dependencies(graph: &SparseMatrix, source: usize) -> Vec<f64> {
+    let count = graph.rows();
+    let mut order = Vec::with_capacity(count);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut paths = vec![0.0; count];
+    let mut distance: Vec<Option<usize>> = vec![None; count];
+    let mut queue = VecDeque::from([source]);
+    paths[source] = 1.0;
+    distance[source] = Some(0);
+
+    while let Some(vertex) = queue.pop_front() {
+        order.push(vertex);
+        let next_distance = distance[vertex].unwrap_or(0) + 1;
+        for &next in graph.row(vertex).0 {
+            if distance[next].is_none() {
+                distance[next] = Some(next_distance);
+                queue.push_back(next);
+            }
+            if distance[next] == Some(next_distance) {
+                paths[next] += paths[vertex];
+                predecessors[next].push(vertex);
+            }
+        }
+    }
+
+    let mut dependency = vec![0.0; count];
+    for &vertex in order.iter().rev() {
+        for &predecessor in &predecessors[vertex] {
+            dependency[predecessor] += paths[predecessor] / paths[vertex] * (1.0 + dependency[vertex]);
+        }
+    }
+    dependency[source] = 0.0;
+    dependency
+}
+
+fn par_mul_vec(matrix: &SparseMatrix, x: &[f64]) -> Vec<f64> {
+    (0..matrix.rows()).into_par_iter()
+        .map(|row| matrix.row_entries(row).map(|(col, value)| value * x[col]).sum())
+        .collect()
+}
+
+// Iterates x <- step(x) / |step(x)|_1 from the uniform vector until the L1 change drops below the tolerance
+fn power_iteration<F>(size: usize, tolerance: f64, max_iterations: usize, step: F) -> Result<Vec<f64>, Box<dyn Error>>
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    if size == 0 {
+        return Ok(Vec::new());
+    }
+    let mut x = vec![1.0 / size as f64; size];
+
+    for _ in 0..max_iterations {
+        let mut next = step(&x);
+        let norm: f64 = next.iter().map(|value| value.abs()).sum();
+        if norm == 0.0 || !norm.is_finite() {
+            return Err("Eigenvector iteration collapsed; the hypergraph has no links".into());
+        }
+        next.iter_mut().for_each(|value| *value /= norm);
+
+        let change: f64 = next.iter().zip(&x).map(|(next, previous)| (next - previous).abs()).sum();
+        x = next;
+        if change < tolerance {
+            return Ok(x);
+        }
+    }
+
+    Err(format!("Eigenvector iteration did not converge in {} iterations", max_iterations).into())
+}
+
+/// Centrality rankings of the named hypergraphs
+pub struct CentralityService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> CentralityService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        CentralityService { repository }
+    }
+
+    /// Builds the incidence of a stored hypergraph, with edge weights from `weight_key` when given
+    pub fn incidence(&self, key: &str, weight_key: Option<&str>) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, weight_key))
+    }
+
+    /// Writes the scores of a table as the `centrality.<measure>` property: into `main_properties` for hyperedge
+    /// scores and into the node properties for node scores
+    pub fn save_scores(&self, key: &str, table: &ScoreTable, target: CentralityTarget) -> Result<(), Box<dyn Error>> {
+        let mut graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let property_key = format!("centrality.{}", table.measure);
+
+        match target {
+            CentralityTarget::Edges => {
+                let scores: HashMap<&str, f64> = table.scores.iter().map(|(id, score)| (id.as_str(), *score)).collect();
+                for edge in graph.hyper_edges.iter_mut() {
+                    if let Some(score) = scores.get(edge.id.as_str()) {
+                        set_property(&mut edge.main_properties, &property_key, score.to_string());
+                    }
+                }
+            }
+            CentralityTarget::Nodes => {
+                set_node_properties(&mut graph, &property_key, table.scores.iter().map(|(id, score)| (id.clone(), score.to_string())));
+            }
+        }
+
+        self.repository.update(key, &graph)?;
+        println!("✅ Saved {} {} scores on '{}'", table.scores.len(), table.measure, key);
+        Ok(())
+    }
+}
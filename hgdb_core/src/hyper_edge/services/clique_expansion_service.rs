@@ -0,0 +1,107 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::repository::relationship_repository::RelationshipRepository;
+use crate::hyper_edge::entity::simple_h_edge::Property;
+use crate::hyper_edge::services::incidence_service::IncidenceService;
+use crate::hyper_edge::services::sparse::adjacency::WeightedAdjacency;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use std::error::Error;
+
+/// How the links of a clique expansion are weighted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CliqueWeighting {
+    /// Every shared hyperedge adds its weight (1 unless a weight key is given), so unweighted links count
+    /// co-occurrences
+    Count,
+    /// Every hyperedge spreads its weight over the other members a node is linked to through it, so a node of
+    /// an undirected edge e gets w(e) / (|e| - 1) per neighbor
+    Normalized,
+}
+
+impl CliqueWeighting {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CliqueWeighting::Count => "count",
+            CliqueWeighting::Normalized => "normalized",
+        }
+    }
+}
+
+/// Turns a stored hypergraph into an ordinary weighted graph. The undirected expansion links every pair of
+/// members of every hyperedge; the directed expansion links every head node of a directed hyperedge to every
+/// tail node of it (undirected hyperedges still give links both ways)
+pub struct CliqueExpansionService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> CliqueExpansionService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        CliqueExpansionService { repository }
+    }
+
+    /// Builds the undirected clique expansion of every stored hyperedge
+    pub fn expand(&self, weighting: CliqueWeighting, weight_key: Option<&str>) -> Result<WeightedAdjacency, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(weight_key)?;
+        Ok(self.compute(&incidence, weighting))
+    }
+
+    /// Builds the head -> tail clique expansion of every stored hyperedge
+    pub fn expand_directed(&self, weighting: CliqueWeighting, weight_key: Option<&str>) -> Result<WeightedAdjacency, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(weight_key)?;
+        Ok(self.compute_directed(&incidence, weighting))
+    }
+
+    /// Computes A = M^T W M without its diagonal, where M is the edge x node membership matrix and W holds the
+    /// contribution of each edge
+    pub fn compute(&self, incidence: &IncidenceMatrix, weighting: CliqueWeighting) -> WeightedAdjacency {
+        let members = incidence.csc().map_values(|_, _, _| 1.0);
+        let sizes = incidence.edge_sizes();
+        let weighted = members.map_values(|edge, _, _| contribution(incidence.edge_weight(edge), sizes[edge].saturating_sub(1), weighting));
+
+        let matrix = members.transpose().multiply(&weighted).without_diagonal();
+        WeightedAdjacency::new(incidence.nodes.clone(), matrix, false)
+    }
+
+    /// Computes A = H^T W T for the directed edges (H and T their head and tail matrices) plus the undirected
+    /// expansion of the undirected edges
+    pub fn compute_directed(&self, incidence: &IncidenceMatrix, weighting: CliqueWeighting) -> WeightedAdjacency {
+        let is_directed = |edge: usize| incidence.is_directed(edge);
+        let heads = incidence.heads().retain(|edge, _, _| is_directed(edge)).map_values(|_, _, _| 1.0);
+        let tails = incidence.tails().retain(|edge, _, _| is_directed(edge));
+        let tail_sizes = tails.row_counts();
+        let weighted_tails = tails.map_values(|edge, _, _| contribution(incidence.edge_weight(edge), tail_sizes[edge], weighting));
+
+        let members = incidence.csc().retain(|edge, _, _| !is_directed(edge)).map_values(|_, _, _| 1.0);
+        let sizes = members.row_counts();
+        let weighted_members = members.map_values(|edge, _, _| contribution(incidence.edge_weight(edge), sizes[edge].saturating_sub(1), weighting));
+
+        let matrix = heads.transpose().multiply(&weighted_tails)
+            .add(&members.transpose().multiply(&weighted_members))
+            .without_diagonal();
+        WeightedAdjacency::new(incidence.nodes.clone(), matrix, true)
+    }
+
+    /// Writes the links of an expansion as pairwise relationships tagged with how they were built, returning how
+    /// many were written
+    pub fn save_relationships(&self, adjacency: &WeightedAdjacency, weighting: CliqueWeighting, relationships: &RelationshipRepository) -> Result<usize, Box<dyn Error>> {
+        let expansion = if adjacency.directed { "clique_directed" } else { "clique" };
+        let extra = vec![
+            Property { key: "expansion".to_string(), value: vec![expansion.to_string()] },
+            Property { key: "weighting".to_string(), value: vec![weighting.as_str().to_string()] },
+        ];
+
+        let pairs = adjacency.to_relationships(&extra);
+        relationships.create_all(&pairs)?;
+        println!("✅ Saved {} relationships from the {} expansion", pairs.len(), expansion);
+        Ok(pairs.len())
+    }
+}
+
+// weight one edge adds to each of its links
+fn contribution(weight: f64, neighbors: usize, weighting: CliqueWeighting) -> f64 {
+    match weighting {
+        CliqueWeighting::Count => weight,
+        CliqueWeighting::Normalized if neighbors > 0 => weight / neighbors as f64,
+        CliqueWeighting::Normalized => 0.0,
+    }
+}
+
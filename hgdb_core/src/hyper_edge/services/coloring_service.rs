@@ -0,0 +1,266 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::Property;
+use crate::hyper_edge::services::h_graph_service::{edge_members, set_property};
+use crate::hyper_edge::services::incidence_service::IncidenceService;
+use crate::hyper_edge::services::sparse::incidence::{IdIndex, IncidenceMatrix};
+use std::collections::HashSet;
+use std::error::Error;
+
+/// `main_properties` key marking whether a hyperedge belongs to the saved matching
+pub const MATCHING_KEY: &str = "matching";
+
+/// Weak colorings forbid monochromatic hyperedges; strong colorings give every member of a hyperedge its own color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColoringKind {
+    Weak,
+    Strong,
+}
+
+impl ColoringKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ColoringKind::Weak => "weak",
+            ColoringKind::Strong => "strong",
+        }
+    }
+}
+
+/// Weakly independent node sets contain no whole hyperedge; strongly independent ones hold at most one member of
+/// every hyperedge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Independence {
+    Weak,
+    Strong,
+}
+
+/// A color (0..) for every node of an incidence
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coloring {
+    pub kind: ColoringKind,
+    nodes: IdIndex,
+    colors: Vec<usize>,
+}
+
+impl Coloring {
+    pub fn color_of(&self, id: &str) -> Option<usize> {
+        self.nodes.position(id).map(|node| self.colors[node])
+    }
+
+    pub fn color_count(&self) -> usize {
+        self.colors.iter().max().map_or(0, |color| color + 1)
+    }
+
+    /// The nodes of every color
+    pub fn classes(&self) -> Vec<Vec<String>> {
+        let mut classes = vec![Vec::new(); self.color_count()];
+        for (node, &color) in self.colors.iter().enumerate() {
+            classes[color].push(self.nodes.id(node).to_string());
+        }
+        classes
+    }
+
+    /// Checks the coloring against the hyperedges of an incidence. Hyperedges with a single member can never be
+    /// weakly colored and are not checked
+    pub fn is_valid(&self, incidence: &IncidenceMatrix) -> bool {
+        (0..incidence.edge_count()).all(|edge| {
+            let mut colors: Vec<usize> = incidence.edge_nodes(edge).iter().filter_map(|&node| self.color_of(incidence.nodes.id(node))).collect();
+            let size = colors.len();
+            colors.sort_unstable();
+            colors.dedup();
+            match self.kind {
+                ColoringKind::Weak => size < 2 || colors.len() > 1,
+                ColoringKind::Strong => colors.len() == size,
+            }
+        })
+    }
+}
+
+/// A set of pairwise disjoint hyperedges and their total weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matching {
+    pub edges: Vec<String>,
+    pub weight: f64,
+}
+
+/// Coloring, independent set and matching heuristics over the incidence of a hypergraph. Direction is ignored:
+/// head and tail members count alike
+pub struct ColoringEngine<'g> {
+    incidence: &'g IncidenceMatrix,
+}
+
+impl<'g> ColoringEngine<'g> {
+    pub fn new(incidence: &'g IncidenceMatrix) -> Self {
+        ColoringEngine { incidence }
+    }
+
+    /// Greedy coloring, highest degree first, giving every node the smallest color that keeps the coloring valid
+    pub fn color(&self, kind: ColoringKind) -> Coloring {
+        let incidence = self.incidence;
+        let mut colors: Vec<Option<usize>> = vec![None; incidence.node_count()];
+
+        for node in self.by_degree(true) {
+            let mut blocked: Vec<usize> = Vec::new();
+            for &edge in incidence.node_edges(node) {
+                let others = incidence.edge_nodes(edge).iter().filter(|&&other| other != node);
+                match kind {
+                    // Only the last uncolored member of an edge can make it monochromatic
+                    ColoringKind::Weak => {
+                        let others: Vec<Option<usize>> = others.map(|&other| colors[other]).collect();
+                        if let Some(Some(first)) = others.first() {
+                            if others.iter().all(|color| *color == Some(*first)) {
+                                blocked.push(*first);
+                            }
+                        }
+                    }
+                    ColoringKind::Strong => blocked.extend(others.filter_map(|&other| colors[other])),
+                }
+            }
+            colors[node] = (0..).find(|color| !blocked.contains(color));
+        }
+
+        Coloring { kind, nodes: incidence.nodes.clone(), colors: colors.into_iter().map(Option::unwrap_or_default).collect() }
+    }
+
+    /// A maximal independent set: nodes are added in index order whenever they keep the set independent
+    pub fn maximal_independent_set(&self, independence: Independence) -> Vec<String> {
+        self.independent_set((0..self.incidence.node_count()).collect(), independence)
+    }
+
+    /// An approximate maximum independent set: the same greedy pass, taking the nodes of lowest degree first
+    pub fn maximum_independent_set(&self, independence: Independence) -> Vec<String> {
+        self.independent_set(self.by_degree(false), independence)
+    }
+
+    /// A maximal matching built greedily, heaviest and then smallest hyperedges first; for hyperedges of at most
+    /// k members its weight is within a factor k of the maximum
+    pub fn matching(&self) -> Matching {
+        let incidence = self.incidence;
+        let mut edges: Vec<usize> = (0..incidence.edge_count()).filter(|&edge| !incidence.edge_nodes(edge).is_empty()).collect();
+        edges.sort_by(|&a, &b| {
+            incidence.edge_weight(b).total_cmp(&incidence.edge_weight(a))
+                .then(incidence.edge_nodes(a).len().cmp(&incidence.edge_nodes(b).len()))
+                .then(a.cmp(&b))
+        });
+
+        let mut covered = vec![false; incidence.node_count()];
+        let mut chosen = Vec::new();
+        for edge in edges {
+            let members = incidence.edge_nodes(edge);
+            if members.iter().any(|&node| covered[node]) {
+                continue;
+            }
+            for &node in members {
+                covered[node] = true;
+            }
+            chosen.push(edge);
+        }
+
+        chosen.sort_unstable();
+        Matching {
+            weight: chosen.iter().map(|&edge| incidence.edge_weight(edge)).sum(),
+            edges: chosen.iter().map(|&edge| incidence.edges.id(edge).to_string()).collect(),
+        }
+    }
+
+    fn independent_set(&self, order: Vec<usize>, independence: Independence) -> Vec<String> {
+        let incidence = self.incidence;
+        let mut inside = vec![false; incidence.node_count()];
+
+        for node in order {
+            let allowed = incidence.node_edges(node).iter().all(|&edge| {
+                let mut others = incidence.edge_nodes(edge).iter().filter(|&&other| other != node);
+                match independence {
+                    Independence::Weak => others.any(|&other| !inside[other]),
+                    Independence::Strong => others.all(|&other| !inside[other]),
+                }
+            });
+            inside[node] = allowed;
+        }
+
+        (0..incidence.node_count())
+            .filter(|&node| inside[node])
+            .map(|node| incidence.nodes.id(node).to_string())
+            .collect()
+    }
+
+    // Node positions by degree, ties by position
+    fn by_degree(&self, descending: bool) -> Vec<usize> {
+        let degrees = self.incidence.node_degrees();
+        let mut order: Vec<usize> = (0..degrees.len()).collect();
+        if descending {
+            order.sort_by_key(|&node| (std::cmp::Reverse(degrees[node]), node));
+        } else {
+            order.sort_by_key(|&node| (degrees[node], node));
+        }
+        order
+    }
+}
+
+/// Coloring, independent sets and matchings of the stored hyperedges
+pub struct ColoringService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> ColoringService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        ColoringService { repository }
+    }
+
+    pub fn coloring(&self, kind: ColoringKind) -> Result<Coloring, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(None)?;
+        Ok(ColoringEngine::new(&incidence).color(kind))
+    }
+
+    pub fn maximal_independent_set(&self, independence: Independence) -> Result<Vec<String>, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(None)?;
+        Ok(ColoringEngine::new(&incidence).maximal_independent_set(independence))
+    }
+
+    pub fn maximum_independent_set(&self, independence: Independence) -> Result<Vec<String>, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(None)?;
+        Ok(ColoringEngine::new(&incidence).maximum_independent_set(independence))
+    }
+
+    /// Greedy matching with edge weights read from the `weight_key` entry of `main_properties`
+    pub fn matching(&self, weight_key: Option<&str>) -> Result<Matching, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(weight_key)?;
+        Ok(ColoringEngine::new(&incidence).matching())
+    }
+
+    /// Stores the colors of the members of every hyperedge as `node:color` values of its `coloring.<kind>` property,
+    /// in one write batch. Hyperedges are expected to be stored under their id. Fails without writing anything when a
+    /// member has no color, e.g. because its hyperedge was added after the coloring was computed
+    pub fn save_coloring(&self, coloring: &Coloring) -> Result<(), Box<dyn Error>> {
+        let property_key = format!("coloring.{}", coloring.kind.as_str());
+        let mut edges = self.repository.get_all()?;
+        for edge in edges.iter_mut() {
+            let values = edge_members(edge).into_iter()
+                .map(|node| match coloring.color_of(&node) {
+                    Some(color) => Ok(format!("{}:{}", node, color)),
+                    None => Err(format!("Node '{}' of hyperedge '{}' has no {} color", node, edge.id, coloring.kind.as_str())),
+                })
+                .collect::<Result<Vec<String>, String>>()?;
+            match edge.main_properties.iter_mut().find(|property| property.key == property_key) {
+                Some(property) => property.value = values,
+                None => edge.main_properties.push(Property { key: property_key.clone(), value: values }),
+            }
+        }
+        self.repository.update_many(&edges.iter().collect::<Vec<_>>(), &[])?;
+        println!("✅ Saved {} coloring ({} colors) on {} hyperedges", coloring.kind.as_str(), coloring.color_count(), edges.len());
+        Ok(())
+    }
+
+    /// Marks every hyperedge with a `matching` property, in one write batch: "true" for the edges of the matching,
+    /// "false" otherwise
+    pub fn save_matching(&self, matching: &Matching) -> Result<(), Box<dyn Error>> {
+        let chosen: HashSet<&str> = matching.edges.iter().map(String::as_str).collect();
+        let mut edges = self.repository.get_all()?;
+        for edge in edges.iter_mut() {
+            let matched = chosen.contains(edge.id.as_str());
+            set_property(&mut edge.main_properties, MATCHING_KEY, matched.to_string());
+        }
+        self.repository.update_many(&edges.iter().collect::<Vec<_>>(), &[])?;
+        println!("✅ Saved matching of {} hyperedges", matching.edges.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,486 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::h_graph_service::{set_node_properties, set_property};
+use crate::hyper_edge::services::sparse::incidence::{IdIndex, IncidenceMatrix};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+/// Node property holding the community id
+pub const COMMUNITY_KEY: &str = "community";
+
+/// When a hyperedge counts as inside a community for the modularity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeContribution {
+    /// Every member of the hyperedge is in the same community
+    Strict,
+    /// More than half of the members are in the same community
+    Majority,
+}
+
+impl EdgeContribution {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EdgeContribution::Strict => "strict",
+            EdgeContribution::Majority => "majority",
+        }
+    }
+}
+
+/// An assignment of every node of an incidence to a community. Community ids are numbered 0.. in the order of the
+/// first node of each community
+#[derive(Debug, Clone, PartialEq)]
+pub struct Partition {
+    nodes: IdIndex,
+    labels: Vec<usize>,
+    count: usize,
+}
+
+impl Partition {
+    /// Builds a partition from arbitrary labels, one per node position
+    pub fn from_labels(nodes: &IdIndex, labels: &[usize]) -> Self {
+        let mut renumbered: HashMap<usize, usize> = HashMap::new();
+        let labels: Vec<usize> = labels.iter()
+            .map(|label| {
+                let next = renumbered.len();
+                *renumbered.entry(*label).or_insert(next)
+            })
+            .collect();
+        Partition { nodes: nodes.clone(), labels, count: renumbered.len() }
+    }
+
+    /// Every node in its own community
+    pub fn singletons(incidence: &IncidenceMatrix) -> Self {
+        let labels: Vec<usize> = (0..incidence.node_count()).collect();
+        Partition::from_labels(&incidence.nodes, &labels)
+    }
+
+    /// Builds a partition from groups of node ids. Unknown ids are ignored and nodes left out of every group get a
+    /// community of their own
+    pub fn from_groups(incidence: &IncidenceMatrix, groups: &[Vec<&str>]) -> Self {
+        let count = incidence.node_count();
+        let mut labels: Vec<usize> = (groups.len()..groups.len() + count).collect();
+        for (group, members) in groups.iter().enumerate() {
+            for node in members.iter().filter_map(|id| incidence.nodes.position(id)) {
+                labels[node] = group;
+            }
+        }
+        Partition::from_labels(&incidence.nodes, &labels)
+    }
+
+    /// The community of every node position
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn community_of(&self, id: &str) -> Option<usize> {
+        self.nodes.position(id).map(|node| self.labels[node])
+    }
+
+    /// The communities as lists of node ids
+    pub fn communities(&self) -> Vec<Vec<String>> {
+        let mut communities = vec![Vec::new(); self.count];
+        for (node, &label) in self.labels.iter().enumerate() {
+            communities[label].push(self.nodes.id(node).to_string());
+        }
+        communities
+    }
+}
+
+/// Hypergraph modularity (Kaminski et al.): the weight of the hyperedges inside a community minus the weight
+/// expected when every hyperedge of size d draws its d members independently, each node with probability
+/// proportional to its degree. Degrees and edge counts use the edge weights; direction is ignored and empty
+/// hyperedges do not count
+pub struct HypergraphModularity<'g> {
+    incidence: &'g IncidenceMatrix,
+    contribution: EdgeContribution,
+    degrees: Vec<f64>,
+    total_volume: f64,
+    total_weight: f64,
+    // Total edge weight of every edge size
+    size_weights: Vec<(usize, f64)>,
+    ln_factorial: Vec<f64>,
+}
+
+impl<'g> HypergraphModularity<'g> {
+    pub fn new(incidence: &'g IncidenceMatrix, contribution: EdgeContribution) -> Self {
+        let degrees = incidence.weighted_node_degrees();
+        let mut size_weights: BTreeMap<usize, f64> = BTreeMap::new();
+        for (edge, size) in incidence.edge_sizes().into_iter().enumerate() {
+            if size > 0 {
+                *size_weights.entry(size).or_default() += incidence.edge_weight(edge);
+            }
+        }
+        let largest = size_weights.keys().next_back().copied().unwrap_or(0);
+        let mut ln_factorial = vec![0.0; largest + 1];
+        for n in 1..=largest {
+            ln_factorial[n] = ln_factorial[n - 1] + (n as f64).ln();
+        }
+
+        HypergraphModularity {
+            incidence,
+            contribution,
+            total_volume: degrees.iter().sum(),
+            total_weight: size_weights.values().sum(),
+            degrees,
+            size_weights: size_weights.into_iter().collect(),
+            ln_factorial,
+        }
+    }
+
+    /// The modularity of a partition of the nodes of the incidence
+    pub fn modularity(&self, partition: &Partition) -> f64 {
+        if self.total_weight <= 0.0 {
+            return 0.0;
+        }
+        let labels = partition.labels();
+
+        let observed: f64 = (0..self.incidence.edge_count())
+            .filter(|&edge| {
+                let members = self.incidence.edge_nodes(edge);
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for &node in members {
+                    *counts.entry(labels[node]).or_default() += 1;
+                }
+                !members.is_empty() && self.qualifies(members.len(), counts.values().copied().max().unwrap_or(0))
+            })
+            .map(|edge| self.incidence.edge_weight(edge))
+            .sum();
+
+        let mut volumes = vec![0.0; partition.count()];
+        for (node, &label) in labels.iter().enumerate() {
+            volumes[label] += self.degrees[node];
+        }
+        let expected: f64 = volumes.iter().map(|&volume| self.expected(volume)).sum();
+
+        (observed - expected) / self.total_weight
+    }
+
+    /// Louvain-style optimization: nodes move to the neighboring community that improves the modularity most until
+    /// no move helps, then whole communities are merged the same way, and both phases repeat until stable
+    pub fn louvain(&self) -> Partition {
+        let mut state = LouvainState::new(self);
+
+        loop {
+            let moved = state.move_nodes();
+            let merged = state.merge_communities();
+            if !moved && !merged {
+                break;
+            }
+        }
+
+        Partition::from_labels(&self.incidence.nodes, &state.labels)
+    }
+
+    fn qualifies(&self, size: usize, largest: usize) -> bool {
+        match self.contribution {
+            EdgeContribution::Strict => largest == size,
+            EdgeContribution::Majority => 2 * largest > size,
+        }
+    }
+
+    // Expected weight of the hyperedges falling inside a community of the given volume
+    fn expected(&self, volume: f64) -> f64 {
+        if self.total_volume <= 0.0 {
+            return 0.0;
+        }
+        let share = (volume / self.total_volume).clamp(0.0, 1.0);
+        self.size_weights.iter()
+            .map(|&(size, weight)| {
+                let probability: f64 = (1..=size)
+                    .filter(|&inside| self.qualifies(size, inside))
+                    .map(|inside| self.binomial(size, inside, share))
+                    .sum();
+                weight * probability
+            })
+            .sum()
+    }
+
+    // P(Bin(n, p) = k), in log space so large hyperedges do not overflow
+    fn binomial(&self, n: usize, k: usize, p: f64) -> f64 {
+        if p <= 0.0 {
+            return if k == 0 { 1.0 } else { 0.0 };
+        }
+        if p >= 1.0 {
+            return if k == n { 1.0 } else { 0.0 };
+        }
+        let ln_choose = self.ln_factorial[n] - self.ln_factorial[k] - self.ln_factorial[n - k];
+        (ln_choose + k as f64 * p.ln() + (n - k) as f64 * (1.0 - p).ln()).exp()
+    }
+}
+
+// Modularity gains below this are treated as no gain, so rounding noise does not cause endless moves
+const GAIN_EPSILON: f64 = 1e-12;
+
+// Incremental state of the Louvain optimization: the community of every node, the volume of every community and,
+// for every hyperedge, how many of its members each community holds
+struct LouvainState<'m, 'g> {
+    modularity: &'m HypergraphModularity<'g>,
+    labels: Vec<usize>,
+    volumes: Vec<f64>,
+    counts: Vec<HashMap<usize, usize>>,
+}
+
+impl<'m, 'g> LouvainState<'m, 'g> {
+    fn new(modularity: &'m HypergraphModularity<'g>) -> Self {
+        let incidence = modularity.incidence;
+        let counts = (0..incidence.edge_count())
+            .map(|edge| incidence.edge_nodes(edge).iter().map(|&node| (node, 1)).collect())
+            .collect();
+        LouvainState {
+            modularity,
+            labels: (0..incidence.node_count()).collect(),
+            volumes: modularity.degrees.clone(),
+            counts,
+        }
+    }
+
+    // Local moving phase; returns whether any node moved
+    fn move_nodes(&mut self) -> bool {
+        let mut moved = false;
+        loop {
+            let mut changed = false;
+            for node in 0..self.labels.len() {
+                if let Some(target) = self.best_target(&[node]) {
+                    self.apply(&[node], target);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return moved;
+            }
+            moved = true;
+        }
+    }
+
+    // Aggregation phase: merges whole communities while that helps; returns whether any merge happened
+    fn merge_communities(&mut self) -> bool {
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); self.labels.len()];
+        for (node, &label) in self.labels.iter().enumerate() {
+            members[label].push(node);
+        }
+
+        let mut merged = false;
+        loop {
+            let mut changed = false;
+            for community in 0..members.len() {
+                if members[community].is_empty() {
+                    continue;
+                }
+                if let Some(target) = self.best_target(&members[community]) {
+                    let moving = std::mem::take(&mut members[community]);
+                    self.apply(&moving, target);
+                    members[target].extend(moving);
+                    changed = true;
+                }
+            }
+            if !changed {
+                return merged;
+            }
+            merged = true;
+        }
+    }
+
+    // The neighboring community that gains most from receiving the given nodes (all in the same community)
+    fn best_target(&self, moving: &[usize]) -> Option<usize> {
+        let incidence = self.modularity.incidence;
+        let from = self.labels[moving[0]];
+        let mut candidates: Vec<usize> = moving.iter()
+            .flat_map(|&node| incidence.node_edges(node).iter())
+            .flat_map(|&edge| self.counts[edge].keys().copied())
+            .filter(|&community| community != from)
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut best: Option<(usize, f64)> = None;
+        for target in candidates {
+            let gain = self.gain(moving, target);
+            if gain > GAIN_EPSILON && best.is_none_or(|(_, best_gain)| gain > best_gain + GAIN_EPSILON) {
+                best = Some((target, gain));
+            }
+        }
+        best.map(|(target, _)| target)
+    }
+
+    // Modularity change of moving the given nodes (all in the same community) into `target`
+    fn gain(&self, moving: &[usize], target: usize) -> f64 {
+        let modularity = self.modularity;
+        let from = self.labels[moving[0]];
+
+        let mut moved_members: HashMap<usize, usize> = HashMap::new();
+        for &node in moving {
+            for &edge in modularity.incidence.node_edges(node) {
+                *moved_members.entry(edge).or_default() += 1;
+            }
+        }
+        let observed: f64 = moved_members.iter()
+            .map(|(&edge, &moved)| {
+                let size = modularity.incidence.edge_nodes(edge).len();
+                let counts = &self.counts[edge];
+                let before = counts.values().copied().max().unwrap_or(0);
+                let after = counts.iter()
+                    .map(|(&community, &count)| match community {
+                        community if community == from => count - moved,
+                        community if community == target => count + moved,
+                        _ => count,
+                    })
+                    .chain(std::iter::once(if counts.contains_key(&target) { 0 } else { moved }))
+                    .max()
+                    .unwrap_or(0);
+                let weight = modularity.incidence.edge_weight(edge);
+                match (modularity.qualifies(size, before), modularity.qualifies(size, after)) {
+                    (false, true) => weight,
+                    (true, false) => -weight,
+                    _ => 0.0,
+                }
+            })
+            .sum();
+
+        let volume: f64 = moving.iter().map(|&node| modularity.degrees[node]).sum();
+        let (from_volume, target_volume) = (self.volumes[from], self.volumes[target]);
+        let expected = modularity.expected(from_volume - volume) + modularity.expected(target_volume + volume)
+            - modularity.expected(from_volume) - modularity.expected(target_volume);
+
+        (observed - expected) / modularity.total_weight
+    }
+
+    fn apply(&mut self, moving: &[usize], target: usize) {
+        let incidence = self.modularity.incidence;
+        for &node in moving {
+            let from = self.labels[node];
+            self.labels[node] = target;
+            self.volumes[from] -= self.modularity.degrees[node];
+            self.volumes[target] += self.modularity.degrees[node];
+            for &edge in incidence.node_edges(node) {
+                let counts = &mut self.counts[edge];
+                if let Some(count) = counts.get_mut(&from) {
+                    *count -= 1;
+                    if *count == 0 {
+                        counts.remove(&from);
+                    }
+                }
+                *counts.entry(target).or_default() += 1;
+            }
+        }
+    }
+}
+
+/// Hypergraph label propagation: every node takes the label most of its hyperedges carry (weighted by the edge
+/// weight), where a hyperedge carries the most frequent label among its members. Nodes are updated one at a time,
+/// ties keep the current label or else pick the smallest, and the process stops once no label changes
+pub fn label_propagation(incidence: &IncidenceMatrix, max_iterations: usize) -> Partition {
+    let mut labels: Vec<usize> = (0..incidence.node_count()).collect();
+
+    for _ in 0..max_iterations {
+        let mut changed = false;
+        for node in 0..labels.len() {
+            let mut votes: BTreeMap<usize, f64> = BTreeMap::new();
+            for &edge in incidence.node_edges(node) {
+                let mut members: BTreeMap<usize, usize> = BTreeMap::new();
+                for &member in incidence.edge_nodes(edge) {
+                    *members.entry(labels[member]).or_default() += 1;
+                }
+                // Smallest label among the most frequent ones
+                if let Some((&label, _)) = members.iter().rev().max_by_key(|(_, &count)| count) {
+                    *votes.entry(label).or_default() += incidence.edge_weight(edge);
+                }
+            }
+
+            let Some(best) = votes.values().copied().reduce(f64::max) else {
+                continue;
+            };
+            let current = labels[node];
+            if votes.get(&current).is_some_and(|&vote| vote >= best) {
+                continue;
+            }
+            if let Some((&label, _)) = votes.iter().find(|(_, &vote)| vote >= best) {
+                labels[node] = label;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    Partition::from_labels(&incidence.nodes, &labels)
+}
+
+/// Community detection over the named hypergraphs
+pub struct CommunityService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> CommunityService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        CommunityService { repository }
+    }
+
+    /// Builds the incidence of a stored hypergraph, with edge weights from `weight_key` when given
+    pub fn incidence(&self, key: &str, weight_key: Option<&str>) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, weight_key))
+    }
+
+    pub fn louvain(&self, key: &str, weight_key: Option<&str>, contribution: EdgeContribution) -> Result<Partition, Box<dyn Error>> {
+        let incidence = self.incidence(key, weight_key)?;
+        Ok(HypergraphModularity::new(&incidence, contribution).louvain())
+    }
+
+    pub fn label_propagation(&self, key: &str, weight_key: Option<&str>, max_iterations: usize) -> Result<Partition, Box<dyn Error>> {
+        Ok(label_propagation(&self.incidence(key, weight_key)?, max_iterations))
+    }
+
+    /// The modularity of a partition given as groups of node ids (nodes left out form their own communities)
+    pub fn modularity(&self, key: &str, groups: &[Vec<&str>], weight_key: Option<&str>, contribution: EdgeContribution) -> Result<f64, Box<dyn Error>> {
+        let incidence = self.incidence(key, weight_key)?;
+        Ok(HypergraphModularity::new(&incidence, contribution).modularity(&Partition::from_groups(&incidence, groups)))
+    }
+
+    /// Reads back the partition stored in the `community` node property; nodes without it form their own
+    /// communities
+    pub fn stored_partition(&self, key: &str) -> Result<Partition, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let incidence = IncidenceMatrix::from_graph(&graph, None);
+
+        let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for node in &graph.hyper_nodes {
+            let community = node.properties.iter()
+                .find(|property| property.key == COMMUNITY_KEY)
+                .and_then(|property| property.value.first());
+            if let Some(community) = community {
+                groups.entry(community.as_str()).or_default().push(node.id.as_str());
+            }
+        }
+        Ok(Partition::from_groups(&incidence, &groups.into_values().collect::<Vec<_>>()))
+    }
+
+    /// Stores the community of every node in its `community` property and the modularity of the partition as the
+    /// `modularity.<contribution>` property of the graph. Returns the modularity
+    pub fn save_communities(&self, key: &str, partition: &Partition, weight_key: Option<&str>, contribution: EdgeContribution) -> Result<f64, Box<dyn Error>> {
+        let mut graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let incidence = IncidenceMatrix::from_graph(&graph, weight_key);
+        let labels: Vec<usize> = incidence.nodes.ids().iter()
+            .map(|id| partition.community_of(id).ok_or_else(|| format!("Node '{}' is not in the partition", id)))
+            .collect::<Result<_, _>>()?;
+        let partition = Partition::from_labels(&incidence.nodes, &labels);
+        let modularity = HypergraphModularity::new(&incidence, contribution).modularity(&partition);
+
+        let communities: Vec<(String, String)> = incidence.nodes.ids().iter()
+            .zip(partition.labels())
+            .map(|(id, label)| (id.clone(), label.to_string()))
+            .collect();
+        set_node_properties(&mut graph, COMMUNITY_KEY, communities);
+        set_property(&mut graph.properties, &format!("modularity.{}", contribution.as_str()), modularity.to_string());
+
+        self.repository.update(key, &graph)?;
+        println!("✅ Saved {} communities of '{}' (modularity {:.4})", partition.count(), key, modularity);
+        Ok(modularity)
+    }
+}
@@ -0,0 +1,198 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::h_graph_service::{set_node_properties, set_property};
+use crate::hyper_edge::services::sparse::incidence::{IdIndex, IncidenceMatrix};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::error::Error;
+
+// Graph properties recording where an extracted core comes from
+pub const CORE_OF_KEY: &str = "core_of";
+pub const CORE_K_KEY: &str = "core.k";
+pub const CORE_M_KEY: &str = "core.m";
+
+/// Core numbers of the nodes of a hypergraph. In the k-core every node lies in at least k hyperedges of the core,
+/// and a hyperedge stays in the core only while all its members do. In the (k,m)-core hyperedges shrink to their
+/// surviving members instead and stay while at least m of them survive. The core number of a node is the largest
+/// k whose core contains it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoreDecomposition {
+    pub m: Option<usize>,
+    nodes: IdIndex,
+    core_numbers: Vec<usize>,
+}
+
+impl CoreDecomposition {
+    /// Decomposition into k-cores
+    pub fn k_cores(incidence: &IncidenceMatrix) -> Self {
+        Self::peel(incidence, None)
+    }
+
+    /// Decomposition into (k,m)-cores for a fixed m
+    pub fn km_cores(incidence: &IncidenceMatrix, m: usize) -> Self {
+        Self::peel(incidence, Some(m.max(1)))
+    }
+
+    // Removes the node of smallest remaining degree until none is left; the level reached when a node goes is
+    // its core number (Batagelj-Zaversnik, with hyperedges dying once they keep too few members)
+    fn peel(incidence: &IncidenceMatrix, m: Option<usize>) -> Self {
+        let sizes = incidence.edge_sizes();
+        let required: Vec<usize> = sizes.iter().map(|&size| m.unwrap_or(size)).collect();
+        let mut surviving = sizes.clone();
+        let mut alive: Vec<bool> = sizes.iter().zip(&required).map(|(&size, &required)| size > 0 && size >= required).collect();
+
+        let mut degrees: Vec<usize> = (0..incidence.node_count())
+            .map(|node| incidence.node_edges(node).iter().filter(|&&edge| alive[edge]).count())
+            .collect();
+        let mut removed = vec![false; incidence.node_count()];
+        let mut core_numbers = vec![0; incidence.node_count()];
+        let mut heap: BinaryHeap<Reverse<(usize, usize)>> = degrees.iter().enumerate().map(|(node, &degree)| Reverse((degree, node))).collect();
+        let mut level = 0;
+
+        while let Some(Reverse((degree, node))) = heap.pop() {
+            if removed[node] || degree != degrees[node] {
+                continue;
+            }
+            removed[node] = true;
+            level = level.max(degree);
+            core_numbers[node] = level;
+
+            for &edge in incidence.node_edges(node) {
+                surviving[edge] -= 1;
+                if !alive[edge] || surviving[edge] >= required[edge] {
+                    continue;
+                }
+                alive[edge] = false;
+                for &member in incidence.edge_nodes(edge) {
+                    if !removed[member] && degrees[member] > 0 {
+                        degrees[member] -= 1;
+                        heap.push(Reverse((degrees[member], member)));
+                    }
+                }
+            }
+        }
+
+        CoreDecomposition { m, nodes: incidence.nodes.clone(), core_numbers }
+    }
+
+    pub fn core_number(&self, id: &str) -> Option<usize> {
+        self.nodes.position(id).map(|node| self.core_numbers[node])
+    }
+
+    /// (node id, core number) pairs in node order
+    pub fn core_numbers(&self) -> Vec<(String, usize)> {
+        self.nodes.ids().iter().cloned().zip(self.core_numbers.iter().copied()).collect()
+    }
+
+    /// The largest k with a non-empty core
+    pub fn max_core(&self) -> usize {
+        self.core_numbers.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The ids of the nodes of the k-core (or (k,m)-core)
+    pub fn core_nodes(&self, k: usize) -> Vec<String> {
+        self.core_numbers.iter().enumerate()
+            .filter(|(_, &core)| core >= k)
+            .map(|(node, _)| self.nodes.id(node).to_string())
+            .collect()
+    }
+
+    /// Builds the core of `graph` (the graph this decomposition was computed on) for the given k: its nodes, and its
+    /// hyperedges restricted to them, keeping ids, properties and head/tail roles
+    pub fn extract(&self, graph: &HyperGraph<String, String, String>, k: usize) -> HyperGraph<String, String, String> {
+        let members: HashSet<String> = self.core_nodes(k).into_iter().collect();
+        let keep = |nodes: &[String]| -> Vec<String> { nodes.iter().filter(|node| members.contains(*node)).cloned().collect() };
+
+        let hyper_edges: Vec<SimpleHyperEdge<String, String, String>> = graph.hyper_edges.iter()
+            .filter_map(|edge| {
+                let all: HashSet<&String> = edge.head_hyper_nodes.iter().chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter())).collect();
+                let kept = all.iter().filter(|node| members.contains(**node)).count();
+                let survives = match self.m {
+                    None => kept == all.len() && kept > 0,
+                    Some(m) => kept >= m,
+                };
+                survives.then(|| SimpleHyperEdge {
+                    head_hyper_nodes: Box::new(keep(&edge.head_hyper_nodes)),
+                    tail_hyper_nodes: edge.tail_hyper_nodes.as_ref().map(|tail| Box::new(keep(tail))),
+                    ..edge.clone()
+                })
+            })
+            .collect();
+
+        // Nodes only referenced by edges are listed too, so the core keeps every member explicitly
+        let mut hyper_nodes: Vec<HyperNode<String, String, String>> = graph.hyper_nodes.iter().filter(|node| members.contains(&node.id)).cloned().collect();
+        let listed: HashSet<String> = hyper_nodes.iter().map(|node| node.id.clone()).collect();
+        for id in self.core_nodes(k).into_iter().filter(|id| !listed.contains(id)) {
+            hyper_nodes.push(HyperNode { id, properties: Vec::new() });
+        }
+
+        HyperGraph { hyper_nodes, hyper_edges, ..graph.clone() }
+    }
+}
+
+/// k-core and (k,m)-core decomposition of the named hypergraphs
+pub struct CoreService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> CoreService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        CoreService { repository }
+    }
+
+    /// Core numbers of a stored hypergraph: k-cores when `m` is None, (k,m)-cores otherwise
+    pub fn decompose(&self, key: &str, m: Option<usize>) -> Result<CoreDecomposition, Box<dyn Error>> {
+        let graph = self.graph(key)?;
+        Ok(Self::decomposition(&graph, m))
+    }
+
+    /// Stores the core numbers in the `core_number` node property (`core_number.m<m>` for (k,m)-cores)
+    pub fn save_core_numbers(&self, key: &str, m: Option<usize>) -> Result<CoreDecomposition, Box<dyn Error>> {
+        let mut graph = self.graph(key)?;
+        let decomposition = Self::decomposition(&graph, m);
+        let property_key = match m {
+            None => "core_number".to_string(),
+            Some(m) => format!("core_number.m{}", m),
+        };
+
+        let values = decomposition.core_numbers().into_iter().map(|(id, core)| (id, core.to_string()));
+        set_node_properties(&mut graph, &property_key, values);
+        self.repository.update(key, &graph)?;
+        println!("✅ Saved core numbers of '{}' (max core {})", key, decomposition.max_core());
+
+        Ok(decomposition)
+    }
+
+    /// Extracts the k-core (or (k,m)-core) of the stored hypergraph `key` and saves it as the hypergraph
+    /// `core_key`, recording the source graph, k and m as graph properties
+    pub fn extract_core(&self, key: &str, k: usize, m: Option<usize>, core_key: &str) -> Result<HyperGraph<String, String, String>, Box<dyn Error>> {
+        let graph = self.graph(key)?;
+        let mut core = Self::decomposition(&graph, m).extract(&graph, k);
+
+        core.id = core_key.to_string();
+        core.name = core_key.to_string();
+        set_property(&mut core.properties, CORE_OF_KEY, key.to_string());
+        set_property(&mut core.properties, CORE_K_KEY, k.to_string());
+        if let Some(m) = m {
+            set_property(&mut core.properties, CORE_M_KEY, m.to_string());
+        }
+
+        self.repository.create(core_key, &core)?;
+        println!("✅ Saved {}-core of '{}' as '{}' ({} nodes, {} hyperedges)", k, key, core_key, core.hyper_nodes.len(), core.hyper_edges.len());
+        Ok(core)
+    }
+
+    fn decomposition(graph: &HyperGraph<String, String, String>, m: Option<usize>) -> CoreDecomposition {
+        let incidence = IncidenceMatrix::from_graph(graph, None);
+        match m {
+            None => CoreDecomposition::k_cores(&incidence),
+            Some(m) => CoreDecomposition::km_cores(&incidence, m),
+        }
+    }
+
+    fn graph(&self, key: &str) -> Result<HyperGraph<String, String, String>, Box<dyn Error>> {
+        Ok(self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?)
+    }
+}
@@ -0,0 +1,308 @@
+use crate::hyper_edge::services::datalog::program::{Literal, Program, Term};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+pub type Tuple = Vec<String>;
+pub type Relations = HashMap<String, HashSet<Tuple>>;
+
+/// Every fact known after evaluation, with the rule that first derived each derived fact
+#[derive(Debug, Clone, Default)]
+pub struct DatalogResult {
+    pub relations: Relations,
+    pub provenance: HashMap<String, HashMap<Tuple, usize>>,
+    pub iterations: usize,
+}
+
+impl DatalogResult {
+    /// Returns the facts of a predicate (base and derived) in sorted order
+    pub fn facts(&self, predicate: &str) -> Vec<Tuple> {
+        let mut facts: Vec<Tuple> = self.relations.get(predicate).map_or(Vec::new(), |facts| facts.iter().cloned().collect());
+        facts.sort();
+        facts
+    }
+
+    /// Returns the derived facts of a predicate in sorted order, each with the index of the rule that derived it
+    pub fn derived_facts(&self, predicate: &str) -> Vec<(Tuple, usize)> {
+        let mut facts: Vec<(Tuple, usize)> = self.provenance.get(predicate)
+            .map_or(Vec::new(), |facts| facts.iter().map(|(tuple, rule)| (tuple.clone(), *rule)).collect());
+        facts.sort();
+        facts
+    }
+
+    pub fn contains(&self, predicate: &str, tuple: &[&str]) -> bool {
+        let tuple: Tuple = tuple.iter().map(|value| value.to_string()).collect();
+        self.relations.get(predicate).is_some_and(|facts| facts.contains(&tuple))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Slot {
+    Var(usize),
+    Const(String),
+    Wildcard,
+}
+
+struct CompiledAtom {
+    predicate: String,
+    slots: Vec<Slot>,
+    // First column that is already bound when the atom is joined, used for index lookups
+    key_column: Option<usize>,
+}
+
+struct CompiledRule {
+    index: usize,
+    head: Vec<Slot>,
+    atoms: Vec<CompiledAtom>,
+    // (left, right, equal, number of atoms after which both sides are bound)
+    comparisons: Vec<(Slot, Slot, bool, usize)>,
+    var_count: usize,
+}
+
+type Index<'r> = HashMap<(String, bool, usize), HashMap<&'r str, Vec<&'r Tuple>>>;
+
+/// Evaluates a program bottom-up with semi-naive iteration: after the first round, rules are only
+/// re-joined with at least one body atom restricted to the facts that are new since the last round
+pub fn evaluate(program: &Program, base: Relations) -> Result<DatalogResult, Box<dyn Error>> {
+    check_arities(program, &base)?;
+
+    let mut result = DatalogResult { relations: base, ..DatalogResult::default() };
+    let mut rules = Vec::new();
+
+    for (index, rule) in program.rules.iter().enumerate() {
+        if rule.is_fact() {
+            let tuple: Tuple = rule.head.terms.iter()
+                .map(|term| match term {
+                    Term::Const(value) => value.clone(),
+                    Term::Var(name) => name.clone(),
+                })
+                .collect();
+            if result.relations.entry(rule.head.predicate.clone()).or_default().insert(tuple.clone()) {
+                result.provenance.entry(rule.head.predicate.clone()).or_default().insert(tuple, index);
+            }
+        } else {
+            rules.push(compile(index, program)?);
+        }
+    }
+
+    // First round: naive evaluation over everything known so far
+    let empty = Relations::new();
+    let mut delta = Relations::new();
+    derive(&rules, program, &result.relations, &empty, false, &mut delta, &mut result.provenance);
+    result.iterations = 1;
+
+    while delta.values().any(|facts| !facts.is_empty()) {
+        for (predicate, facts) in &delta {
+            result.relations.entry(predicate.clone()).or_default().extend(facts.iter().cloned());
+        }
+
+        let mut next = Relations::new();
+        derive(&rules, program, &result.relations, &delta, true, &mut next, &mut result.provenance);
+        delta = next;
+        result.iterations += 1;
+    }
+
+    Ok(result)
+}
+
+fn derive(
+    rules: &[CompiledRule],
+    program: &Program,
+    full: &Relations,
+    delta: &Relations,
+    semi_naive: bool,
+    new_facts: &mut Relations,
+    provenance: &mut HashMap<String, HashMap<Tuple, usize>>,
+) {
+    let index = build_index(rules, full, delta);
+
+    for rule in rules {
+        let predicate = &program.rules[rule.index].head.predicate;
+        let mut derived = Vec::new();
+
+        if semi_naive {
+            for (position, atom) in rule.atoms.iter().enumerate() {
+                if delta.get(&atom.predicate).is_some_and(|facts| !facts.is_empty()) {
+                    join(rule, 0, Some(position), full, delta, &index, &mut vec![None; rule.var_count], &mut derived);
+                }
+            }
+        } else {
+            join(rule, 0, None, full, delta, &index, &mut vec![None; rule.var_count], &mut derived);
+        }
+
+        for tuple in derived {
+            let known = full.get(predicate).is_some_and(|facts| facts.contains(&tuple));
+            if !known && new_facts.entry(predicate.clone()).or_default().insert(tuple.clone()) {
+                provenance.entry(predicate.clone()).or_default().entry(tuple).or_insert(rule.index);
+            }
+        }
+    }
+}
+
+fn build_index<'r>(rules: &[CompiledRule], full: &'r Relations, delta: &'r Relations) -> Index<'r> {
+    let mut index: Index<'r> = HashMap::new();
+
+    for atom in rules.iter().flat_map(|rule| rule.atoms.iter()) {
+        let Some(column) = atom.key_column else { continue };
+        for (from_delta, relations) in [(false, full), (true, delta)] {
+            let key = (atom.predicate.clone(), from_delta, column);
+            if index.contains_key(&key) {
+                continue;
+            }
+            let mut entries: HashMap<&'r str, Vec<&'r Tuple>> = HashMap::new();
+            for tuple in relations.get(&atom.predicate).into_iter().flatten() {
+                entries.entry(tuple[column].as_str()).or_default().push(tuple);
+            }
+            index.insert(key, entries);
+        }
+    }
+
+    index
+}
+
+#[allow(clippy::too_many_arguments)]
+fn join<'r>(
+    rule: &CompiledRule,
+    step: usize,
+    delta_position: Option<usize>,
+    full: &'r Relations,
+    delta: &'r Relations,
+    index: &Index<'r>,
+    binding: &mut Vec<Option<&'r str>>,
+    derived: &mut Vec<Tuple>,
+) {
+    for (left, right, equal, after) in &rule.comparisons {
+        if *after == step && (resolve(left, binding) == resolve(right, binding)) != *equal {
+            return;
+        }
+    }
+
+    if step == rule.atoms.len() {
+        derived.push(rule.head.iter().map(|slot| resolve(slot, binding).unwrap_or_default().to_string()).collect());
+        return;
+    }
+
+    let atom = &rule.atoms[step];
+    let from_delta = delta_position == Some(step);
+    let relations = if from_delta { delta } else { full };
+
+    let candidates: Vec<&'r Tuple> = match atom.key_column {
+        Some(column) => {
+            let key = resolve(&atom.slots[column], binding).unwrap_or_default();
+            index.get(&(atom.predicate.clone(), from_delta, column))
+                .and_then(|entries| entries.get(key))
+                .map_or(Vec::new(), |tuples| tuples.clone())
+        }
+        None => relations.get(&atom.predicate).map_or(Vec::new(), |facts| facts.iter().collect()),
+    };
+
+    for tuple in candidates {
+        let mut bound_here = Vec::new();
+        let mut matches = true;
+
+        for (slot, value) in atom.slots.iter().zip(tuple) {
+            match slot {
+                Slot::Const(constant) => matches = constant == value,
+                Slot::Var(var) => match binding[*var] {
+                    Some(bound) => matches = bound == value,
+                    None => {
+                        binding[*var] = Some(value.as_str());
+                        bound_here.push(*var);
+                    }
+                },
+                Slot::Wildcard => {}
+            }
+            if !matches {
+                break;
+            }
+        }
+
+        if matches {
+            join(rule, step + 1, delta_position, full, delta, index, binding, derived);
+        }
+        for var in bound_here {
+            binding[var] = None;
+        }
+    }
+}
+
+fn resolve<'a>(slot: &'a Slot, binding: &[Option<&'a str>]) -> Option<&'a str> {
+    match slot {
+        Slot::Var(var) => binding[*var],
+        Slot::Const(value) => Some(value.as_str()),
+        Slot::Wildcard => None,
+    }
+}
+
+fn compile(index: usize, program: &Program) -> Result<CompiledRule, Box<dyn Error>> {
+    let rule = &program.rules[index];
+    let mut vars: Vec<String> = Vec::new();
+    let slot = |term: &Term, vars: &mut Vec<String>| match term {
+        Term::Const(value) => Slot::Const(value.clone()),
+        Term::Var(name) if name == "_" => Slot::Wildcard,
+        Term::Var(name) => match vars.iter().position(|var| var == name) {
+            Some(position) => Slot::Var(position),
+            None => {
+                vars.push(name.clone());
+                Slot::Var(vars.len() - 1)
+            }
+        },
+    };
+
+    let mut atoms = Vec::new();
+    let mut bound_after: Vec<usize> = Vec::new(); // variable -> number of atoms after which it is bound
+    for literal in &rule.body {
+        if let Literal::Atom(atom) = literal {
+            let slots: Vec<Slot> = atom.terms.iter().map(|term| slot(term, &mut vars)).collect();
+            let key_column = slots.iter().position(|slot| match slot {
+                Slot::Const(_) => true,
+                Slot::Var(var) => *var < bound_after.len(),
+                Slot::Wildcard => false,
+            });
+            let step = atoms.len() + 1;
+            bound_after.resize(vars.len(), step);
+            atoms.push(CompiledAtom { predicate: atom.predicate.clone(), slots, key_column });
+        }
+    }
+
+    let mut comparisons = Vec::new();
+    for literal in &rule.body {
+        if let Literal::Compare { left, right, equal } = literal {
+            let left = slot(left, &mut vars);
+            let right = slot(right, &mut vars);
+            let after = [&left, &right].iter()
+                .map(|slot| match slot {
+                    Slot::Var(var) => bound_after[*var],
+                    _ => 0,
+                })
+                .max()
+                .unwrap_or(0);
+            comparisons.push((left, right, *equal, after));
+        }
+    }
+
+    let head = rule.head.terms.iter().map(|term| slot(term, &mut vars)).collect();
+    Ok(CompiledRule { index, head, atoms, comparisons, var_count: vars.len() })
+}
+
+fn check_arities(program: &Program, base: &Relations) -> Result<(), Box<dyn Error>> {
+    let mut arities: HashMap<&str, usize> = HashMap::new();
+    for (predicate, facts) in base {
+        if let Some(tuple) = facts.iter().next() {
+            arities.insert(predicate.as_str(), tuple.len());
+        }
+    }
+
+    let atoms = program.rules.iter().flat_map(|rule| {
+        std::iter::once(&rule.head).chain(rule.body.iter().filter_map(|literal| match literal {
+            Literal::Atom(atom) => Some(atom),
+            Literal::Compare { .. } => None,
+        }))
+    });
+    for atom in atoms {
+        let arity = *arities.entry(atom.predicate.as_str()).or_insert(atom.terms.len());
+        if arity != atom.terms.len() {
+            return Err(format!("Datalog: predicate '{}' used with {} arguments, expected {}", atom.predicate, atom.terms.len(), arity).into());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,2 @@
+pub mod program;
+pub mod engine;
\ No newline at end of file
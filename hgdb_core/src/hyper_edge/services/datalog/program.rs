@@ -0,0 +1,284 @@
+use std::error::Error;
+use std::fmt;
+
+/// Variables start with an uppercase letter or `_`, everything else (quoted strings, lowercase names, numbers) is a constant
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Term {
+    Var(String),
+    Const(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atom {
+    pub predicate: String,
+    pub terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Atom(Atom),
+    /// `X = Y` (`equal == true`) or `X != Y`
+    Compare { left: Term, right: Term, equal: bool },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    pub head: Atom,
+    pub body: Vec<Literal>,
+}
+
+impl Rule {
+    pub fn is_fact(&self) -> bool {
+        self.body.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Program {
+    pub rules: Vec<Rule>,
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Var(name) => write!(f, "{}", name),
+            Term::Const(value) => write!(f, "'{}'", value),
+        }
+    }
+}
+
+impl fmt::Display for Atom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms: Vec<String> = self.terms.iter().map(|term| term.to_string()).collect();
+        write!(f, "{}({})", self.predicate, terms.join(", "))
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Atom(atom) => write!(f, "{}", atom),
+            Literal::Compare { left, right, equal } => write!(f, "{} {} {}", left, if *equal { "=" } else { "!=" }, right),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_fact() {
+            return write!(f, "{}.", self.head);
+        }
+        let body: Vec<String> = self.body.iter().map(|literal| literal.to_string()).collect();
+        write!(f, "{} :- {}.", self.head, body.join(", "))
+    }
+}
+
+/// Parses a program of facts and rules, e.g.
+///
+/// ```text
+/// % transitive reachability over directed hyperedges (tail -> head)
+/// reach(X, Y) :- tail(E, X), head(E, Y).
+/// reach(X, Z) :- reach(X, Y), reach(Y, Z), X != Z.
+/// ```
+pub fn parse_program(input: &str) -> Result<Program, Box<dyn Error>> {
+    let mut parser = ProgramParser { chars: input.chars().collect(), position: 0 };
+    let mut program = Program::default();
+
+    loop {
+        parser.skip_whitespace();
+        if parser.position >= parser.chars.len() {
+            break;
+        }
+        let rule = parser.parse_rule()?;
+        check_safety(&rule)?;
+        program.rules.push(rule);
+    }
+
+    Ok(program)
+}
+
+/// Every variable of the head and of a comparison has to be bound by a body atom
+fn check_safety(rule: &Rule) -> Result<(), Box<dyn Error>> {
+    let bound: Vec<&Term> = rule.body.iter()
+        .filter_map(|literal| match literal {
+            Literal::Atom(atom) => Some(atom.terms.iter()),
+            Literal::Compare { .. } => None,
+        })
+        .flatten()
+        .collect();
+
+    let comparison_terms = rule.body.iter().flat_map(|literal| match literal {
+        Literal::Compare { left, right, .. } => vec![left, right],
+        Literal::Atom(_) => Vec::new(),
+    });
+
+    for term in rule.head.terms.iter().chain(comparison_terms) {
+        if let Term::Var(name) = term {
+            if name == "_" || !bound.contains(&term) {
+                return Err(format!("Datalog: variable {} is not bound by a body atom in rule '{}'", name, rule).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+struct ProgramParser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl ProgramParser {
+    fn parse_rule(&mut self) -> Result<Rule, Box<dyn Error>> {
+        let head = self.parse_atom()?;
+        let mut body = Vec::new();
+
+        self.skip_whitespace();
+        if self.eat(":-") {
+            loop {
+                body.push(self.parse_literal()?);
+                self.skip_whitespace();
+                if !self.eat(",") {
+                    break;
+                }
+            }
+        }
+
+        self.skip_whitespace();
+        if !self.eat(".") {
+            return Err(self.error("expected '.' at the end of the rule"));
+        }
+        Ok(Rule { head, body })
+    }
+
+    fn parse_literal(&mut self) -> Result<Literal, Box<dyn Error>> {
+        self.skip_whitespace();
+        let start = self.position;
+
+        // A literal starting with a name followed by '(' is an atom, anything else a comparison
+        if let Ok(name) = self.parse_name() {
+            self.skip_whitespace();
+            if self.peek() == Some('(') && !starts_variable(&name) {
+                self.position = start;
+                return Ok(Literal::Atom(self.parse_atom()?));
+            }
+        }
+        self.position = start;
+
+        let left = self.parse_term()?;
+        self.skip_whitespace();
+        let equal = if self.eat("!=") {
+            false
+        } else if self.eat("=") {
+            true
+        } else {
+            return Err(self.error("expected an atom or a comparison"));
+        };
+        let right = self.parse_term()?;
+        Ok(Literal::Compare { left, right, equal })
+    }
+
+    fn parse_atom(&mut self) -> Result<Atom, Box<dyn Error>> {
+        self.skip_whitespace();
+        let predicate = self.parse_name()?;
+        if starts_variable(&predicate) {
+            return Err(self.error(&format!("predicate '{}' must start with a lowercase letter", predicate)));
+        }
+
+        self.skip_whitespace();
+        let mut terms = Vec::new();
+        if self.eat("(") {
+            loop {
+                terms.push(self.parse_term()?);
+                self.skip_whitespace();
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.skip_whitespace();
+            if !self.eat(")") {
+                return Err(self.error("expected ')'"));
+            }
+        }
+        Ok(Atom { predicate, terms })
+    }
+
+    fn parse_term(&mut self) -> Result<Term, Box<dyn Error>> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(quote) if quote == '\'' || quote == '"' => {
+                self.position += 1;
+                let mut value = String::new();
+                loop {
+                    match self.peek() {
+                        Some(c) if c == quote => {
+                            self.position += 1;
+                            break;
+                        }
+                        Some(c) => {
+                            value.push(c);
+                            self.position += 1;
+                        }
+                        None => return Err(self.error("unterminated string")),
+                    }
+                }
+                Ok(Term::Const(value))
+            }
+            _ => {
+                let name = self.parse_name()?;
+                Ok(if starts_variable(&name) { Term::Var(name) } else { Term::Const(name) })
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, Box<dyn Error>> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '_' || c == '-' {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+        if start == self.position {
+            return Err(self.error("expected a name"));
+        }
+        Ok(self.chars[start..self.position].iter().collect())
+    }
+
+    // Skips whitespace and `%` line comments
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.position += 1;
+            } else if c == '%' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.position += 1;
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        let expected: Vec<char> = text.chars().collect();
+        if self.chars[self.position..].starts_with(&expected) {
+            self.position += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: &str) -> Box<dyn Error> {
+        format!("Datalog parse error at position {}: {}", self.position, message).into()
+    }
+}
+
+fn starts_variable(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_uppercase() || c == '_')
+}
@@ -0,0 +1,115 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use crate::hyper_edge::services::h_graph_service::{edge_members, HyperGraphService};
+use crate::hyper_edge::services::datalog::engine::{evaluate, DatalogResult, Relations};
+use crate::hyper_edge::services::datalog::program::{parse_program, Program};
+use std::error::Error;
+
+/// Runs Datalog programs over the stored hyperedges. The base facts of a hypergraph are:
+///
+/// ```text
+/// edge(E)  node(N)  name(E, Name)  head(E, N)  tail(E, N)  member(E, N)
+/// directed(E)  traversable(E)  property(E, Key, Value)  node_property(N, Key, Value)
+/// ```
+pub struct DatalogService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> DatalogService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        DatalogService { repository }
+    }
+
+    /// Parses and evaluates a program against every hyperedge stored in the repository
+    pub fn run(&self, program: &str) -> Result<DatalogResult, Box<dyn Error>> {
+        let graph = HyperGraphService::new(self.repository).load_graph("datalog")?;
+        self.run_on_graph(&graph, &parse_program(program)?)
+    }
+
+    pub fn run_on_graph(&self, graph: &HyperGraph<String, String, String>, program: &Program) -> Result<DatalogResult, Box<dyn Error>> {
+        evaluate(program, self.base_facts(graph))
+    }
+
+    // method to turn the nodes and hyperedges of a graph into base facts
+    pub fn base_facts(&self, graph: &HyperGraph<String, String, String>) -> Relations {
+        let mut facts = Relations::new();
+        let mut add = |predicate: &str, tuple: &[&String]| {
+            facts.entry(predicate.to_string()).or_default().insert(tuple.iter().map(|value| value.to_string()).collect());
+        };
+
+        for node in &graph.hyper_nodes {
+            add("node", &[&node.id]);
+            for property in &node.properties {
+                for value in &property.value {
+                    add("node_property", &[&node.id, &property.key, value]);
+                }
+            }
+        }
+
+        for edge in &graph.hyper_edges {
+            add("edge", &[&edge.id]);
+            add("name", &[&edge.id, &edge.name]);
+            if edge.directed {
+                add("directed", &[&edge.id]);
+            }
+            if edge.traversable {
+                add("traversable", &[&edge.id]);
+            }
+            for node in edge.head_hyper_nodes.iter() {
+                add("head", &[&edge.id, node]);
+            }
+            for node in edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()) {
+                add("tail", &[&edge.id, node]);
+            }
+            for node in edge_members(edge) {
+                add("member", &[&edge.id, &node]);
+                add("node", &[&node]);
+            }
+            for property in &edge.main_properties {
+                for value in &property.value {
+                    add("property", &[&edge.id, &property.key, value]);
+                }
+            }
+        }
+
+        facts
+    }
+
+    /// Writes the derived facts of the given predicates back as hyperedges tagged with the rule that derived them
+    /// and returns their ids. Unary facts become undirected single-node edges, for longer facts the first argument
+    /// is the tail and the rest the head
+    pub fn materialize(&self, result: &DatalogResult, program: &Program, predicates: &[&str]) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut edge_ids = Vec::new();
+
+        for predicate in predicates {
+            for (tuple, rule) in result.derived_facts(predicate) {
+                let property = |key: &str, value: Vec<String>| Property { key: key.to_string(), value };
+                let (head, tail) = match tuple.split_first() {
+                    Some((first, rest)) if !rest.is_empty() => (rest.to_vec(), Some(Box::new(vec![first.clone()]))),
+                    _ => (tuple.clone(), None),
+                };
+
+                let edge = SimpleHyperEdge {
+                    id: format!("{}({})", predicate, tuple.join(",")),
+                    name: predicate.to_string(),
+                    main_properties: vec![
+                        property("predicate", vec![predicate.to_string()]),
+                        property("arguments", tuple.clone()),
+                        property("provenance", vec![program.rules[rule].to_string()]),
+                    ],
+                    traversable: true,
+                    directed: tail.is_some(),
+                    head_hyper_nodes: Box::new(head),
+                    tail_hyper_nodes: tail,
+                };
+
+                self.repository.create(&edge.id, &edge)?;
+                edge_ids.push(edge.id);
+            }
+        }
+
+        println!("✅ Materialized {} derived hyperedges", edge_ids.len());
+        Ok(edge_ids)
+    }
+}
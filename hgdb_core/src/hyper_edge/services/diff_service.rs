@@ -0,0 +1,391 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+type Graph = HyperGraph<String, String, String>;
+type Edge = SimpleHyperEdge<String, String, String>;
+type Node = HyperNode<String, String, String>;
+
+/// One changed field of a hyperedge or node. Every change carries the old value so that applying a patch can
+/// check it still holds
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "field", rename_all = "snake_case")]
+pub enum FieldChange {
+    Name { from: String, to: String },
+    Traversable { from: bool, to: bool },
+    Directed { from: bool, to: bool },
+    /// A property key that was added (`from` is `None`), removed (`to` is `None`) or given other values
+    Property { key: String, from: Option<Vec<String>>, to: Option<Vec<String>> },
+    /// Head members that joined or left the hyperedge
+    Head { added: Vec<String>, removed: Vec<String> },
+    /// Tail members that joined or left the hyperedge; a missing tail counts as an empty one
+    Tail { added: Vec<String>, removed: Vec<String> },
+}
+
+/// What happened to one hyperedge between two versions
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum EdgeChange {
+    Added { edge: Edge },
+    Removed { edge: Edge },
+    Modified { id: String, fields: Vec<FieldChange> },
+}
+
+/// What happened to one listed node between two versions of a named hypergraph
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum NodeChange {
+    Added { node: Node },
+    Removed { node: Node },
+    Modified { id: String, fields: Vec<FieldChange> },
+}
+
+/// The differences between two versions, in the order of the old version followed by the additions of the new
+/// one. A patch can be stored as JSON and applied to any store holding the old version
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Patch {
+    pub edges: Vec<EdgeChange>,
+    pub nodes: Vec<NodeChange>,
+}
+
+impl Patch {
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty() && self.nodes.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Applies the patch to a hypergraph. Nothing is changed unless every change applies: an added id must be
+    /// free, and removed or modified elements must still hold the old values recorded in the patch
+    pub fn apply(&self, graph: &Graph) -> Result<Graph, Box<dyn Error>> {
+        let mut patched = graph.clone();
+        patched.hyper_edges = apply_changes(&graph.hyper_edges, &self.edges)?;
+        patched.hyper_nodes = apply_changes(&graph.hyper_nodes, &self.nodes)?;
+        Ok(patched)
+    }
+}
+
+/// Field-level diff of two lists of hyperedges matched by id, such as two snapshots of the flat store
+pub fn diff_edges(before: &[Edge], after: &[Edge]) -> Vec<EdgeChange> {
+    diff_by_id(before, after, edge_fields)
+}
+
+/// Field-level diff of two hypergraphs: their hyperedges and their listed nodes, matched by id
+pub fn diff(before: &Graph, after: &Graph) -> Patch {
+    Patch {
+        edges: diff_edges(&before.hyper_edges, &after.hyper_edges),
+        nodes: diff_by_id(&before.hyper_nodes, &after.hyper_nodes, |old, new| property_fields(&old.properties, &new.properties)),
+    }
+}
+
+// Elements that can be added, removed or modified field by field
+trait Patchable: Clone + PartialEq {
+    type Change;
+
+    fn id(&self) -> &String;
+    fn added(self) -> Self::Change;
+    fn removed(self) -> Self::Change;
+    fn modified(id: String, fields: Vec<FieldChange>) -> Self::Change;
+    // The parts of a change: the id it targets, the element to add, the element to remove and the fields to modify
+    fn parts(change: &Self::Change) -> (&str, Option<&Self>, Option<&Self>, &[FieldChange]);
+    fn apply_field(&mut self, field: &FieldChange) -> Result<(), Box<dyn Error>>;
+}
+
+impl Patchable for Edge {
+    type Change = EdgeChange;
+
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    fn added(self) -> EdgeChange {
+        EdgeChange::Added { edge: self }
+    }
+
+    fn removed(self) -> EdgeChange {
+        EdgeChange::Removed { edge: self }
+    }
+
+    fn modified(id: String, fields: Vec<FieldChange>) -> EdgeChange {
+        EdgeChange::Modified { id, fields }
+    }
+
+    fn parts(change: &EdgeChange) -> (&str, Option<&Self>, Option<&Self>, &[FieldChange]) {
+        match change {
+            EdgeChange::Added { edge } => (&edge.id, Some(edge), None, &[]),
+            EdgeChange::Removed { edge } => (&edge.id, None, Some(edge), &[]),
+            EdgeChange::Modified { id, fields } => (id, None, None, fields),
+        }
+    }
+
+    fn apply_field(&mut self, field: &FieldChange) -> Result<(), Box<dyn Error>> {
+        match field {
+            FieldChange::Name { from, to } => self.name = replace(&self.name, from, to, "name")?,
+            FieldChange::Traversable { from, to } => self.traversable = replace(&self.traversable, from, to, "traversable")?,
+            FieldChange::Directed { from, to } => self.directed = replace(&self.directed, from, to, "directed")?,
+            FieldChange::Property { key, from, to } => apply_property(&mut self.main_properties, key, from, to)?,
+            FieldChange::Head { added, removed } => apply_members(&mut self.head_hyper_nodes, added, removed, "head")?,
+            FieldChange::Tail { added, removed } => {
+                let mut tail = self.tail_hyper_nodes.take().map(|tail| *tail).unwrap_or_default();
+                apply_members(&mut tail, added, removed, "tail")?;
+                self.tail_hyper_nodes = (!tail.is_empty()).then(|| Box::new(tail));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Patchable for Node {
+    type Change = NodeChange;
+
+    fn id(&self) -> &String {
+        &self.id
+    }
+
+    fn added(self) -> NodeChange {
+        NodeChange::Added { node: self }
+    }
+
+    fn removed(self) -> NodeChange {
+        NodeChange::Removed { node: self }
+    }
+
+    fn modified(id: String, fields: Vec<FieldChange>) -> NodeChange {
+        NodeChange::Modified { id, fields }
+    }
+
+    fn parts(change: &NodeChange) -> (&str, Option<&Self>, Option<&Self>, &[FieldChange]) {
+        match change {
+            NodeChange::Added { node } => (&node.id, Some(node), None, &[]),
+            NodeChange::Removed { node } => (&node.id, None, Some(node), &[]),
+            NodeChange::Modified { id, fields } => (id, None, None, fields),
+        }
+    }
+
+    fn apply_field(&mut self, field: &FieldChange) -> Result<(), Box<dyn Error>> {
+        match field {
+            FieldChange::Property { key, from, to } => apply_property(&mut self.properties, key, from, to),
+            other => Err(format!("Nodes have no field {:?}", other).into()),
+        }
+    }
+}
+
+fn diff_by_id<T: Patchable, F: Fn(&T, &T) -> Vec<FieldChange>>(before: &[T], after: &[T], fields: F) -> Vec<T::Change> {
+    let old: HashMap<&String, &T> = before.iter().map(|element| (element.id(), element)).collect();
+    let new: HashMap<&String, &T> = after.iter().map(|element| (element.id(), element)).collect();
+
+    let mut changes = Vec::new();
+    for element in before {
+        match new.get(element.id()) {
+            None => changes.push(element.clone().removed()),
+            Some(updated) => {
+                let fields = fields(element, updated);
+                if !fields.is_empty() {
+                    changes.push(T::modified(element.id().clone(), fields));
+                }
+            }
+        }
+    }
+    changes.extend(after.iter().filter(|element| !old.contains_key(element.id())).map(|element| element.clone().added()));
+    changes
+}
+
+fn edge_fields(old: &Edge, new: &Edge) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+    if old.name != new.name {
+        fields.push(FieldChange::Name { from: old.name.clone(), to: new.name.clone() });
+    }
+    if old.traversable != new.traversable {
+        fields.push(FieldChange::Traversable { from: old.traversable, to: new.traversable });
+    }
+    if old.directed != new.directed {
+        fields.push(FieldChange::Directed { from: old.directed, to: new.directed });
+    }
+    fields.extend(property_fields(&old.main_properties, &new.main_properties));
+
+    let (added, removed) = members_diff(&old.head_hyper_nodes, &new.head_hyper_nodes);
+    if !added.is_empty() || !removed.is_empty() {
+        fields.push(FieldChange::Head { added, removed });
+    }
+    let empty = Vec::new();
+    let tail = |edge: &Edge| edge.tail_hyper_nodes.as_deref().unwrap_or(&empty).clone();
+    let (added, removed) = members_diff(&tail(old), &tail(new));
+    if !added.is_empty() || !removed.is_empty() {
+        fields.push(FieldChange::Tail { added, removed });
+    }
+    fields
+}
+
+fn property_fields(old: &[Property<String, String>], new: &[Property<String, String>]) -> Vec<FieldChange> {
+    let values = |properties: &[Property<String, String>], key: &str| properties.iter().find(|property| property.key == key).map(|property| property.value.clone());
+
+    let mut fields = Vec::new();
+    for property in old {
+        let to = values(new, &property.key);
+        if to.as_ref() != Some(&property.value) {
+            fields.push(FieldChange::Property { key: property.key.clone(), from: Some(property.value.clone()), to });
+        }
+    }
+    for property in new.iter().filter(|property| values(old, &property.key).is_none()) {
+        fields.push(FieldChange::Property { key: property.key.clone(), from: None, to: Some(property.value.clone()) });
+    }
+    fields
+}
+
+// Members of `new` missing from `old`, and members of `old` missing from `new`, in list order
+fn members_diff(old: &[String], new: &[String]) -> (Vec<String>, Vec<String>) {
+    let old_set: HashSet<&String> = old.iter().collect();
+    let new_set: HashSet<&String> = new.iter().collect();
+    (
+        new.iter().filter(|node| !old_set.contains(node)).cloned().collect(),
+        old.iter().filter(|node| !new_set.contains(node)).cloned().collect(),
+    )
+}
+
+fn apply_changes<T: Patchable>(current: &[T], changes: &[T::Change]) -> Result<Vec<T>, Box<dyn Error>> {
+    let mut elements: Vec<Option<T>> = current.iter().cloned().map(Some).collect();
+    let mut positions: HashMap<String, usize> = current.iter().enumerate().map(|(position, element)| (element.id().clone(), position)).collect();
+
+    for change in changes {
+        match T::parts(change) {
+            (target, Some(added), _, _) => {
+                if positions.contains_key(target) {
+                    return Err(format!("Cannot add '{}': it already exists", target).into());
+                }
+                positions.insert(target.to_string(), elements.len());
+                elements.push(Some(added.clone()));
+            }
+            (target, None, Some(removed), _) => {
+                let position = positions.remove(target).ok_or_else(|| format!("Cannot remove '{}': it does not exist", target))?;
+                if elements[position].as_ref() != Some(removed) {
+                    return Err(format!("Cannot remove '{}': it changed since the patch was made", target).into());
+                }
+                elements[position] = None;
+            }
+            (target, None, None, fields) => {
+                let position = *positions.get(target).ok_or_else(|| format!("Cannot modify '{}': it does not exist", target))?;
+                let element = elements[position].as_mut().ok_or_else(|| format!("Cannot modify '{}': it does not exist", target))?;
+                for field in fields {
+                    element.apply_field(field).map_err(|e| format!("Cannot modify '{}': {}", target, e))?;
+                }
+            }
+        }
+    }
+
+    Ok(elements.into_iter().flatten().collect())
+}
+
+fn replace<T: PartialEq + Clone + std::fmt::Debug>(current: &T, from: &T, to: &T, field: &str) -> Result<T, Box<dyn Error>> {
+    if current != from {
+        return Err(format!("{} is {:?}, expected {:?}", field, current, from).into());
+    }
+    Ok(to.clone())
+}
+
+fn apply_property(properties: &mut Vec<Property<String, String>>, key: &str, from: &Option<Vec<String>>, to: &Option<Vec<String>>) -> Result<(), Box<dyn Error>> {
+    let position = properties.iter().position(|property| property.key == key);
+    let current = position.map(|position| &properties[position].value);
+    if current != from.as_ref() {
+        return Err(format!("property '{}' is {:?}, expected {:?}", key, current, from).into());
+    }
+    match (position, to) {
+        (Some(position), Some(values)) => properties[position].value = values.clone(),
+        (Some(position), None) => {
+            properties.remove(position);
+        }
+        (None, Some(values)) => properties.push(Property { key: key.to_string(), value: values.clone() }),
+        (None, None) => {}
+    }
+    Ok(())
+}
+
+fn apply_members(members: &mut Vec<String>, added: &[String], removed: &[String], role: &str) -> Result<(), Box<dyn Error>> {
+    if let Some(node) = removed.iter().find(|node| !members.contains(node)) {
+        return Err(format!("'{}' is not a {} member", node, role).into());
+    }
+    if let Some(node) = added.iter().find(|node| members.contains(node)) {
+        return Err(format!("'{}' is already a {} member", node, role).into());
+    }
+    members.retain(|node| !removed.contains(node));
+    members.extend(added.iter().cloned());
+    Ok(())
+}
+
+/// Diffs and patches of the flat hyperedge store. Hyperedges are expected to be stored under their id
+pub struct DiffService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> DiffService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        DiffService { repository }
+    }
+
+    /// The current hyperedges, to diff against later
+    pub fn snapshot(&self) -> Result<Vec<Edge>, Box<dyn Error>> {
+        self.repository.get_all()
+    }
+
+    /// What changed in the store since `snapshot` was taken
+    pub fn diff_since(&self, snapshot: &[Edge]) -> Result<Patch, Box<dyn Error>> {
+        let edges = diff_edges(snapshot, &self.repository.get_all()?);
+        println!("🔍 {} hyperedges changed since the snapshot", edges.len());
+        Ok(Patch { edges, nodes: Vec::new() })
+    }
+
+    /// Applies the hyperedge changes of a patch to this store. Every change is checked first, then all are
+    /// written in one batch
+    pub fn apply(&self, patch: &Patch) -> Result<(), Box<dyn Error>> {
+        let current = self.repository.get_all()?;
+        let patched = apply_changes(&current, &patch.edges)?;
+
+        let kept: HashSet<&String> = patched.iter().map(|edge| &edge.id).collect();
+        let deleted: Vec<&str> = current.iter().filter(|edge| !kept.contains(&edge.id)).map(|edge| edge.id.as_str()).collect();
+        let old: HashMap<&String, &Edge> = current.iter().map(|edge| (&edge.id, edge)).collect();
+        let written: Vec<&Edge> = patched.iter().filter(|edge| old.get(&edge.id) != Some(edge)).collect();
+        self.repository.update_many(&written, &deleted)?;
+        println!("✅ Applied {} hyperedge changes", patch.edges.len());
+        Ok(())
+    }
+}
+
+/// Diffs and patches of the named hypergraphs
+pub struct HyperGraphDiffService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> HyperGraphDiffService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        HyperGraphDiffService { repository }
+    }
+
+    /// What changes the stored hypergraph `before` into `after`
+    pub fn diff(&self, before: &str, after: &str) -> Result<Patch, Box<dyn Error>> {
+        let patch = diff(&self.graph(before)?, &self.graph(after)?);
+        println!("🔍 '{}' -> '{}': {} hyperedge and {} node changes", before, after, patch.edges.len(), patch.nodes.len());
+        Ok(patch)
+    }
+
+    /// Applies a patch to the stored hypergraph `key`, which is left untouched if any change does not apply
+    pub fn apply(&self, key: &str, patch: &Patch) -> Result<Graph, Box<dyn Error>> {
+        let patched = patch.apply(&self.graph(key)?)?;
+        self.repository.create(key, &patched)?;
+        println!("✅ Applied {} hyperedge and {} node changes to '{}'", patch.edges.len(), patch.nodes.len(), key);
+        Ok(patched)
+    }
+
+    fn graph(&self, key: &str) -> Result<Graph, Box<dyn Error>> {
+        Ok(self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Dual nodes remember the fields of the hyperedge they stand for, so the dual of the dual restores it
+pub const DUAL_NAME_KEY: &str = "dual.name";
+pub const DUAL_DIRECTED_KEY: &str = "dual.directed";
+pub const DUAL_TRAVERSABLE_KEY: &str = "dual.traversable";
+// Marks dual hyperedges built from a plain node, so dualizing them again yields a plain node
+pub const DUAL_DERIVED_KEY: &str = "dual.derived";
+// Graph property naming the hypergraph a dual was computed from
+pub const DUAL_OF_KEY: &str = "dual_of";
+
+/// Computes the dual H* of a whole hypergraph H: every node v of H becomes a hyperedge of H* containing the
+/// hyperedges of H that contain v, and every hyperedge e of H becomes a node of H*. Ids are kept, so the dual
+/// hyperedge `v` maps back to node `v` and the dual node `e` maps back to hyperedge `e`. Head and tail roles are
+/// preserved (v in the tail of e puts e in the tail of v*), and dual(dual(H)) = H up to the order of the members
+pub struct DualHyperGraphService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> DualHyperGraphService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        DualHyperGraphService { repository }
+    }
+
+    /// Computes the dual of the stored hypergraph `key` and saves it as `dual_<key>`
+    pub fn create_dual(&self, key: &str) -> Result<HyperGraph<String, String, String>, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+
+        let dual = self.compute_dual(&graph);
+        let dual_key = format!("dual_{}", key);
+        self.repository.create(&dual_key, &dual)?;
+        println!("✅ Saved dual of '{}' as '{}' ({} nodes, {} hyperedges)", key, dual_key, dual.hyper_nodes.len(), dual.hyper_edges.len());
+
+        Ok(dual)
+    }
+
+    // method to compute the dual of a hypergraph without storing it
+    pub fn compute_dual(&self, graph: &HyperGraph<String, String, String>) -> HyperGraph<String, String, String> {
+        // node id -> (edges having it in the head, edges having it in the tail)
+        let mut incidences: HashMap<&str, (Vec<String>, Vec<String>)> = HashMap::new();
+        for edge in &graph.hyper_edges {
+            for node in edge.head_hyper_nodes.iter() {
+                incidences.entry(node.as_str()).or_default().0.push(edge.id.clone());
+            }
+            for node in edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()) {
+                incidences.entry(node.as_str()).or_default().1.push(edge.id.clone());
+            }
+        }
+
+        // Nodes referenced by edges but missing from the node list still get a dual hyperedge
+        let mut nodes: Vec<HyperNode<String, String, String>> = graph.hyper_nodes.clone();
+        let mut known: HashSet<String> = nodes.iter().map(|node| node.id.clone()).collect();
+        for edge in &graph.hyper_edges {
+            for node in edge.head_hyper_nodes.iter().chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter())) {
+                if known.insert(node.clone()) {
+                    nodes.push(HyperNode { id: node.clone(), properties: Vec::new() });
+                }
+            }
+        }
+
+        let hyper_edges = nodes.iter()
+            .map(|node| {
+                let (head, tail) = incidences.remove(node.id.as_str()).unwrap_or_default();
+                self.node_to_dual_edge(node, head, tail)
+            })
+            .collect();
+        let hyper_nodes = graph.hyper_edges.iter().map(|edge| self.edge_to_dual_node(edge)).collect();
+
+        let mut properties: Vec<Property<String, String>> = graph.properties.iter()
+            .filter(|property| property.key != DUAL_OF_KEY)
+            .cloned()
+            .collect();
+        let dual_of = graph.properties.iter()
+            .find(|property| property.key == DUAL_OF_KEY)
+            .and_then(|property| property.value.first());
+
+        // The dual of a dual takes back the identity of the original graph
+        let (id, name) = match dual_of {
+            Some(original_name) => {
+                let id = graph.id.strip_prefix("dual_").unwrap_or(&graph.id).to_string();
+                (id, original_name.clone())
+            }
+            None => {
+                properties.push(Property { key: DUAL_OF_KEY.to_string(), value: vec![graph.name.clone()] });
+                (format!("dual_{}", graph.id), format!("Dual of {}", graph.name))
+            }
+        };
+
+        HyperGraph { id, name, properties, hyper_nodes, hyper_edges }
+    }
+
+    fn node_to_dual_edge(&self, node: &HyperNode<String, String, String>, head: Vec<String>, tail: Vec<String>) -> SimpleHyperEdge<String, String, String> {
+        let meta = |key: &str| node.properties.iter().find(|property| property.key == key).and_then(|property| property.value.first());
+        let main_properties: Vec<Property<String, String>> = node.properties.iter()
+            .filter(|property| !property.key.starts_with("dual."))
+            .cloned()
+            .collect();
+
+        match meta(DUAL_NAME_KEY) {
+            // The node stands for a hyperedge of an earlier dualization: restore that hyperedge's fields
+            Some(name) => {
+                let directed = meta(DUAL_DIRECTED_KEY).is_some_and(|value| value == "true");
+                SimpleHyperEdge {
+                    id: node.id.clone(),
+                    name: name.clone(),
+                    main_properties,
+                    traversable: meta(DUAL_TRAVERSABLE_KEY).is_none_or(|value| value == "true"),
+                    directed,
+                    head_hyper_nodes: Box::new(head),
+                    tail_hyper_nodes: if directed || !tail.is_empty() { Some(Box::new(tail)) } else { None },
+                }
+            }
+            None => {
+                let mut main_properties = main_properties;
+                main_properties.push(Property { key: DUAL_DERIVED_KEY.to_string(), value: vec!["true".to_string()] });
+                let directed = !tail.is_empty();
+                SimpleHyperEdge {
+                    id: node.id.clone(),
+                    name: node.id.clone(),
+                    main_properties,
+                    traversable: true,
+                    directed,
+                    head_hyper_nodes: Box::new(head),
+                    tail_hyper_nodes: if directed { Some(Box::new(tail)) } else { None },
+                }
+            }
+        }
+    }
+
+    fn edge_to_dual_node(&self, edge: &SimpleHyperEdge<String, String, String>) -> HyperNode<String, String, String> {
+        let mut properties: Vec<Property<String, String>> = edge.main_properties.iter()
+            .filter(|property| !property.key.starts_with("dual."))
+            .cloned()
+            .collect();
+
+        // An edge built from a plain node turns back into that plain node
+        let derived = edge.main_properties.iter().any(|property| property.key == DUAL_DERIVED_KEY);
+        if !derived {
+            let meta = |key: &str, value: String| Property { key: key.to_string(), value: vec![value] };
+            properties.push(meta(DUAL_NAME_KEY, edge.name.clone()));
+            properties.push(meta(DUAL_DIRECTED_KEY, edge.directed.to_string()));
+            properties.push(meta(DUAL_TRAVERSABLE_KEY, edge.traversable.to_string()));
+        }
+
+        HyperNode { id: edge.id.clone(), properties }
+    }
+}
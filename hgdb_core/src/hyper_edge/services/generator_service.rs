@@ -0,0 +1,317 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use rand::seq::index;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::error::Error;
+use std::time::Instant;
+
+type Edge = SimpleHyperEdge<String, String, String>;
+
+/// Hyperedges written per write batch
+pub const GENERATOR_BATCH_SIZE: usize = 10_000;
+
+/// A random hypergraph model. Nodes are named `n0`, `n1`, ... after their position
+#[derive(Debug, Clone, PartialEq)]
+pub enum Model {
+    /// `edges` hyperedges of exactly `size` distinct nodes, drawn uniformly
+    Uniform { nodes: usize, edges: usize, size: usize },
+    /// Every set of `size` nodes is a hyperedge with probability `probability`, independently
+    ErdosRenyi { nodes: usize, size: usize, probability: f64 },
+    /// One hyperedge per entry of `sizes`, its members drawn with probability proportional to `degrees`, so every
+    /// node gets its degree in expectation
+    ChungLu { degrees: Vec<f64>, sizes: Vec<usize> },
+    /// Node stubs (`degrees[i]` copies of node i) shuffled and cut into hyperedges of the given sizes. The two lists
+    /// must have the same sum; a node drawn twice for one hyperedge is kept once
+    Configuration { degrees: Vec<usize>, sizes: Vec<usize> },
+    /// `edges` directed hyperedges with `head_size` head and `tail_size` tail members, all distinct, drawn uniformly
+    Directed { nodes: usize, edges: usize, head_size: usize, tail_size: usize },
+}
+
+impl Model {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Model::Uniform { .. } => "uniform",
+            Model::ErdosRenyi { .. } => "erdos_renyi",
+            Model::ChungLu { .. } => "chung_lu",
+            Model::Configuration { .. } => "configuration",
+            Model::Directed { .. } => "directed",
+        }
+    }
+
+    fn validate(&self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Model::Uniform { nodes, size, .. } | Model::ErdosRenyi { nodes, size, .. } if size > nodes => {
+                Err(format!("Hyperedges of {} members need at least as many nodes, not {}", size, nodes).into())
+            }
+            Model::ErdosRenyi { probability, .. } if !(0.0..=1.0).contains(probability) => {
+                Err(format!("Probability {} is not between 0 and 1", probability).into())
+            }
+            Model::ErdosRenyi { nodes, size, .. } if binomial(*nodes as u128, *size as u128).and_then(|total| total.checked_mul(*size as u128 + 1)).is_none() => {
+                Err(format!("Too many possible hyperedges of {} out of {} nodes", size, nodes).into())
+            }
+            Model::ChungLu { degrees, .. } if degrees.iter().any(|degree| !degree.is_finite() || *degree < 0.0) => {
+                Err("Chung-Lu degrees must be finite and non-negative".into())
+            }
+            Model::ChungLu { degrees, sizes } if sizes.iter().any(|&size| size > 0) && !degrees.iter().any(|&degree| degree > 0.0) => {
+                Err("Chung-Lu needs a node with a positive degree".into())
+            }
+            Model::Configuration { degrees, sizes } if degrees.iter().sum::<usize>() != sizes.iter().sum::<usize>() => {
+                Err(format!("Degrees sum to {} but hyperedge sizes to {}", degrees.iter().sum::<usize>(), sizes.iter().sum::<usize>()).into())
+            }
+            Model::Directed { nodes, head_size, tail_size, .. } if head_size + tail_size > *nodes => {
+                Err(format!("Directed hyperedges of {} members need at least as many nodes, not {}", head_size + tail_size, nodes).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Streams the hyperedges of a model, drawn from a seed: the same model, seed and prefix always give the same
+/// hyperedges. Hyperedges are named `<prefix><position>` and built one at a time, so only the configuration model
+/// (its shuffled stubs) and Chung-Lu (its cumulative degrees) hold more than one in memory
+pub struct Generator {
+    rng: ChaCha8Rng,
+    prefix: String,
+    position: usize,
+    state: State,
+}
+
+enum State {
+    Uniform { nodes: usize, edges: usize, size: usize },
+    // The lexicographic rank of the last emitted combination
+    ErdosRenyi { nodes: usize, size: usize, probability: f64, total: u128, rank: Option<u128> },
+    ChungLu { sampler: WeightedSampler, sizes: Vec<usize> },
+    Configuration { stubs: Vec<usize>, sizes: Vec<usize>, offset: usize },
+    Directed { nodes: usize, edges: usize, head_size: usize, tail_size: usize },
+}
+
+impl Generator {
+    pub fn new(model: &Model, seed: u64, prefix: &str) -> Result<Self, Box<dyn Error>> {
+        model.validate()?;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let state = match model.clone() {
+            Model::Uniform { nodes, edges, size } => State::Uniform { nodes, edges, size },
+            Model::ErdosRenyi { nodes, size, probability } => {
+                let total = binomial(nodes as u128, size as u128).expect("validated");
+                State::ErdosRenyi { nodes, size, probability, total, rank: None }
+            }
+            Model::ChungLu { degrees, sizes } => State::ChungLu { sampler: WeightedSampler::new(&degrees), sizes },
+            Model::Configuration { degrees, sizes } => {
+                let mut stubs: Vec<usize> = degrees.iter().enumerate().flat_map(|(node, &degree)| std::iter::repeat_n(node, degree)).collect();
+                stubs.shuffle(&mut rng);
+                State::Configuration { stubs, sizes, offset: 0 }
+            }
+            Model::Directed { nodes, edges, head_size, tail_size } => State::Directed { nodes, edges, head_size, tail_size },
+        };
+        Ok(Generator { rng, prefix: prefix.to_string(), position: 0, state })
+    }
+
+    // The members of the next hyperedge as (head, tail) node positions
+    fn next_members(&mut self) -> Option<(Vec<usize>, Option<Vec<usize>>)> {
+        let position = self.position;
+        let rng = &mut self.rng;
+        match &mut self.state {
+            State::Uniform { nodes, edges, size } => {
+                (position < *edges).then(|| (index::sample(rng, *nodes, *size).into_vec(), None))
+            }
+            State::ErdosRenyi { nodes, size, probability, total, rank } => {
+                // Geometric jumps over the ranks of all combinations: the gap to the next chosen one
+                let next = match rank {
+                    None => 0,
+                    Some(rank) => *rank + 1,
+                };
+                let gap = if *probability >= 1.0 {
+                    0.0
+                } else if *probability <= 0.0 {
+                    f64::INFINITY
+                } else {
+                    ((1.0 - rng.gen::<f64>()).ln() / (1.0 - *probability).ln()).floor()
+                };
+                if gap >= (*total - next.min(*total)) as f64 {
+                    *rank = Some(*total);
+                    return None;
+                }
+                let chosen = next + gap as u128;
+                *rank = Some(chosen);
+                Some((unrank(chosen, *nodes, *size), None))
+            }
+            State::ChungLu { sampler, sizes } => {
+                let size = *sizes.get(position)?;
+                Some((sampler.sample_distinct(rng, size), None))
+            }
+            State::Configuration { stubs, sizes, offset } => {
+                let size = *sizes.get(position)?;
+                let mut members = stubs[*offset..*offset + size].to_vec();
+                *offset += size;
+                members.sort_unstable();
+                members.dedup();
+                Some((members, None))
+            }
+            State::Directed { nodes, edges, head_size, tail_size } => {
+                if position >= *edges {
+                    return None;
+                }
+                let mut members = index::sample(rng, *nodes, *head_size + *tail_size).into_vec();
+                let tail = members.split_off(*head_size);
+                Some((members, Some(tail)))
+            }
+        }
+    }
+}
+
+impl Iterator for Generator {
+    type Item = Edge;
+
+    fn next(&mut self) -> Option<Edge> {
+        let (head, tail) = self.next_members()?;
+        let id = format!("{}{}", self.prefix, self.position);
+        self.position += 1;
+        let names = |members: Vec<usize>| Box::new(members.into_iter().map(|node| format!("n{}", node)).collect::<Vec<String>>());
+        Some(SimpleHyperEdge {
+            id: id.clone(),
+            name: id,
+            main_properties: Vec::new(),
+            traversable: true,
+            directed: tail.is_some(),
+            head_hyper_nodes: names(head),
+            tail_hyper_nodes: tail.map(names),
+        })
+    }
+}
+
+/// Draws nodes with probability proportional to their weight, without replacement within one sample. The weights
+/// sit in the leaves of a sum tree whose inner nodes are recomputed from their children, never subtracted, so
+/// removing a drawn node is exact even when one weight dwarfs the others
+pub(crate) struct WeightedSampler {
+    weights: Vec<f64>,
+    sums: Vec<f64>,
+    leaves: usize,
+    positive: usize,
+}
+
+impl WeightedSampler {
+    pub(crate) fn new(weights: &[f64]) -> Self {
+        let leaves = weights.len().next_power_of_two();
+        let mut sums = vec![0.0; 2 * leaves];
+        sums[leaves..leaves + weights.len()].copy_from_slice(weights);
+        for inner in (1..leaves).rev() {
+            sums[inner] = sums[2 * inner] + sums[2 * inner + 1];
+        }
+        WeightedSampler { weights: weights.to_vec(), sums, leaves, positive: weights.iter().filter(|&&weight| weight > 0.0).count() }
+    }
+
+    /// `size` distinct nodes, or every node of positive weight when there are fewer, in drawing order. Each draw
+    /// costs O(log n): drawn nodes are set to weight 0 for the rest of the sample and restored afterwards
+    pub(crate) fn sample_distinct<R: Rng>(&mut self, rng: &mut R, size: usize) -> Vec<usize> {
+        let size = size.min(self.positive);
+        let mut drawn: Vec<usize> = Vec::with_capacity(size);
+        for _ in 0..size {
+            let node = self.draw(rng);
+            self.set(node, 0.0);
+            drawn.push(node);
+        }
+        for &node in &drawn {
+            self.set(node, self.weights[node]);
+        }
+        drawn
+    }
+
+    // Descends from the root towards a leaf, never into a subtree without weight
+    fn draw<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut ticket = rng.gen::<f64>() * self.sums[1];
+        let mut inner = 1;
+        while inner < self.leaves {
+            let (left, right) = (self.sums[2 * inner], self.sums[2 * inner + 1]);
+            if (ticket < left && left > 0.0) || right <= 0.0 {
+                inner *= 2;
+            } else {
+                ticket -= left;
+                inner = 2 * inner + 1;
+            }
+        }
+        inner - self.leaves
+    }
+
+    fn set(&mut self, node: usize, weight: f64) {
+        let mut inner = self.leaves + node;
+        self.sums[inner] = weight;
+        while inner > 1 {
+            inner /= 2;
+            self.sums[inner] = self.sums[2 * inner] + self.sums[2 * inner + 1];
+        }
+    }
+}
+
+// C(n, k), `None` when it does not fit
+fn binomial(n: u128, k: u128) -> Option<u128> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result.checked_mul(n - i)? / (i + 1);
+    }
+    Some(result)
+}
+
+// The combination of `size` out of `nodes` with lexicographic rank `rank`. Every element is found by binary search
+// on the number of combinations starting below it
+fn unrank(mut rank: u128, nodes: usize, size: usize) -> Vec<usize> {
+    let mut combination = Vec::with_capacity(size);
+    let mut low = 0usize;
+    for remaining in (1..=size).rev() {
+        let all = binomial((nodes - low) as u128, remaining as u128).expect("validated");
+        // Combinations whose next element is below `element`
+        let before = |element: usize| all - binomial((nodes - element) as u128, remaining as u128).expect("validated");
+        let (mut lo, mut hi) = (low, nodes - remaining);
+        while lo < hi {
+            let middle = (lo + hi).div_ceil(2);
+            if before(middle) <= rank {
+                lo = middle;
+            } else {
+                hi = middle - 1;
+            }
+        }
+        rank -= before(lo);
+        combination.push(lo);
+        low = lo + 1;
+    }
+    combination
+}
+
+/// Fills the flat hyperedge store with synthetic hypergraphs, for tests and load tests
+pub struct GeneratorService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> GeneratorService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        GeneratorService { repository }
+    }
+
+    /// Generates a hypergraph and writes its hyperedges under their ids, `GENERATOR_BATCH_SIZE` at a time.
+    /// Returns the number of hyperedges written. Fails before writing anything when the store already holds a key
+    /// of the generated form `<prefix><number>`, which would be overwritten
+    pub fn generate(&self, model: &Model, seed: u64, prefix: &str) -> Result<usize, Box<dyn Error>> {
+        let started = Instant::now();
+        let mut generator = Generator::new(model, seed, prefix)?;
+        let generated = |key: &str| key[prefix.len()..].parse::<usize>().is_ok();
+        if let Some(key) = self.repository.find_key(prefix, generated)? {
+            return Err(format!("Hyperedge '{}' already exists; generating with prefix '{}' would overwrite it", key, prefix).into());
+        }
+        let mut written = 0;
+        loop {
+            let batch: Vec<Edge> = generator.by_ref().take(GENERATOR_BATCH_SIZE).collect();
+            if batch.is_empty() {
+                break;
+            }
+            self.repository.create_many(&batch)?;
+            written += batch.len();
+        }
+        println!("✅ Generated {} {} hyperedges (seed {}) in {:?}", written, model.as_str(), seed, started.elapsed());
+        Ok(written)
+    }
+}
@@ -0,0 +1,246 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::h_graph_service::{edge_members, set_property};
+use crate::hyper_edge::services::simplify_service::{edge_shape, EdgeShape};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+// Graph properties recording how a combined graph was built
+pub const ALGEBRA_OPERATION_KEY: &str = "algebra.operation";
+pub const ALGEBRA_LEFT_KEY: &str = "algebra.left";
+pub const ALGEBRA_RIGHT_KEY: &str = "algebra.right";
+
+type Graph = HyperGraph<String, String, String>;
+type Edge = SimpleHyperEdge<String, String, String>;
+type Node = HyperNode<String, String, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SetOperation {
+    Union,
+    Intersection,
+    /// Left minus right
+    Difference,
+    SymmetricDifference,
+}
+
+impl SetOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SetOperation::Union => "union",
+            SetOperation::Intersection => "intersection",
+            SetOperation::Difference => "difference",
+            SetOperation::SymmetricDifference => "symmetric_difference",
+        }
+    }
+}
+
+/// When a hyperedge of one graph is the same as a hyperedge of the other
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeMatching {
+    ById,
+    /// Same direction, head set and tail set, whatever the ids
+    ByNodes,
+}
+
+/// What to do when matched hyperedges (or nodes with the same id) hold different values for a property key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    /// The left graph wins
+    KeepLeft,
+    /// The right graph wins, for the properties and the other fields of matched hyperedges (but not their id)
+    KeepRight,
+    /// The values of both sides are united
+    Merge,
+    /// The operation fails
+    Fail,
+}
+
+/// Combines two hypergraphs. Hyperedges are matched according to `matching`; matched pairs are resolved with
+/// `policy` and always keep the left id, otherwise keep the left fields. The listed nodes follow the same set operation on node
+/// ids, and every node used by a resulting hyperedge is listed. Under `ByNodes`, an unmatched right hyperedge whose
+/// id is taken by a left one is renamed `<id>@<right graph id>`, with a `.2`, `.3`, ... suffix while that is taken too
+pub fn combine(left: &Graph, right: &Graph, operation: SetOperation, matching: EdgeMatching, policy: ConflictPolicy) -> Result<Graph, Box<dyn Error>> {
+    let right_positions: HashMap<EdgeKey, usize> = right.hyper_edges.iter().enumerate().map(|(position, edge)| (key(edge, matching), position)).collect();
+    let mut matched_right = vec![false; right.hyper_edges.len()];
+    let mut hyper_edges: Vec<Edge> = Vec::new();
+
+    for edge in &left.hyper_edges {
+        match right_positions.get(&key(edge, matching)) {
+            Some(&position) => {
+                matched_right[position] = true;
+                if matches!(operation, SetOperation::Union | SetOperation::Intersection) {
+                    hyper_edges.push(resolve_edge(edge, &right.hyper_edges[position], policy)?);
+                }
+            }
+            None if operation != SetOperation::Intersection => hyper_edges.push(edge.clone()),
+            None => {}
+        }
+    }
+
+    if matches!(operation, SetOperation::Union | SetOperation::SymmetricDifference) {
+        let mut taken: HashSet<String> = hyper_edges.iter().map(|edge| edge.id.clone()).collect();
+        for (edge, _) in right.hyper_edges.iter().zip(&matched_right).filter(|(_, matched)| !**matched) {
+            let mut edge = edge.clone();
+            if taken.contains(&edge.id) {
+                let renamed = format!("{}@{}", edge.id, right.id);
+                edge.id = renamed.clone();
+                // The new id may be taken as well; every id must stay unique, as the repository keys edges by id
+                let mut attempt = 1;
+                while taken.contains(&edge.id) {
+                    attempt += 1;
+                    edge.id = format!("{}.{}", renamed, attempt);
+                }
+            }
+            taken.insert(edge.id.clone());
+            hyper_edges.push(edge);
+        }
+    }
+
+    let hyper_nodes = combine_nodes(left, right, &hyper_edges, operation, policy)?;
+    let mut properties = left.properties.clone();
+    set_property(&mut properties, ALGEBRA_OPERATION_KEY, operation.as_str().to_string());
+    set_property(&mut properties, ALGEBRA_LEFT_KEY, left.id.clone());
+    set_property(&mut properties, ALGEBRA_RIGHT_KEY, right.id.clone());
+
+    Ok(HyperGraph { id: left.id.clone(), name: left.name.clone(), properties, hyper_nodes, hyper_edges })
+}
+
+// Matching key of a hyperedge: its id or its shape
+#[derive(PartialEq, Eq, Hash)]
+enum EdgeKey<'g> {
+    Id(&'g str),
+    Shape(EdgeShape<'g>),
+}
+
+fn key(edge: &Edge, matching: EdgeMatching) -> EdgeKey<'_> {
+    match matching {
+        EdgeMatching::ById => EdgeKey::Id(&edge.id),
+        EdgeMatching::ByNodes => EdgeKey::Shape(edge_shape(edge)),
+    }
+}
+
+fn resolve_edge(left: &Edge, right: &Edge, policy: ConflictPolicy) -> Result<Edge, Box<dyn Error>> {
+    let mut edge = match policy {
+        ConflictPolicy::KeepRight => right.clone(),
+        _ => left.clone(),
+    };
+    // The id is the left one, so it cannot collide with another left hyperedge
+    edge.id = left.id.clone();
+    edge.main_properties = resolve_properties(&left.main_properties, &right.main_properties, policy, &left.id)?;
+    Ok(edge)
+}
+
+fn combine_nodes(left: &Graph, right: &Graph, edges: &[Edge], operation: SetOperation, policy: ConflictPolicy) -> Result<Vec<Node>, Box<dyn Error>> {
+    let right_nodes: HashMap<&str, &Node> = right.hyper_nodes.iter().map(|node| (node.id.as_str(), node)).collect();
+    let left_ids: HashSet<&str> = left.hyper_nodes.iter().map(|node| node.id.as_str()).collect();
+    let used: HashSet<String> = edges.iter().flat_map(edge_members).collect();
+    let keep = |in_left: bool, in_right: bool, id: &str| {
+        used.contains(id) || match operation {
+            SetOperation::Union => true,
+            SetOperation::Intersection => in_left && in_right,
+            SetOperation::Difference => in_left && !in_right,
+            SetOperation::SymmetricDifference => in_left != in_right,
+        }
+    };
+
+    let mut nodes = Vec::new();
+    for node in &left.hyper_nodes {
+        let other = right_nodes.get(node.id.as_str());
+        if !keep(true, other.is_some(), &node.id) {
+            continue;
+        }
+        let mut node = node.clone();
+        if let Some(other) = other {
+            node.properties = resolve_properties(&node.properties, &other.properties, policy, &node.id)?;
+        }
+        nodes.push(node);
+    }
+    nodes.extend(right.hyper_nodes.iter().filter(|node| !left_ids.contains(node.id.as_str()) && keep(false, true, &node.id)).cloned());
+
+    // Nodes only referenced by hyperedges are listed too
+    let listed: HashSet<String> = nodes.iter().map(|node| node.id.clone()).collect();
+    let mut missing: Vec<String> = used.into_iter().filter(|id| !listed.contains(id)).collect();
+    missing.sort_unstable();
+    nodes.extend(missing.into_iter().map(|id| HyperNode { id, properties: Vec::new() }));
+    Ok(nodes)
+}
+
+// Keys of only one side are kept as they are; keys of both sides with different values follow the policy
+fn resolve_properties(left: &[Property<String, String>], right: &[Property<String, String>], policy: ConflictPolicy, owner: &str) -> Result<Vec<Property<String, String>>, Box<dyn Error>> {
+    let mut properties = left.to_vec();
+    for property in right {
+        let Some(existing) = properties.iter_mut().find(|existing| existing.key == property.key) else {
+            properties.push(property.clone());
+            continue;
+        };
+        if existing.value == property.value {
+            continue;
+        }
+        match policy {
+            ConflictPolicy::KeepLeft => {}
+            ConflictPolicy::KeepRight => existing.value = property.value.clone(),
+            ConflictPolicy::Merge => {
+                for value in &property.value {
+                    if !existing.value.contains(value) {
+                        existing.value.push(value.clone());
+                    }
+                }
+            }
+            ConflictPolicy::Fail => {
+                return Err(format!("Conflicting values for property '{}' of '{}': {:?} and {:?}", property.key, owner, existing.value, property.value).into());
+            }
+        }
+    }
+    Ok(properties)
+}
+
+/// Set operations between named hypergraphs
+pub struct HyperGraphAlgebraService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> HyperGraphAlgebraService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        HyperGraphAlgebraService { repository }
+    }
+
+    /// Combines the stored hypergraphs `left` and `right` and saves the result as `result_key`, recording the
+    /// operation and both operands as graph properties
+    pub fn combine(&self, left: &str, right: &str, operation: SetOperation, matching: EdgeMatching, policy: ConflictPolicy, result_key: &str) -> Result<Graph, Box<dyn Error>> {
+        let left_graph = self.graph(left)?;
+        let right_graph = self.graph(right)?;
+        let mut result = combine(&left_graph, &right_graph, operation, matching, policy)?;
+
+        result.id = result_key.to_string();
+        result.name = result_key.to_string();
+        set_property(&mut result.properties, ALGEBRA_LEFT_KEY, left.to_string());
+        set_property(&mut result.properties, ALGEBRA_RIGHT_KEY, right.to_string());
+        self.repository.create(result_key, &result)?;
+        println!("✅ Saved {} of '{}' and '{}' as '{}' ({} nodes, {} hyperedges)",
+                 operation.as_str(), left, right, result_key, result.hyper_nodes.len(), result.hyper_edges.len());
+
+        Ok(result)
+    }
+
+    pub fn union(&self, left: &str, right: &str, matching: EdgeMatching, policy: ConflictPolicy, result_key: &str) -> Result<Graph, Box<dyn Error>> {
+        self.combine(left, right, SetOperation::Union, matching, policy, result_key)
+    }
+
+    pub fn intersection(&self, left: &str, right: &str, matching: EdgeMatching, policy: ConflictPolicy, result_key: &str) -> Result<Graph, Box<dyn Error>> {
+        self.combine(left, right, SetOperation::Intersection, matching, policy, result_key)
+    }
+
+    pub fn difference(&self, left: &str, right: &str, matching: EdgeMatching, result_key: &str) -> Result<Graph, Box<dyn Error>> {
+        self.combine(left, right, SetOperation::Difference, matching, ConflictPolicy::KeepLeft, result_key)
+    }
+
+    pub fn symmetric_difference(&self, left: &str, right: &str, matching: EdgeMatching, result_key: &str) -> Result<Graph, Box<dyn Error>> {
+        self.combine(left, right, SetOperation::SymmetricDifference, matching, ConflictPolicy::KeepLeft, result_key)
+    }
+
+    fn graph(&self, key: &str) -> Result<Graph, Box<dyn Error>> {
+        Ok(self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?)
+    }
+}
@@ -0,0 +1,166 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::error::Error;
+
+/// A path between two nodes: `edges[i]` is the hyperedge connecting `nodes[i]` and `nodes[i + 1]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HyperPath {
+    pub nodes: Vec<String>,
+    pub edges: Vec<String>,
+}
+
+/// Returns the members of a hyperedge (head nodes first, then tail nodes) without duplicates
+pub fn edge_members(edge: &SimpleHyperEdge<String, String, String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let tail = edge.tail_hyper_nodes.as_deref().map_or(&[][..], |nodes| &nodes[..]);
+
+    edge.head_hyper_nodes.iter()
+        .chain(tail.iter())
+        .filter(|node| seen.insert(node.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Sets a single-valued property, replacing any property with the same key
+pub fn set_property(properties: &mut Vec<Property<String, String>>, key: &str, value: String) {
+    match properties.iter_mut().find(|property| property.key == key) {
+        Some(property) => property.value = vec![value],
+        None => properties.push(Property { key: key.to_string(), value: vec![value] }),
+    }
+}
+
+/// Sets the `key` property of many nodes at once from (node id, value) pairs. Nodes only referenced by edges are
+/// added to `hyper_nodes`
+pub fn set_node_properties<I: IntoIterator<Item = (String, String)>>(graph: &mut HyperGraph<String, String, String>, key: &str, values: I) {
+    let mut positions: HashMap<String, usize> = graph.hyper_nodes.iter()
+        .enumerate()
+        .map(|(position, node)| (node.id.clone(), position))
+        .collect();
+
+    for (id, value) in values {
+        let position = *positions.entry(id.clone()).or_insert_with(|| {
+            graph.hyper_nodes.push(HyperNode { id, properties: Vec::new() });
+            graph.hyper_nodes.len() - 1
+        });
+        set_property(&mut graph.hyper_nodes[position].properties, key, value);
+    }
+}
+
+pub struct HyperGraphService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> HyperGraphService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        HyperGraphService { repository }
+    }
+
+    /// Loads every SimpleHyperEdge of the repository into one hypergraph called `name`
+    pub fn load_graph(&self, name: &str) -> Result<HyperGraph<String, String, String>, Box<dyn Error>> {
+        let edges = self.repository.get_all()?;
+        Ok(self.build_graph(name, edges))
+    }
+
+    /// Builds a hypergraph from a list of edges, collecting the nodes in order of first appearance
+    pub fn build_graph(&self, name: &str, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        let mut seen = HashSet::new();
+        let mut hyper_nodes = Vec::new();
+
+        for edge in &edges {
+            for node in edge_members(edge) {
+                if seen.insert(node.clone()) {
+                    hyper_nodes.push(HyperNode { id: node, properties: Vec::new() });
+                }
+            }
+        }
+
+        HyperGraph {
+            id: name.to_string(),
+            name: name.to_string(),
+            properties: Vec::new(),
+            hyper_nodes,
+            hyper_edges: edges,
+        }
+    }
+
+    // method to get the edges that contain the given node
+    pub fn edges_of<'g>(&self, graph: &'g HyperGraph<String, String, String>, node: &str) -> Vec<&'g SimpleHyperEdge<String, String, String>> {
+        graph.hyper_edges.iter()
+            .filter(|edge| edge_members(edge).iter().any(|member| member == node))
+            .collect()
+    }
+
+    /// Returns every node sharing at least one edge with `node`, together with the ids of the shared edges
+    pub fn neighbors(&self, graph: &HyperGraph<String, String, String>, node: &str) -> Vec<(String, Vec<String>)> {
+        let mut neighbors: Vec<(String, Vec<String>)> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+
+        for edge in self.edges_of(graph, node) {
+            for member in edge_members(edge) {
+                if member == node {
+                    continue;
+                }
+                let position = *positions.entry(member.clone()).or_insert_with(|| {
+                    neighbors.push((member.clone(), Vec::new()));
+                    neighbors.len() - 1
+                });
+                neighbors[position].1.push(edge.id.clone());
+            }
+        }
+
+        neighbors
+    }
+
+    /// Breadth-first search for a path with the fewest edges between two nodes
+    pub fn shortest_path(&self, graph: &HyperGraph<String, String, String>, from: &str, to: &str) -> Option<HyperPath> {
+        let mut node_edges: HashMap<&str, Vec<usize>> = HashMap::new();
+        let members: Vec<Vec<String>> = graph.hyper_edges.iter().map(edge_members).collect();
+        for (index, nodes) in members.iter().enumerate() {
+            for node in nodes {
+                node_edges.entry(node.as_str()).or_default().push(index);
+            }
+        }
+
+        if !node_edges.contains_key(from) || !node_edges.contains_key(to) {
+            return None;
+        }
+
+        // Remember for every reached node the node and edge it was reached from
+        let mut parents: HashMap<&str, Option<(&str, usize)>> = HashMap::new();
+        let mut queue = VecDeque::new();
+        parents.insert(from, None);
+        queue.push_back(from);
+
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                break;
+            }
+            for &edge_index in &node_edges[current] {
+                for next in &members[edge_index] {
+                    if !parents.contains_key(next.as_str()) {
+                        parents.insert(next.as_str(), Some((current, edge_index)));
+                        queue.push_back(next.as_str());
+                    }
+                }
+            }
+        }
+
+        if !parents.contains_key(to) {
+            return None;
+        }
+
+        let mut path = HyperPath { nodes: vec![to.to_string()], edges: Vec::new() };
+        let mut current = to;
+        while let Some(Some((previous, edge_index))) = parents.get(current) {
+            path.nodes.push(previous.to_string());
+            path.edges.push(graph.hyper_edges[*edge_index].id.clone());
+            current = previous;
+        }
+        path.nodes.reverse();
+        path.edges.reverse();
+
+        Some(path)
+    }
+}
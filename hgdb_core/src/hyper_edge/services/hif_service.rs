@@ -0,0 +1,338 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::h_graph_service::edge_members;
+use crate::hyper_edge::services::random_walk_service::VERTEX_WEIGHTS_KEY;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::io::{Read, Write};
+
+type Graph = HyperGraph<String, String, String>;
+type Edge = SimpleHyperEdge<String, String, String>;
+type Properties = Vec<Property<String, String>>;
+
+/// Edge `main_properties` holding the attributes of its incidences as `node:value` entries, one property per
+/// attribute, named `incidence.<attribute>`. Incidence weights go to `vertex_weights`, read by the random walks.
+/// Entries are split after the member id they start with, so values and ids may both contain ':'
+pub const INCIDENCE_PREFIX: &str = "incidence.";
+
+/// Attributes under this prefix stand for fields of the graph or of `SimpleHyperEdge` rather than for properties,
+/// so that properties named `name`, `traversable` or `directed` are kept apart from the fields
+pub const FIELD_PREFIX: &str = "hgdb.";
+const NAME_ATTR: &str = "hgdb.name";
+const TRAVERSABLE_ATTR: &str = "hgdb.traversable";
+const DIRECTED_ATTR: &str = "hgdb.directed";
+
+/// A hypergraph in the Hypergraph Interchange Format (HIF) shared with HyperNetX, XGI and HyperGraphX
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Hif {
+    #[serde(rename = "network-type", default, skip_serializing_if = "Option::is_none")]
+    pub network_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub metadata: Map<String, Value>,
+    #[serde(default)]
+    pub nodes: Vec<HifNode>,
+    #[serde(default)]
+    pub edges: Vec<HifEdge>,
+    pub incidences: Vec<HifIncidence>,
+}
+
+/// HIF ids are strings or integers
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum HifId {
+    Text(String),
+    Number(serde_json::Number),
+}
+
+impl std::fmt::Display for HifId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HifId::Text(text) => write!(f, "{}", text),
+            HifId::Number(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HifNode {
+    pub node: HifId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub attrs: Map<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HifEdge {
+    pub edge: HifId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub attrs: Map<String, Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HifIncidence {
+    pub edge: HifId,
+    pub node: HifId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// `head` or `tail`, in directed networks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub attrs: Map<String, Value>,
+}
+
+/// Converts a hypergraph to HIF. The network is directed when some hyperedge is directed or has tail members; its
+/// incidences then carry the head or tail role, and hyperedges whose `directed` flag differs from the network
+/// get a `directed` attribute. `main_properties` and node properties become attributes (a single value as a
+/// string, several as an array), the graph properties the metadata. The hyperedge name is an attribute when it
+/// differs from the id, and so is `traversable` when false; these fields are named with `FIELD_PREFIX`, and
+/// properties under that prefix are rejected
+pub fn to_hif(graph: &Graph) -> Result<Hif, Box<dyn Error>> {
+    let directed = graph.hyper_edges.iter().any(|edge| edge.directed || edge.tail_hyper_nodes.as_ref().is_some_and(|tail| !tail.is_empty()));
+
+    let mut metadata = to_attributes(&graph.properties, &graph.id)?;
+    metadata.insert(NAME_ATTR.to_string(), Value::String(graph.name.clone()));
+
+    let nodes = graph.hyper_nodes.iter()
+        .map(|node| Ok(HifNode { node: HifId::Text(node.id.clone()), weight: None, attrs: to_attributes(&node.properties, &node.id)? }))
+        .collect::<Result<_, Box<dyn Error>>>()?;
+
+    let mut edges = Vec::new();
+    let mut incidences = Vec::new();
+    for edge in &graph.hyper_edges {
+        let (incidence_properties, properties): (Properties, Properties) = edge.main_properties.iter()
+            .cloned()
+            .partition(|property| property.key == VERTEX_WEIGHTS_KEY || property.key.starts_with(INCIDENCE_PREFIX));
+
+        let mut attrs = to_attributes(&properties, &edge.id)?;
+        if edge.name != edge.id {
+            attrs.insert(NAME_ATTR.to_string(), Value::String(edge.name.clone()));
+        }
+        if !edge.traversable {
+            attrs.insert(TRAVERSABLE_ATTR.to_string(), Value::Bool(false));
+        }
+        if edge.directed != directed {
+            attrs.insert(DIRECTED_ATTR.to_string(), Value::Bool(edge.directed));
+        }
+        edges.push(HifEdge { edge: HifId::Text(edge.id.clone()), weight: None, attrs });
+
+        // node -> (weight, attributes) of its incidence
+        let members = edge_members(edge);
+        let mut per_node: HashMap<&str, (Option<f64>, Map<String, Value>)> = HashMap::new();
+        for property in &incidence_properties {
+            for entry in &property.value {
+                let Some((node, value)) = split_entry(entry, &members) else {
+                    continue;
+                };
+                let slot = per_node.entry(node).or_default();
+                match property.key.strip_prefix(INCIDENCE_PREFIX) {
+                    Some(attribute) => {
+                        slot.1.insert(attribute.to_string(), Value::String(value.to_string()));
+                    }
+                    None => slot.0 = value.trim().parse().ok(),
+                }
+            }
+        }
+
+        let roles = edge.head_hyper_nodes.iter().map(|node| (node, "head"))
+            .chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()).map(|node| (node, "tail")));
+        for (node, role) in roles {
+            let (weight, attrs) = per_node.get(node.as_str()).cloned().unwrap_or_default();
+            incidences.push(HifIncidence {
+                edge: HifId::Text(edge.id.clone()),
+                node: HifId::Text(node.clone()),
+                weight,
+                direction: directed.then(|| role.to_string()),
+                attrs,
+            });
+        }
+    }
+
+    Ok(Hif {
+        network_type: Some(if directed { "directed" } else { "undirected" }.to_string()),
+        metadata,
+        nodes,
+        edges,
+        incidences,
+    })
+}
+
+// Splits a `node:value` entry after the longest member id it starts with
+fn split_entry<'e>(entry: &'e str, members: &[String]) -> Option<(&'e str, &'e str)> {
+    members.iter()
+        .filter(|member| entry.len() > member.len() && entry.starts_with(member.as_str()) && entry.as_bytes()[member.len()] == b':')
+        .max_by_key(|member| member.len())
+        .map(|member| (&entry[..member.len()], &entry[member.len() + 1..]))
+}
+
+/// Converts HIF to a hypergraph with id `key`, reversing `to_hif`. Hyperedges and nodes only named by incidences
+/// are created; in directed networks incidences without a direction are head members. Other attribute values
+/// (numbers, booleans, objects) are stored as their JSON text, node and hyperedge weights as a `weight` property
+pub fn from_hif(hif: &Hif, key: &str) -> Result<Graph, Box<dyn Error>> {
+    let directed = match hif.network_type.as_deref() {
+        None | Some("undirected") | Some("asc") => false,
+        Some("directed") => true,
+        Some(other) => return Err(format!("Unknown HIF network type '{}'", other).into()),
+    };
+
+    let mut properties = to_properties(&hif.metadata, None);
+    let name = take_text(&mut properties, NAME_ATTR).unwrap_or_else(|| key.to_string());
+
+    let mut hyper_nodes: Vec<HyperNode<String, String, String>> = hif.nodes.iter()
+        .map(|node| HyperNode { id: node.node.to_string(), properties: to_properties(&node.attrs, node.weight) })
+        .collect();
+
+    let mut hyper_edges: Vec<Edge> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut add_edge = |id: String, attrs: &Map<String, Value>, weight: Option<f64>, hyper_edges: &mut Vec<Edge>| -> Result<usize, Box<dyn Error>> {
+        if let Some(&position) = positions.get(&id) {
+            return Ok(position);
+        }
+        let mut main_properties = to_properties(attrs, weight);
+        let name = take_text(&mut main_properties, NAME_ATTR).unwrap_or_else(|| id.clone());
+        let traversable = take_flag(&mut main_properties, TRAVERSABLE_ATTR)?.unwrap_or(true);
+        let edge_directed = take_flag(&mut main_properties, DIRECTED_ATTR)?.unwrap_or(directed);
+        positions.insert(id.clone(), hyper_edges.len());
+        hyper_edges.push(SimpleHyperEdge {
+            id,
+            name,
+            main_properties,
+            traversable,
+            directed: edge_directed,
+            head_hyper_nodes: Box::default(),
+            tail_hyper_nodes: None,
+        });
+        Ok(hyper_edges.len() - 1)
+    };
+    for edge in &hif.edges {
+        add_edge(edge.edge.to_string(), &edge.attrs, edge.weight, &mut hyper_edges)?;
+    }
+
+    for incidence in &hif.incidences {
+        let position = add_edge(incidence.edge.to_string(), &Map::new(), None, &mut hyper_edges)?;
+        let edge = &mut hyper_edges[position];
+        let node = incidence.node.to_string();
+
+        let members = match incidence.direction.as_deref() {
+            Some("tail") if directed => edge.tail_hyper_nodes.get_or_insert_with(Box::default),
+            None | Some("head") | Some("tail") => &mut edge.head_hyper_nodes,
+            Some(other) => return Err(format!("Unknown incidence direction '{}' in hyperedge '{}'", other, edge.id).into()),
+        };
+        if members.contains(&node) {
+            continue;
+        }
+        members.push(node.clone());
+
+        if let Some(weight) = incidence.weight {
+            add_value(&mut edge.main_properties, VERTEX_WEIGHTS_KEY, format!("{}:{}", node, weight));
+        }
+        for (attribute, value) in &incidence.attrs {
+            add_value(&mut edge.main_properties, &format!("{}{}", INCIDENCE_PREFIX, attribute), format!("{}:{}", node, text(value)));
+        }
+    }
+
+    let listed: HashSet<String> = hyper_nodes.iter().map(|node| node.id.clone()).collect();
+    let mut missing: Vec<String> = hif.incidences.iter().map(|incidence| incidence.node.to_string()).filter(|node| !listed.contains(node)).collect();
+    missing.sort();
+    missing.dedup();
+    hyper_nodes.extend(missing.into_iter().map(|id| HyperNode { id, properties: Vec::new() }));
+
+    Ok(HyperGraph { id: key.to_string(), name, properties, hyper_nodes, hyper_edges })
+}
+
+fn to_attributes(properties: &[Property<String, String>], owner: &str) -> Result<Map<String, Value>, Box<dyn Error>> {
+    properties.iter()
+        .map(|property| {
+            if property.key.starts_with(FIELD_PREFIX) {
+                return Err(format!("Property '{}' of '{}' uses the reserved prefix '{}'", property.key, owner, FIELD_PREFIX).into());
+            }
+            let value = match property.value.as_slice() {
+                [single] => Value::String(single.clone()),
+                values => Value::Array(values.iter().cloned().map(Value::String).collect()),
+            };
+            Ok((property.key.clone(), value))
+        })
+        .collect()
+}
+
+fn to_properties(attrs: &Map<String, Value>, weight: Option<f64>) -> Vec<Property<String, String>> {
+    let mut properties: Vec<Property<String, String>> = attrs.iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Array(values) => values.iter().map(text).collect(),
+                Value::Null => Vec::new(),
+                other => vec![text(other)],
+            };
+            Property { key: key.clone(), value }
+        })
+        .collect();
+    if let Some(weight) = weight {
+        properties.push(Property { key: "weight".to_string(), value: vec![weight.to_string()] });
+    }
+    properties
+}
+
+// Strings as they are, anything else as JSON
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn add_value(properties: &mut Vec<Property<String, String>>, key: &str, value: String) {
+    match properties.iter_mut().find(|property| property.key == key) {
+        Some(property) => property.value.push(value),
+        None => properties.push(Property { key: key.to_string(), value: vec![value] }),
+    }
+}
+
+// Removes a property and returns its single value
+fn take_text(properties: &mut Vec<Property<String, String>>, key: &str) -> Option<String> {
+    let position = properties.iter().position(|property| property.key == key)?;
+    properties.remove(position).value.into_iter().next()
+}
+
+fn take_flag(properties: &mut Vec<Property<String, String>>, key: &str) -> Result<Option<bool>, Box<dyn Error>> {
+    match take_text(properties, key) {
+        None => Ok(None),
+        Some(flag) => Ok(Some(flag.parse().map_err(|_| format!("Attribute '{}' should be true or false, not '{}'", key, flag))?)),
+    }
+}
+
+/// HIF import and export of the named hypergraphs
+pub struct HifService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> HifService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        HifService { repository }
+    }
+
+    /// Writes the stored hypergraph `key` as HIF JSON
+    pub fn export<W: Write>(&self, key: &str, writer: &mut W) -> Result<Hif, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let hif = to_hif(&graph)?;
+        serde_json::to_writer_pretty(&mut *writer, &hif)?;
+        writer.flush()?;
+        println!("✅ Exported '{}' as HIF ({} edges, {} incidences)", key, hif.edges.len(), hif.incidences.len());
+        Ok(hif)
+    }
+
+    /// Reads HIF JSON and stores it as the named hypergraph `key`, replacing any graph stored under that key
+    pub fn import<R: Read>(&self, reader: R, key: &str) -> Result<Graph, Box<dyn Error>> {
+        let hif: Hif = serde_json::from_reader(reader)?;
+        let graph = from_hif(&hif, key)?;
+        self.repository.create(key, &graph)?;
+        println!("✅ Imported HIF as '{}' ({} nodes, {} hyperedges)", key, graph.hyper_nodes.len(), graph.hyper_edges.len());
+        Ok(graph)
+    }
+}
@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// A value produced while evaluating an HQL expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    List(Vec<Value>),
+}
+
+impl Value {
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(value) => *value,
+            Value::Int(value) => *value != 0,
+            Value::Float(value) => *value != 0.0,
+            Value::Str(value) => !value.is_empty(),
+            Value::List(values) => !values.is_empty(),
+        }
+    }
+
+    /// Numeric view of the value; strings holding a number are coerced so properties can be compared to numbers
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(value) => Some(*value as f64),
+            Value::Float(value) => Some(*value),
+            Value::Str(value) => value.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Total order used by comparisons and ORDER BY: nulls first, then booleans, numbers, strings and lists
+    pub fn compare(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (Value::Null, _) => Ordering::Less,
+            (_, Value::Null) => Ordering::Greater,
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            // Property values are stored as strings, so two numeric strings compare as numbers
+            (Value::Str(a), Value::Str(b)) => match (self.as_f64(), other.as_f64()) {
+                (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+                _ => a.cmp(b),
+            },
+            (Value::List(a), Value::List(b)) => {
+                for (x, y) in a.iter().zip(b) {
+                    let ordering = x.compare(y);
+                    if ordering != Ordering::Equal {
+                        return ordering;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            _ => match (self.as_f64(), other.as_f64()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+                _ => self.rank().cmp(&other.rank()).then_with(|| self.to_string().cmp(&other.to_string())),
+            },
+        }
+    }
+
+    /// Equality used by `=`: a list equals a scalar when one of its elements does
+    pub fn matches(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::List(values), scalar) | (scalar, Value::List(values)) if !matches!(scalar, Value::List(_)) => {
+                values.iter().any(|value| value.matches(scalar))
+            }
+            _ => self.compare(other) == Ordering::Equal,
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Bool(_) => 1,
+            Value::Int(_) | Value::Float(_) => 2,
+            Value::Str(_) => 3,
+            Value::List(_) => 4,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Str(value) => write!(f, "{}", value),
+            Value::List(values) => {
+                let items: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Label {
+    Node,
+    Edge,
+}
+
+/// Which side of a hyperedge a node has to be on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Head,
+    Tail,
+    Any,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// `(e:Edge)` or `(n:Node)`
+    Single { var: String, label: Label },
+    /// `(n:Node)-[:HEAD]-(e:Edge)`: every node `n` incident to edge `e` in the given role
+    Incidence { node: String, edge: String, role: Role },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AggregateFunc {
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Value),
+    /// A bare identifier: a pattern variable or a RETURN alias
+    Var(String),
+    /// `e.name` resolves a built-in field first and a property second, `e['name']` always reads a property
+    Field { var: String, field: String, property_only: bool },
+    List(Vec<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare { op: CompareOp, left: Box<Expr>, right: Box<Expr> },
+    In { item: Box<Expr>, list: Box<Expr> },
+    /// `COUNT(*)` has no argument
+    Aggregate { func: AggregateFunc, arg: Option<Box<Expr>> },
+}
+
+impl Expr {
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            Expr::Aggregate { .. } => true,
+            Expr::Literal(_) | Expr::Var(_) | Expr::Field { .. } => false,
+            Expr::List(items) => items.iter().any(Expr::contains_aggregate),
+            Expr::Not(inner) => inner.contains_aggregate(),
+            Expr::And(left, right) | Expr::Or(left, right) => left.contains_aggregate() || right.contains_aggregate(),
+            Expr::Compare { left, right, .. } => left.contains_aggregate() || right.contains_aggregate(),
+            Expr::In { item, list } => item.contains_aggregate() || list.contains_aggregate(),
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(Value::Str(value)) => write!(f, "'{}'", value),
+            Expr::Literal(value) => write!(f, "{}", value),
+            Expr::Var(var) => write!(f, "{}", var),
+            Expr::Field { var, field, property_only: false } => write!(f, "{}.{}", var, field),
+            Expr::Field { var, field, property_only: true } => write!(f, "{}['{}']", var, field),
+            Expr::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+            Expr::Not(inner) => write!(f, "NOT {}", inner),
+            Expr::And(left, right) => write!(f, "({} AND {})", left, right),
+            Expr::Or(left, right) => write!(f, "({} OR {})", left, right),
+            Expr::Compare { op, left, right } => {
+                let op = match op {
+                    CompareOp::Eq => "=",
+                    CompareOp::NotEq => "!=",
+                    CompareOp::Lt => "<",
+                    CompareOp::LtEq => "<=",
+                    CompareOp::Gt => ">",
+                    CompareOp::GtEq => ">=",
+                };
+                write!(f, "{} {} {}", left, op, right)
+            }
+            Expr::In { item, list } => write!(f, "{} IN {}", item, list),
+            Expr::Aggregate { func, arg } => {
+                let name = match func {
+                    AggregateFunc::Count => "COUNT",
+                    AggregateFunc::Sum => "SUM",
+                    AggregateFunc::Avg => "AVG",
+                    AggregateFunc::Min => "MIN",
+                    AggregateFunc::Max => "MAX",
+                };
+                match arg {
+                    Some(arg) => write!(f, "{}({})", name, arg),
+                    None => write!(f, "{}(*)", name),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projection {
+    pub expr: Expr,
+    pub alias: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrderItem {
+    pub expr: Expr,
+    pub descending: bool,
+}
+
+/// A parsed `[EXPLAIN] MATCH ... [WHERE ...] [RETURN ...] [ORDER BY ...] [LIMIT ...]` query
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    pub explain: bool,
+    pub pattern: Pattern,
+    pub filter: Option<Expr>,
+    pub projections: Vec<Projection>,
+    pub order_by: Vec<OrderItem>,
+    pub limit: Option<usize>,
+}
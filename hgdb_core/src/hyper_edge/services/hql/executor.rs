@@ -0,0 +1,337 @@
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use crate::hyper_edge::entity::simple_h_edge::{Property, SimpleHyperEdge};
+use crate::hyper_edge::services::h_graph_service::edge_members;
+use crate::hyper_edge::services::hql::ast::{AggregateFunc, CompareOp, Expr, Label, Projection, Role, Value};
+use crate::hyper_edge::services::hql::plan::{LogicalPlan, QueryPlan};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// The rows returned by a query, one value per column
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Bound<'g> {
+    Edge(usize),
+    Node(&'g str),
+}
+
+type Binding<'g> = Vec<(&'g str, Bound<'g>)>;
+
+/// Executes query plans over a loaded hypergraph, using id and incidence indexes built on construction
+pub struct HqlExecutor<'g> {
+    graph: &'g HyperGraph<String, String, String>,
+    edge_index: HashMap<&'g str, usize>,
+    node_index: HashMap<&'g str, usize>,
+    // node id -> (edge position, role of the node in that edge)
+    incidence_index: HashMap<&'g str, Vec<(usize, Role)>>,
+}
+
+impl<'g> HqlExecutor<'g> {
+    pub fn new(graph: &'g HyperGraph<String, String, String>) -> Self {
+        let mut incidence_index: HashMap<&'g str, Vec<(usize, Role)>> = HashMap::new();
+        for (position, edge) in graph.hyper_edges.iter().enumerate() {
+            for node in edge.head_hyper_nodes.iter() {
+                incidence_index.entry(node.as_str()).or_default().push((position, Role::Head));
+            }
+            for node in edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()) {
+                incidence_index.entry(node.as_str()).or_default().push((position, Role::Tail));
+            }
+        }
+
+        HqlExecutor {
+            graph,
+            edge_index: graph.hyper_edges.iter().enumerate().map(|(position, edge)| (edge.id.as_str(), position)).collect(),
+            node_index: graph.hyper_nodes.iter().enumerate().map(|(position, node)| (node.id.as_str(), position)).collect(),
+            incidence_index,
+        }
+    }
+
+    pub fn execute(&self, plan: &'g QueryPlan) -> Result<QueryResult, Box<dyn Error>> {
+        let mut rows = self.execute_rows(&plan.root)?;
+        for row in &mut rows {
+            row.truncate(plan.columns.len());
+        }
+        Ok(QueryResult { columns: plan.columns.clone(), rows })
+    }
+
+    fn execute_rows(&self, plan: &'g LogicalPlan) -> Result<Vec<Vec<Value>>, Box<dyn Error>> {
+        match plan {
+            LogicalPlan::Project { input, items } => {
+                self.execute_bindings(input)?.iter()
+                    .map(|binding| items.iter().map(|item| self.evaluate(&item.expr, binding)).collect())
+                    .collect()
+            }
+            LogicalPlan::Aggregate { input, items } => self.aggregate(&self.execute_bindings(input)?, items),
+            LogicalPlan::Sort { input, keys, .. } => {
+                let mut rows = self.execute_rows(input)?;
+                rows.sort_by(|a, b| {
+                    keys.iter()
+                        .map(|(column, descending)| {
+                            let ordering = a[*column].compare(&b[*column]);
+                            if *descending { ordering.reverse() } else { ordering }
+                        })
+                        .find(|ordering| *ordering != Ordering::Equal)
+                        .unwrap_or(Ordering::Equal)
+                });
+                Ok(rows)
+            }
+            LogicalPlan::Limit { input, count } => {
+                let mut rows = self.execute_rows(input)?;
+                rows.truncate(*count);
+                Ok(rows)
+            }
+            _ => Err("HQL: plan does not produce output rows".into()),
+        }
+    }
+
+    fn execute_bindings(&self, plan: &'g LogicalPlan) -> Result<Vec<Binding<'g>>, Box<dyn Error>> {
+        match plan {
+            LogicalPlan::EdgeScan { var } => {
+                Ok((0..self.graph.hyper_edges.len()).map(|position| vec![(var.as_str(), Bound::Edge(position))]).collect())
+            }
+            LogicalPlan::EdgeLookup { var, ids } => Ok(ids.iter()
+                .filter_map(|id| self.edge_index.get(id.as_str()))
+                .map(|position| vec![(var.as_str(), Bound::Edge(*position))])
+                .collect()),
+            LogicalPlan::EdgesOfNodes { var, nodes, role } => {
+                let mut positions: Vec<usize> = nodes.iter()
+                    .filter_map(|node| self.incidence_index.get(node.as_str()))
+                    .flatten()
+                    .filter(|(_, incidence_role)| *role == Role::Any || incidence_role == role)
+                    .map(|(position, _)| *position)
+                    .collect();
+                positions.sort_unstable();
+                positions.dedup();
+                Ok(positions.into_iter().map(|position| vec![(var.as_str(), Bound::Edge(position))]).collect())
+            }
+            LogicalPlan::NodeScan { var } => Ok(self.graph.hyper_nodes.iter()
+                .map(|node| vec![(var.as_str(), Bound::Node(node.id.as_str()))])
+                .collect()),
+            LogicalPlan::NodeLookup { var, ids } => Ok(ids.iter()
+                .filter_map(|id| self.incidence_index.get_key_value(id.as_str()).map(|(id, _)| *id)
+                    .or_else(|| self.node_index.get_key_value(id.as_str()).map(|(id, _)| *id)))
+                .map(|id| vec![(var.as_str(), Bound::Node(id))])
+                .collect()),
+            LogicalPlan::Expand { input, from, from_label, to, role } => {
+                let mut expanded = Vec::new();
+                for binding in self.execute_bindings(input)? {
+                    let targets: Vec<Bound<'g>> = match (from_label, lookup(&binding, from)) {
+                        (Label::Edge, Some(Bound::Edge(position))) => {
+                            let edge = &self.graph.hyper_edges[position];
+                            let tail = edge.tail_hyper_nodes.as_deref().map_or(&[][..], |tail| &tail[..]);
+                            let mut nodes: Vec<&'g String> = match role {
+                                Role::Head => edge.head_hyper_nodes.iter().collect(),
+                                Role::Tail => tail.iter().collect(),
+                                Role::Any => edge.head_hyper_nodes.iter().chain(tail.iter()).collect(),
+                            };
+                            let mut seen = Vec::new();
+                            nodes.retain(|node| if seen.contains(node) { false } else { seen.push(*node); true });
+                            nodes.into_iter().map(|node| Bound::Node(node.as_str())).collect()
+                        }
+                        (Label::Node, Some(Bound::Node(node))) => {
+                            let mut positions: Vec<usize> = self.incidence_index.get(node).into_iter()
+                                .flatten()
+                                .filter(|(_, incidence_role)| *role == Role::Any || incidence_role == role)
+                                .map(|(position, _)| *position)
+                                .collect();
+                            positions.dedup();
+                            positions.into_iter().map(Bound::Edge).collect()
+                        }
+                        _ => return Err(format!("HQL: '{}' is not bound to a {:?}", from, from_label).into()),
+                    };
+
+                    for target in targets {
+                        let mut row = binding.clone();
+                        row.push((to.as_str(), target));
+                        expanded.push(row);
+                    }
+                }
+                Ok(expanded)
+            }
+            LogicalPlan::Filter { input, predicate } => {
+                let bindings = self.execute_bindings(input)?;
+                let mut kept = Vec::new();
+                for binding in bindings {
+                    if self.evaluate(predicate, &binding)?.is_truthy() {
+                        kept.push(binding);
+                    }
+                }
+                Ok(kept)
+            }
+            _ => Err("HQL: plan does not produce bindings".into()),
+        }
+    }
+
+    fn aggregate(&self, bindings: &[Binding<'g>], items: &[Projection]) -> Result<Vec<Vec<Value>>, Box<dyn Error>> {
+        let key_columns: Vec<usize> = (0..items.len()).filter(|index| !items[*index].expr.contains_aggregate()).collect();
+
+        // Groups keep the order in which their first row was seen
+        let mut groups: Vec<(Vec<Value>, Vec<&Binding<'g>>)> = Vec::new();
+        let mut group_index: HashMap<String, usize> = HashMap::new();
+        for binding in bindings {
+            let key: Vec<Value> = key_columns.iter()
+                .map(|column| self.evaluate(&items[*column].expr, binding))
+                .collect::<Result<_, _>>()?;
+            let index = *group_index.entry(format!("{:?}", key)).or_insert_with(|| {
+                groups.push((key, Vec::new()));
+                groups.len() - 1
+            });
+            groups[index].1.push(binding);
+        }
+        if groups.is_empty() && key_columns.is_empty() {
+            groups.push((Vec::new(), Vec::new()));
+        }
+
+        let mut rows = Vec::new();
+        for (key, members) in groups {
+            let mut key_values = key.into_iter();
+            let mut row = Vec::new();
+            for item in items {
+                match &item.expr {
+                    Expr::Aggregate { func, arg } => row.push(self.aggregate_value(*func, arg.as_deref(), &members)?),
+                    _ => row.push(key_values.next().unwrap_or(Value::Null)),
+                }
+            }
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+
+    fn aggregate_value(&self, func: AggregateFunc, arg: Option<&Expr>, members: &[&Binding<'g>]) -> Result<Value, Box<dyn Error>> {
+        let Some(arg) = arg else {
+            return Ok(Value::Int(members.len() as i64));
+        };
+
+        let values: Vec<Value> = members.iter()
+            .map(|binding| self.evaluate(arg, binding))
+            .collect::<Result<Vec<Value>, _>>()?
+            .into_iter()
+            .filter(|value| *value != Value::Null)
+            .collect();
+
+        Ok(match func {
+            AggregateFunc::Count => Value::Int(values.len() as i64),
+            AggregateFunc::Sum | AggregateFunc::Avg => {
+                let numbers: Vec<f64> = values.iter().filter_map(Value::as_f64).collect();
+                let sum: f64 = numbers.iter().sum();
+                if func == AggregateFunc::Avg {
+                    if numbers.is_empty() { Value::Null } else { Value::Float(sum / numbers.len() as f64) }
+                } else if values.iter().all(|value| matches!(value, Value::Int(_))) {
+                    Value::Int(sum as i64)
+                } else {
+                    Value::Float(sum)
+                }
+            }
+            AggregateFunc::Min => values.into_iter().min_by(|a, b| a.compare(b)).unwrap_or(Value::Null),
+            AggregateFunc::Max => values.into_iter().max_by(|a, b| a.compare(b)).unwrap_or(Value::Null),
+        })
+    }
+
+    fn evaluate(&self, expr: &Expr, binding: &Binding<'g>) -> Result<Value, Box<dyn Error>> {
+        Ok(match expr {
+            Expr::Literal(value) => value.clone(),
+            Expr::Var(var) => match lookup(binding, var) {
+                Some(Bound::Edge(position)) => Value::Str(self.graph.hyper_edges[position].id.clone()),
+                Some(Bound::Node(node)) => Value::Str(node.to_string()),
+                None => return Err(format!("HQL: unknown variable '{}'", var).into()),
+            },
+            Expr::Field { var, field, property_only } => match lookup(binding, var) {
+                Some(Bound::Edge(position)) => self.edge_field(&self.graph.hyper_edges[position], field, *property_only),
+                Some(Bound::Node(node)) => self.node_field(node, field, *property_only),
+                None => return Err(format!("HQL: unknown variable '{}'", var).into()),
+            },
+            Expr::List(items) => Value::List(items.iter().map(|item| self.evaluate(item, binding)).collect::<Result<_, _>>()?),
+            Expr::Not(inner) => Value::Bool(!self.evaluate(inner, binding)?.is_truthy()),
+            Expr::And(left, right) => {
+                Value::Bool(self.evaluate(left, binding)?.is_truthy() && self.evaluate(right, binding)?.is_truthy())
+            }
+            Expr::Or(left, right) => {
+                Value::Bool(self.evaluate(left, binding)?.is_truthy() || self.evaluate(right, binding)?.is_truthy())
+            }
+            Expr::Compare { op, left, right } => {
+                let left = self.evaluate(left, binding)?;
+                let right = self.evaluate(right, binding)?;
+                Value::Bool(match op {
+                    CompareOp::Eq => left.matches(&right),
+                    CompareOp::NotEq => !left.matches(&right),
+                    _ if left == Value::Null || right == Value::Null => false,
+                    CompareOp::Lt => left.compare(&right) == Ordering::Less,
+                    CompareOp::LtEq => left.compare(&right) != Ordering::Greater,
+                    CompareOp::Gt => left.compare(&right) == Ordering::Greater,
+                    CompareOp::GtEq => left.compare(&right) != Ordering::Less,
+                })
+            }
+            Expr::In { item, list } => {
+                let item = self.evaluate(item, binding)?;
+                match self.evaluate(list, binding)? {
+                    Value::List(values) => Value::Bool(values.iter().any(|value| value.matches(&item))),
+                    Value::Null => Value::Bool(false),
+                    value => Value::Bool(value.matches(&item)),
+                }
+            }
+            Expr::Aggregate { .. } => return Err(format!("HQL: aggregate '{}' used outside RETURN", expr).into()),
+        })
+    }
+
+    fn edge_field(&self, edge: &SimpleHyperEdge<String, String, String>, field: &str, property_only: bool) -> Value {
+        let strings = |nodes: &[String]| Value::List(nodes.iter().map(|node| Value::Str(node.clone())).collect());
+        let tail = edge.tail_hyper_nodes.as_deref().map_or(&[][..], |tail| &tail[..]);
+
+        if !property_only {
+            match field {
+                "id" => return Value::Str(edge.id.clone()),
+                "name" => return Value::Str(edge.name.clone()),
+                "directed" => return Value::Bool(edge.directed),
+                "traversable" => return Value::Bool(edge.traversable),
+                "head" => return strings(&edge.head_hyper_nodes),
+                "tail" => return strings(tail),
+                "nodes" => return strings(&edge_members(edge)),
+                "size" => return Value::Int(edge_members(edge).len() as i64),
+                "head_size" => return Value::Int(edge.head_hyper_nodes.len() as i64),
+                "tail_size" => return Value::Int(tail.len() as i64),
+                _ => {}
+            }
+        }
+        property_value(&edge.main_properties, field)
+    }
+
+    fn node_field(&self, node: &str, field: &str, property_only: bool) -> Value {
+        let incidences = self.incidence_index.get(node).map_or(&[][..], |incidences| &incidences[..]);
+
+        if !property_only {
+            match field {
+                "id" => return Value::Str(node.to_string()),
+                "degree" => {
+                    let mut edges: Vec<usize> = incidences.iter().map(|(position, _)| *position).collect();
+                    edges.dedup();
+                    return Value::Int(edges.len() as i64);
+                }
+                "head_degree" => return Value::Int(incidences.iter().filter(|(_, role)| *role == Role::Head).count() as i64),
+                "tail_degree" => return Value::Int(incidences.iter().filter(|(_, role)| *role == Role::Tail).count() as i64),
+                _ => {}
+            }
+        }
+        match self.node_index.get(node) {
+            Some(position) => property_value(&self.graph.hyper_nodes[*position].properties, field),
+            None => Value::Null,
+        }
+    }
+}
+
+fn lookup<'g>(binding: &Binding<'g>, var: &str) -> Option<Bound<'g>> {
+    binding.iter().find(|(name, _)| *name == var).map(|(_, bound)| *bound)
+}
+
+// Single-valued properties read as a scalar, multi-valued ones as a list, missing ones as null
+fn property_value(properties: &[Property<String, String>], key: &str) -> Value {
+    match properties.iter().find(|property| property.key == key) {
+        Some(property) if property.value.len() == 1 => Value::Str(property.value[0].clone()),
+        Some(property) => Value::List(property.value.iter().map(|value| Value::Str(value.clone())).collect()),
+        None => Value::Null,
+    }
+}
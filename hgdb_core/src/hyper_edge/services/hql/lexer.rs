@@ -0,0 +1,123 @@
+use std::error::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    Float(f64),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Colon,
+    Comma,
+    Dot,
+    Star,
+    Dash,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl Token {
+    /// Returns true if the token is the given keyword (keywords are case-insensitive)
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Ident(ident) if ident.eq_ignore_ascii_case(keyword))
+    }
+}
+
+/// Splits an HQL query into tokens, each paired with its byte offset for error messages
+pub fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, Box<dyn Error>> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (offset, c) = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].1.is_ascii_alphanumeric() || chars[i].1 == '_') {
+                i += 1;
+            }
+            let ident: String = chars[start..i].iter().map(|(_, c)| c).collect();
+            tokens.push((Token::Ident(ident), offset));
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].1.is_ascii_digit() || chars[i].1 == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().map(|(_, c)| c).collect();
+            let token = if number.contains('.') {
+                Token::Float(number.parse().map_err(|_| format!("HQL: invalid number '{}' at position {}", number, offset))?)
+            } else {
+                Token::Int(number.parse().map_err(|_| format!("HQL: invalid number '{}' at position {}", number, offset))?)
+            };
+            tokens.push((token, offset));
+            continue;
+        }
+
+        if c == '\'' || c == '"' {
+            let quote = c;
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    Some((_, '\\')) => {
+                        if let Some((_, escaped)) = chars.get(i + 1) {
+                            value.push(*escaped);
+                        }
+                        i += 2;
+                    }
+                    Some((_, c)) if *c == quote => {
+                        i += 1;
+                        break;
+                    }
+                    Some((_, c)) => {
+                        value.push(*c);
+                        i += 1;
+                    }
+                    None => return Err(format!("HQL: unterminated string starting at position {}", offset).into()),
+                }
+            }
+            tokens.push((Token::Str(value), offset));
+            continue;
+        }
+
+        let next = chars.get(i + 1).map(|(_, c)| *c);
+        let (token, width) = match (c, next) {
+            ('!', Some('=')) | ('<', Some('>')) => (Token::NotEq, 2),
+            ('<', Some('=')) => (Token::LtEq, 2),
+            ('>', Some('=')) => (Token::GtEq, 2),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            ('[', _) => (Token::LBracket, 1),
+            (']', _) => (Token::RBracket, 1),
+            (':', _) => (Token::Colon, 1),
+            (',', _) => (Token::Comma, 1),
+            ('.', _) => (Token::Dot, 1),
+            ('*', _) => (Token::Star, 1),
+            ('-', _) => (Token::Dash, 1),
+            ('=', _) => (Token::Eq, 1),
+            ('<', _) => (Token::Lt, 1),
+            ('>', _) => (Token::Gt, 1),
+            _ => return Err(format!("HQL: unexpected character '{}' at position {}", c, offset).into()),
+        };
+        tokens.push((token, offset));
+        i += width;
+    }
+
+    Ok(tokens)
+}
@@ -0,0 +1,5 @@
+pub mod lexer;
+pub mod ast;
+pub mod parser;
+pub mod plan;
+pub mod executor;
\ No newline at end of file
@@ -0,0 +1,321 @@
+use crate::hyper_edge::services::hql::ast::{AggregateFunc, CompareOp, Expr, Label, OrderItem, Pattern, Projection, Query, Role, Value};
+use crate::hyper_edge::services::hql::lexer::{tokenize, Token};
+use std::error::Error;
+
+/// Parses an HQL query:
+///
+/// ```text
+/// [EXPLAIN] MATCH (e:Edge) | (n:Node) | (n:Node)-[:HEAD|:TAIL]-(e:Edge)
+///   [WHERE <expr>] [RETURN <expr> [AS alias], ...] [ORDER BY <expr> [ASC|DESC], ...] [LIMIT <n>]
+/// ```
+pub fn parse(input: &str) -> Result<Query, Box<dyn Error>> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, position: 0, input_len: input.len() };
+    parser.parse_query()
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    position: usize,
+    input_len: usize,
+}
+
+impl Parser {
+    fn parse_query(&mut self) -> Result<Query, Box<dyn Error>> {
+        let explain = self.eat_keyword("EXPLAIN");
+        self.expect_keyword("MATCH")?;
+        let pattern = self.parse_pattern()?;
+
+        let filter = if self.eat_keyword("WHERE") { Some(self.parse_expr()?) } else { None };
+
+        let mut projections = Vec::new();
+        if self.eat_keyword("RETURN") {
+            loop {
+                let expr = self.parse_expr()?;
+                let alias = if self.eat_keyword("AS") { self.expect_ident()? } else { expr.to_string() };
+                projections.push(Projection { expr, alias });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        } else {
+            // Without RETURN every pattern variable is returned by id
+            let vars = match &pattern {
+                Pattern::Single { var, .. } => vec![var.clone()],
+                Pattern::Incidence { node, edge, .. } => vec![node.clone(), edge.clone()],
+            };
+            projections = vars.into_iter().map(|var| Projection { expr: Expr::Var(var.clone()), alias: var }).collect();
+        }
+
+        let mut order_by = Vec::new();
+        if self.eat_keyword("ORDER") {
+            self.expect_keyword("BY")?;
+            loop {
+                let expr = self.parse_expr()?;
+                let descending = self.eat_keyword("DESC");
+                if !descending {
+                    self.eat_keyword("ASC");
+                }
+                order_by.push(OrderItem { expr, descending });
+                if !self.eat(&Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let limit = if self.eat_keyword("LIMIT") {
+            match self.next() {
+                Some(Token::Int(count)) if count >= 0 => Some(count as usize),
+                _ => return Err(self.error("expected a non-negative integer after LIMIT")),
+            }
+        } else {
+            None
+        };
+
+        if self.position < self.tokens.len() {
+            return Err(self.error("unexpected trailing input"));
+        }
+
+        Ok(Query { explain, pattern, filter, projections, order_by, limit })
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, Box<dyn Error>> {
+        let (first_var, first_label) = self.parse_element()?;
+
+        if !self.eat(&Token::Dash) {
+            return Ok(Pattern::Single { var: first_var, label: first_label.unwrap_or(Label::Edge) });
+        }
+
+        self.expect(&Token::LBracket)?;
+        let role = if self.eat(&Token::Colon) {
+            let role = self.expect_ident()?;
+            match role.to_ascii_uppercase().as_str() {
+                "HEAD" => Role::Head,
+                "TAIL" => Role::Tail,
+                _ => return Err(self.error(&format!("unknown role '{}', expected HEAD or TAIL", role))),
+            }
+        } else {
+            Role::Any
+        };
+        self.expect(&Token::RBracket)?;
+        self.expect(&Token::Dash)?;
+        let (second_var, second_label) = self.parse_element()?;
+
+        // Unlabeled elements take whatever label the other side leaves, defaulting to (node)-[]-(edge)
+        let first_label = first_label.unwrap_or(match second_label {
+            Some(Label::Node) => Label::Edge,
+            _ => Label::Node,
+        });
+        let second_label = second_label.unwrap_or(match first_label {
+            Label::Node => Label::Edge,
+            Label::Edge => Label::Node,
+        });
+
+        match (first_label, second_label) {
+            (Label::Node, Label::Edge) => Ok(Pattern::Incidence { node: first_var, edge: second_var, role }),
+            (Label::Edge, Label::Node) => Ok(Pattern::Incidence { node: second_var, edge: first_var, role }),
+            _ => Err(self.error("an incidence pattern must connect a Node and an Edge")),
+        }
+    }
+
+    fn parse_element(&mut self) -> Result<(String, Option<Label>), Box<dyn Error>> {
+        self.expect(&Token::LParen)?;
+        let var = self.expect_ident()?;
+        let label = if self.eat(&Token::Colon) {
+            let label = self.expect_ident()?;
+            match label.to_ascii_uppercase().as_str() {
+                "NODE" => Some(Label::Node),
+                "EDGE" => Some(Label::Edge),
+                _ => return Err(self.error(&format!("unknown label '{}', expected Node or Edge", label))),
+            }
+        } else {
+            None
+        };
+        self.expect(&Token::RParen)?;
+        Ok((var, label))
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut left = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let right = self.parse_and()?;
+            left = Expr::Or(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let mut left = self.parse_not()?;
+        while self.eat_keyword("AND") {
+            let right = self.parse_not()?;
+            left = Expr::And(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, Box<dyn Error>> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, Box<dyn Error>> {
+        let left = self.parse_primary()?;
+
+        if self.eat_keyword("IN") {
+            let list = self.parse_primary()?;
+            return Ok(Expr::In { item: Box::new(left), list: Box::new(list) });
+        }
+
+        let op = match self.peek() {
+            Some(Token::Eq) => CompareOp::Eq,
+            Some(Token::NotEq) => CompareOp::NotEq,
+            Some(Token::Lt) => CompareOp::Lt,
+            Some(Token::LtEq) => CompareOp::LtEq,
+            Some(Token::Gt) => CompareOp::Gt,
+            Some(Token::GtEq) => CompareOp::GtEq,
+            _ => return Ok(left),
+        };
+        self.position += 1;
+        let right = self.parse_primary()?;
+        Ok(Expr::Compare { op, left: Box::new(left), right: Box::new(right) })
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, Box<dyn Error>> {
+        match self.next() {
+            Some(Token::Str(value)) => Ok(Expr::Literal(Value::Str(value))),
+            Some(Token::Int(value)) => Ok(Expr::Literal(Value::Int(value))),
+            Some(Token::Float(value)) => Ok(Expr::Literal(Value::Float(value))),
+            Some(Token::Dash) => match self.next() {
+                Some(Token::Int(value)) => Ok(Expr::Literal(Value::Int(-value))),
+                Some(Token::Float(value)) => Ok(Expr::Literal(Value::Float(-value))),
+                _ => Err(self.error("expected a number after '-'")),
+            },
+            Some(Token::LParen) => {
+                let expr = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::LBracket) => {
+                let mut items = Vec::new();
+                if !self.eat(&Token::RBracket) {
+                    loop {
+                        items.push(self.parse_expr()?);
+                        if !self.eat(&Token::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&Token::RBracket)?;
+                }
+                Ok(Expr::List(items))
+            }
+            Some(Token::Ident(ident)) => {
+                let upper = ident.to_ascii_uppercase();
+                match upper.as_str() {
+                    "TRUE" => return Ok(Expr::Literal(Value::Bool(true))),
+                    "FALSE" => return Ok(Expr::Literal(Value::Bool(false))),
+                    "NULL" => return Ok(Expr::Literal(Value::Null)),
+                    _ => {}
+                }
+
+                let func = match upper.as_str() {
+                    "COUNT" => Some(AggregateFunc::Count),
+                    "SUM" => Some(AggregateFunc::Sum),
+                    "AVG" => Some(AggregateFunc::Avg),
+                    "MIN" => Some(AggregateFunc::Min),
+                    "MAX" => Some(AggregateFunc::Max),
+                    _ => None,
+                };
+                if let (Some(func), Some(Token::LParen)) = (func, self.peek()) {
+                    self.position += 1;
+                    let arg = if func == AggregateFunc::Count && self.eat(&Token::Star) {
+                        None
+                    } else {
+                        Some(Box::new(self.parse_expr()?))
+                    };
+                    self.expect(&Token::RParen)?;
+                    return Ok(Expr::Aggregate { func, arg });
+                }
+
+                if self.eat(&Token::Dot) {
+                    let field = self.expect_ident()?;
+                    return Ok(Expr::Field { var: ident, field, property_only: false });
+                }
+                if self.eat(&Token::LBracket) {
+                    let field = match self.next() {
+                        Some(Token::Str(field)) => field,
+                        _ => return Err(self.error("expected a quoted property name")),
+                    };
+                    self.expect(&Token::RBracket)?;
+                    return Ok(Expr::Field { var: ident, field, property_only: true });
+                }
+                Ok(Expr::Var(ident))
+            }
+            _ => {
+                self.position = self.position.saturating_sub(1);
+                Err(self.error("expected an expression"))
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|(token, _)| token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek().is_some_and(|token| token.is_keyword(keyword)) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), Box<dyn Error>> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", token)))
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), Box<dyn Error>> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {}", keyword)))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, Box<dyn Error>> {
+        match self.peek() {
+            Some(Token::Ident(ident)) => {
+                let ident = ident.clone();
+                self.position += 1;
+                Ok(ident)
+            }
+            _ => Err(self.error("expected an identifier")),
+        }
+    }
+
+    fn error(&self, message: &str) -> Box<dyn Error> {
+        let offset = self.tokens.get(self.position).map_or(self.input_len, |(_, offset)| *offset);
+        format!("HQL parse error at position {}: {}", offset, message).into()
+    }
+}
@@ -0,0 +1,301 @@
+use crate::hyper_edge::services::hql::ast::{CompareOp, Expr, Label, OrderItem, Pattern, Projection, Query, Role, Value};
+use std::error::Error;
+
+/// Logical operators of an HQL query. Leaves pick the access path, the index they use is named in EXPLAIN
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalPlan {
+    /// Reads every hyperedge
+    EdgeScan { var: String },
+    /// Reads the hyperedges with the given ids through the edge id index
+    EdgeLookup { var: String, ids: Vec<String> },
+    /// Reads the hyperedges containing one of the given nodes in the given role through the node incidence index
+    EdgesOfNodes { var: String, nodes: Vec<String>, role: Role },
+    /// Reads every node
+    NodeScan { var: String },
+    /// Reads the nodes with the given ids through the node id index
+    NodeLookup { var: String, ids: Vec<String> },
+    /// Binds `to` to every node of edge `from` (or every edge of node `from`) in the given role
+    Expand { input: Box<LogicalPlan>, from: String, from_label: Label, to: String, role: Role },
+    Filter { input: Box<LogicalPlan>, predicate: Expr },
+    Project { input: Box<LogicalPlan>, items: Vec<Projection> },
+    /// Groups by the items without aggregates and computes the others per group
+    Aggregate { input: Box<LogicalPlan>, items: Vec<Projection> },
+    /// Sorts output rows by column index, `true` meaning descending
+    Sort { input: Box<LogicalPlan>, keys: Vec<(usize, bool)>, labels: Vec<String> },
+    Limit { input: Box<LogicalPlan>, count: usize },
+}
+
+/// A planned query; rows carry `columns.len()` visible columns followed by hidden sort columns
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    pub root: LogicalPlan,
+    pub columns: Vec<String>,
+}
+
+impl QueryPlan {
+    /// Textual EXPLAIN output, one operator per line with its input indented below it
+    pub fn explain(&self) -> String {
+        let mut output = String::new();
+        explain_node(&self.root, 0, &mut output);
+        output
+    }
+}
+
+fn explain_node(plan: &LogicalPlan, depth: usize, output: &mut String) {
+    let indent = "  ".repeat(depth);
+    let quote = |ids: &[String]| ids.iter().map(|id| format!("'{}'", id)).collect::<Vec<String>>().join(", ");
+    let list = |items: &[Projection]| items.iter()
+        .map(|item| if item.alias == item.expr.to_string() { item.alias.clone() } else { format!("{} AS {}", item.expr, item.alias) })
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let (line, input) = match plan {
+        LogicalPlan::EdgeScan { var } => (format!("EdgeScan {} (full scan)", var), None),
+        LogicalPlan::EdgeLookup { var, ids } => (format!("EdgeLookup {} ids=[{}] (edge id index)", var, quote(ids)), None),
+        LogicalPlan::EdgesOfNodes { var, nodes, role } => {
+            (format!("EdgesOfNodes {} nodes=[{}] role={} (node incidence index)", var, quote(nodes), role_name(*role)), None)
+        }
+        LogicalPlan::NodeScan { var } => (format!("NodeScan {} (full scan)", var), None),
+        LogicalPlan::NodeLookup { var, ids } => (format!("NodeLookup {} ids=[{}] (node id index)", var, quote(ids)), None),
+        LogicalPlan::Expand { input, from, from_label, to, role } => {
+            let via = match from_label {
+                Label::Node => "node incidence index",
+                Label::Edge => "edge members",
+            };
+            (format!("Expand {} -> {} role={} ({})", from, to, role_name(*role), via), Some(input))
+        }
+        LogicalPlan::Filter { input, predicate } => (format!("Filter {}", predicate), Some(input)),
+        LogicalPlan::Project { input, items } => (format!("Project [{}]", list(items)), Some(input)),
+        LogicalPlan::Aggregate { input, items } => (format!("Aggregate [{}]", list(items)), Some(input)),
+        LogicalPlan::Sort { input, keys, labels } => {
+            let keys: Vec<String> = keys.iter().zip(labels)
+                .map(|((_, descending), label)| format!("{} {}", label, if *descending { "DESC" } else { "ASC" }))
+                .collect();
+            (format!("Sort [{}]", keys.join(", ")), Some(input))
+        }
+        LogicalPlan::Limit { input, count } => (format!("Limit {}", count), Some(input)),
+    };
+
+    output.push_str(&format!("{}{}\n", indent, line));
+    if let Some(input) = input {
+        explain_node(input, depth + 1, output);
+    }
+}
+
+fn role_name(role: Role) -> &'static str {
+    match role {
+        Role::Head => "HEAD",
+        Role::Tail => "TAIL",
+        Role::Any => "ANY",
+    }
+}
+
+/// Turns a parsed query into a logical plan, pushing id and membership predicates down into index lookups
+pub fn plan_query(query: &Query) -> Result<QueryPlan, Box<dyn Error>> {
+    let mut conjuncts = Vec::new();
+    if let Some(filter) = &query.filter {
+        if filter.contains_aggregate() {
+            return Err("HQL: aggregates are not allowed in WHERE".into());
+        }
+        split_conjuncts(filter.clone(), &mut conjuncts);
+    }
+
+    let vars: Vec<&str> = match &query.pattern {
+        Pattern::Single { var, .. } => vec![var.as_str()],
+        Pattern::Incidence { node, edge, .. } => {
+            if node == edge {
+                return Err(format!("HQL: variable '{}' is bound twice", node).into());
+            }
+            vec![node.as_str(), edge.as_str()]
+        }
+    };
+    for expr in conjuncts.iter().chain(query.projections.iter().map(|item| &item.expr)) {
+        check_vars(expr, &vars)?;
+    }
+
+    let mut plan = match &query.pattern {
+        Pattern::Single { var, label: Label::Edge } => {
+            if let Some(ids) = take_id_lookup(&mut conjuncts, var) {
+                LogicalPlan::EdgeLookup { var: var.clone(), ids }
+            } else if let Some((nodes, role)) = take_membership_lookup(&mut conjuncts, var) {
+                LogicalPlan::EdgesOfNodes { var: var.clone(), nodes, role }
+            } else {
+                LogicalPlan::EdgeScan { var: var.clone() }
+            }
+        }
+        Pattern::Single { var, label: Label::Node } => match take_id_lookup(&mut conjuncts, var) {
+            Some(ids) => LogicalPlan::NodeLookup { var: var.clone(), ids },
+            None => LogicalPlan::NodeScan { var: var.clone() },
+        },
+        Pattern::Incidence { node, edge, role } => {
+            let expand = |input: LogicalPlan, from: &String, from_label: Label, to: &String| LogicalPlan::Expand {
+                input: Box::new(input), from: from.clone(), from_label, to: to.clone(), role: *role,
+            };
+
+            if let Some(ids) = take_id_lookup(&mut conjuncts, node) {
+                expand(LogicalPlan::NodeLookup { var: node.clone(), ids }, node, Label::Node, edge)
+            } else if let Some(ids) = take_id_lookup(&mut conjuncts, edge) {
+                expand(LogicalPlan::EdgeLookup { var: edge.clone(), ids }, edge, Label::Edge, node)
+            } else if let Some((nodes, member_role)) = take_membership_lookup(&mut conjuncts, edge) {
+                expand(LogicalPlan::EdgesOfNodes { var: edge.clone(), nodes, role: member_role }, edge, Label::Edge, node)
+            } else {
+                expand(LogicalPlan::EdgeScan { var: edge.clone() }, edge, Label::Edge, node)
+            }
+        }
+    };
+
+    if let Some(predicate) = conjuncts.into_iter().reduce(|left, right| Expr::And(Box::new(left), Box::new(right))) {
+        plan = LogicalPlan::Filter { input: Box::new(plan), predicate };
+    }
+
+    let aggregated = query.projections.iter().any(|item| item.expr.contains_aggregate());
+    if aggregated {
+        for item in &query.projections {
+            if item.expr.contains_aggregate() && !matches!(item.expr, Expr::Aggregate { .. }) {
+                return Err(format!("HQL: aggregate must be the whole RETURN item in '{}'", item.expr).into());
+            }
+        }
+    }
+
+    // ORDER BY keys are RETURN columns (by alias or expression) or extra hidden columns
+    let mut items = query.projections.clone();
+    let mut keys = Vec::new();
+    let mut labels = Vec::new();
+    for order in &query.order_by {
+        let column = resolve_order_column(order, &items[..query.projections.len()]);
+        let column = match column {
+            Some(column) => column,
+            None if aggregated => {
+                return Err(format!("HQL: ORDER BY '{}' must reference a RETURN column when aggregating", order.expr).into());
+            }
+            None => {
+                check_vars(&order.expr, &vars)?;
+                items.push(Projection { expr: order.expr.clone(), alias: order.expr.to_string() });
+                items.len() - 1
+            }
+        };
+        keys.push((column, order.descending));
+        labels.push(order.expr.to_string());
+    }
+
+    plan = if aggregated {
+        LogicalPlan::Aggregate { input: Box::new(plan), items }
+    } else {
+        LogicalPlan::Project { input: Box::new(plan), items }
+    };
+    if !keys.is_empty() {
+        plan = LogicalPlan::Sort { input: Box::new(plan), keys, labels };
+    }
+    if let Some(count) = query.limit {
+        plan = LogicalPlan::Limit { input: Box::new(plan), count };
+    }
+
+    Ok(QueryPlan {
+        root: plan,
+        columns: query.projections.iter().map(|item| item.alias.clone()).collect(),
+    })
+}
+
+fn split_conjuncts(expr: Expr, conjuncts: &mut Vec<Expr>) {
+    match expr {
+        Expr::And(left, right) => {
+            split_conjuncts(*left, conjuncts);
+            split_conjuncts(*right, conjuncts);
+        }
+        other => conjuncts.push(other),
+    }
+}
+
+fn check_vars(expr: &Expr, vars: &[&str]) -> Result<(), Box<dyn Error>> {
+    let check = |var: &String| {
+        if vars.contains(&var.as_str()) {
+            Ok(())
+        } else {
+            Err(format!("HQL: unknown variable '{}'", var).into())
+        }
+    };
+
+    match expr {
+        Expr::Literal(_) => Ok(()),
+        Expr::Var(var) | Expr::Field { var, .. } => check(var),
+        Expr::List(items) => items.iter().try_for_each(|item| check_vars(item, vars)),
+        Expr::Not(inner) => check_vars(inner, vars),
+        Expr::And(left, right) | Expr::Or(left, right) | Expr::Compare { left, right, .. } => {
+            check_vars(left, vars)?;
+            check_vars(right, vars)
+        }
+        Expr::In { item, list } => {
+            check_vars(item, vars)?;
+            check_vars(list, vars)
+        }
+        Expr::Aggregate { arg, .. } => arg.as_ref().map_or(Ok(()), |arg| check_vars(arg, vars)),
+    }
+}
+
+fn resolve_order_column(order: &OrderItem, items: &[Projection]) -> Option<usize> {
+    if let Expr::Var(name) = &order.expr {
+        if let Some(index) = items.iter().position(|item| &item.alias == name) {
+            return Some(index);
+        }
+    }
+    items.iter().position(|item| item.expr == order.expr)
+}
+
+fn is_field(expr: &Expr, var: &str, field: &str) -> bool {
+    matches!(expr, Expr::Field { var: v, field: f, property_only: false } if v == var && f == field)
+}
+
+fn string_literals(expr: &Expr) -> Option<Vec<String>> {
+    match expr {
+        Expr::Literal(Value::Str(value)) => Some(vec![value.clone()]),
+        Expr::List(items) => items.iter()
+            .map(|item| match item {
+                Expr::Literal(Value::Str(value)) => Some(value.clone()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+/// Removes and returns the ids of a `var.id = '...'` or `var.id IN [...]` conjunct
+fn take_id_lookup(conjuncts: &mut Vec<Expr>, var: &str) -> Option<Vec<String>> {
+    let position = conjuncts.iter().position(|expr| id_lookup(expr, var).is_some())?;
+    id_lookup(&conjuncts.remove(position), var)
+}
+
+fn id_lookup(expr: &Expr, var: &str) -> Option<Vec<String>> {
+    match expr {
+        Expr::Compare { op: CompareOp::Eq, left, right } => {
+            if is_field(left, var, "id") {
+                string_literals(right).filter(|ids| ids.len() == 1)
+            } else if is_field(right, var, "id") {
+                string_literals(left).filter(|ids| ids.len() == 1)
+            } else {
+                None
+            }
+        }
+        Expr::In { item, list } if is_field(item, var, "id") => string_literals(list),
+        _ => None,
+    }
+}
+
+/// Removes and returns the node ids and role of a `'v1' IN var.head|tail|nodes` conjunct
+fn take_membership_lookup(conjuncts: &mut Vec<Expr>, var: &str) -> Option<(Vec<String>, Role)> {
+    let position = conjuncts.iter().position(|expr| membership_lookup(expr, var).is_some())?;
+    membership_lookup(&conjuncts.remove(position), var)
+}
+
+fn membership_lookup(expr: &Expr, var: &str) -> Option<(Vec<String>, Role)> {
+    let Expr::In { item, list } = expr else { return None };
+    let role = if is_field(list, var, "head") {
+        Role::Head
+    } else if is_field(list, var, "tail") {
+        Role::Tail
+    } else if is_field(list, var, "nodes") {
+        Role::Any
+    } else {
+        return None;
+    };
+    string_literals(item).filter(|nodes| nodes.len() == 1).map(|nodes| (nodes, role))
+}
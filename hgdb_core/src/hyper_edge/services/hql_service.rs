@@ -0,0 +1,46 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use crate::hyper_edge::services::h_graph_service::HyperGraphService;
+use crate::hyper_edge::services::hql::ast::Value;
+use crate::hyper_edge::services::hql::executor::{HqlExecutor, QueryResult};
+use crate::hyper_edge::services::hql::parser::parse;
+use crate::hyper_edge::services::hql::plan::plan_query;
+use std::error::Error;
+
+/// Runs HQL (hypergraph query language) queries, e.g.
+/// `MATCH (n:Node)-[:HEAD]-(e:Edge) WHERE e.type = 'linked' RETURN n.id, COUNT(*) AS edges ORDER BY edges DESC LIMIT 5`
+pub struct HqlService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> HqlService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        HqlService { repository }
+    }
+
+    /// Runs a query over every hyperedge stored in the repository
+    pub fn query(&self, text: &str) -> Result<QueryResult, Box<dyn Error>> {
+        let graph = HyperGraphService::new(self.repository).load_graph("hql")?;
+        self.query_graph(&graph, text)
+    }
+
+    /// Runs a query over an already loaded hypergraph; `EXPLAIN` queries return the plan, one line per row
+    pub fn query_graph(&self, graph: &HyperGraph<String, String, String>, text: &str) -> Result<QueryResult, Box<dyn Error>> {
+        let query = parse(text)?;
+        let plan = plan_query(&query)?;
+
+        if query.explain {
+            return Ok(QueryResult {
+                columns: vec!["plan".to_string()],
+                rows: plan.explain().lines().map(|line| vec![Value::Str(line.to_string())]).collect(),
+            });
+        }
+
+        HqlExecutor::new(graph).execute(&plan)
+    }
+
+    // method to get the textual plan of a query without running it
+    pub fn explain(&self, text: &str) -> Result<String, Box<dyn Error>> {
+        Ok(plan_query(&parse(text)?)?.explain())
+    }
+}
@@ -0,0 +1,33 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::sparse::incidence::{IncidenceBuilder, IncidenceMatrix};
+use std::error::Error;
+
+/// Builds sparse incidence matrices straight from storage, the common base of the matrix-based algorithms
+pub struct IncidenceService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> IncidenceService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        IncidenceService { repository }
+    }
+
+    /// Builds the incidence matrix of every hyperedge in the repository, streaming the edges in key order.
+    /// `weight_key` names the numeric `main_properties` entry used as edge weight
+    pub fn build(&self, weight_key: Option<&str>) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let mut builder = IncidenceBuilder::new(weight_key);
+        self.repository.scan(|edge| builder.add_edge(&edge))?;
+
+        let incidence = builder.build();
+        println!("✅ Built incidence matrix: {} nodes x {} hyperedges, {} incidences", incidence.node_count(), incidence.edge_count(), incidence.csr().nnz());
+        Ok(incidence)
+    }
+
+    // method to build the incidence matrix of a named hypergraph
+    pub fn build_named(&self, graphs: &HyperGraphRepository, key: &str, weight_key: Option<&str>) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = graphs.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, weight_key))
+    }
+}
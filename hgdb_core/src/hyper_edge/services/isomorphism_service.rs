@@ -0,0 +1,414 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::Property;
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+type Graph = HyperGraph<String, String, String>;
+
+/// The values of the label keys an element holds, by key
+pub type Labels = Vec<(String, Vec<String>)>;
+
+// Roles of a member in a hyperedge
+const HEAD: u8 = 0;
+const TAIL: u8 = 1;
+
+/// A hyperedge of a canonical form, its members given by canonical node position
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalEdge {
+    pub directed: bool,
+    pub labels: Labels,
+    pub head: Vec<usize>,
+    pub tail: Vec<usize>,
+}
+
+/// A relabeling of a hypergraph that only depends on its structure: two hypergraphs are isomorphic exactly when
+/// their canonical forms are equal
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalForm {
+    /// The labels of every node, by canonical position
+    pub nodes: Vec<Labels>,
+    /// The hyperedges, by canonical position
+    pub edges: Vec<CanonicalEdge>,
+}
+
+impl CanonicalForm {
+    /// A stable 64-bit FNV-1a hash of the form, as 16 hex digits, to fingerprint hypergraphs across databases
+    pub fn fingerprint(&self) -> String {
+        let json = serde_json::to_string(self).expect("canonical forms always serialize");
+        let hash = json.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+        format!("{:016x}", hash)
+    }
+}
+
+/// A canonical form together with the ids of the nodes and hyperedges at every canonical position
+#[derive(Debug, Clone, PartialEq)]
+pub struct CanonicalLabeling {
+    pub form: CanonicalForm,
+    pub nodes: Vec<String>,
+    pub edges: Vec<String>,
+}
+
+/// A structure-preserving bijection, as (left id, right id) pairs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Isomorphism {
+    pub nodes: Vec<(String, String)>,
+    pub edges: Vec<(String, String)>,
+}
+
+/// Computes the canonical labeling of a hypergraph. Nodes are the listed nodes plus every node a hyperedge uses;
+/// members keep their head or tail role, and `directed` is part of every hyperedge. The values of the `label_keys`
+/// properties (node `properties`, hyperedge `main_properties`) must match too; with no keys the structure alone
+/// counts. A missing tail and an empty one are the same, and repeated members count once.
+///
+/// Works by color refinement of the node-hyperedge incidence followed by individualization of the first
+/// non-singleton cell, keeping the smallest leaf; automorphisms found along the way prune equivalent branches.
+/// Highly symmetric hypergraphs can still take exponential time
+pub fn canonical_labeling(graph: &Graph, label_keys: &[&str]) -> CanonicalLabeling {
+    let incidence = LabeledIncidence::new(graph, label_keys);
+    let mut search = Search { incidence: &incidence, first: None, best: None, automorphisms: Vec::new() };
+    let colors = incidence.refine(incidence.initial_colors());
+    search.explore(colors, &mut Vec::new());
+
+    let (form, colors) = search.best.expect("the search always reaches a leaf");
+    let mut nodes = vec![String::new(); incidence.node_ids.len()];
+    let mut edges = vec![String::new(); incidence.edge_ids.len()];
+    for (vertex, &color) in colors.iter().enumerate() {
+        match vertex.checked_sub(nodes.len()) {
+            None => nodes[color] = incidence.node_ids[vertex].clone(),
+            Some(edge) => edges[color - nodes.len()] = incidence.edge_ids[edge].clone(),
+        }
+    }
+    CanonicalLabeling { form, nodes, edges }
+}
+
+pub fn canonical_form(graph: &Graph, label_keys: &[&str]) -> CanonicalForm {
+    canonical_labeling(graph, label_keys).form
+}
+
+/// An isomorphism between two hypergraphs, if there is one
+pub fn isomorphism(left: &Graph, right: &Graph, label_keys: &[&str]) -> Option<Isomorphism> {
+    let left = canonical_labeling(left, label_keys);
+    let right = canonical_labeling(right, label_keys);
+    if left.form != right.form {
+        return None;
+    }
+    Some(Isomorphism {
+        nodes: left.nodes.into_iter().zip(right.nodes).collect(),
+        edges: left.edges.into_iter().zip(right.edges).collect(),
+    })
+}
+
+// The incidence as a bipartite graph: vertices 0..n are the nodes and n.. the hyperedges, every adjacency entry
+// holding the role of the member and the other vertex
+struct LabeledIncidence {
+    node_ids: Vec<String>,
+    edge_ids: Vec<String>,
+    node_labels: Vec<Labels>,
+    edge_labels: Vec<Labels>,
+    directed: Vec<bool>,
+    adjacency: Vec<Vec<(u8, usize)>>,
+}
+
+impl LabeledIncidence {
+    fn new(graph: &Graph, label_keys: &[&str]) -> Self {
+        let labels = |properties: &[Property<String, String>]| -> Labels {
+            let mut labels: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+            for property in properties.iter().filter(|property| label_keys.contains(&property.key.as_str())) {
+                let values = labels.entry(&property.key).or_default();
+                values.extend(property.value.iter().cloned());
+                values.sort();
+                values.dedup();
+            }
+            labels.into_iter().map(|(key, values)| (key.to_string(), values)).collect()
+        };
+
+        let mut node_ids: Vec<String> = Vec::new();
+        let mut node_labels: Vec<Labels> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for node in &graph.hyper_nodes {
+            if !positions.contains_key(&node.id) {
+                positions.insert(node.id.clone(), node_ids.len());
+                node_ids.push(node.id.clone());
+                node_labels.push(labels(&node.properties));
+            }
+        }
+
+        let mut members: Vec<Vec<(u8, usize)>> = Vec::new();
+        for edge in &graph.hyper_edges {
+            let roles = edge.head_hyper_nodes.iter().map(|node| (HEAD, node))
+                .chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()).map(|node| (TAIL, node)));
+            let mut edge_members = Vec::new();
+            for (role, node) in roles {
+                let position = *positions.entry(node.clone()).or_insert_with(|| {
+                    node_ids.push(node.clone());
+                    node_labels.push(Vec::new());
+                    node_ids.len() - 1
+                });
+                edge_members.push((role, position));
+            }
+            edge_members.sort_unstable();
+            edge_members.dedup();
+            members.push(edge_members);
+        }
+
+        let node_count = node_ids.len();
+        let mut adjacency = vec![Vec::new(); node_count + members.len()];
+        for (edge, edge_members) in members.iter().enumerate() {
+            for &(role, node) in edge_members {
+                adjacency[node].push((role, node_count + edge));
+                adjacency[node_count + edge].push((role, node));
+            }
+        }
+
+        LabeledIncidence {
+            node_ids,
+            edge_ids: graph.hyper_edges.iter().map(|edge| edge.id.clone()).collect(),
+            node_labels,
+            edge_labels: graph.hyper_edges.iter().map(|edge| labels(&edge.main_properties)).collect(),
+            directed: graph.hyper_edges.iter().map(|edge| edge.directed).collect(),
+            adjacency,
+        }
+    }
+
+    fn vertex_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    // Nodes before hyperedges, then by direction and labels
+    fn initial_colors(&self) -> Vec<usize> {
+        let node_count = self.node_ids.len();
+        let signatures: Vec<(bool, bool, &Labels)> = (0..self.vertex_count())
+            .map(|vertex| match vertex.checked_sub(node_count) {
+                None => (false, false, &self.node_labels[vertex]),
+                Some(edge) => (true, self.directed[edge], &self.edge_labels[edge]),
+            })
+            .collect();
+        rank(&signatures)
+    }
+
+    // Splits cells by the colors of the neighbors, with their roles, until the partition is stable. Colors are
+    // ranks of sorted signatures that start with the previous color, so cells keep their relative order
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        let mut count = cell_count(&colors);
+        loop {
+            let signatures: Vec<(usize, Vec<(u8, usize)>)> = self.adjacency.iter()
+                .enumerate()
+                .map(|(vertex, neighbors)| {
+                    let mut around: Vec<(u8, usize)> = neighbors.iter().map(|&(role, other)| (role, colors[other])).collect();
+                    around.sort_unstable();
+                    (colors[vertex], around)
+                })
+                .collect();
+            colors = rank(&signatures);
+            let refined = cell_count(&colors);
+            if refined == count {
+                return colors;
+            }
+            count = refined;
+        }
+    }
+
+    // The relabeled hypergraph of a discrete coloring
+    fn form(&self, colors: &[usize]) -> CanonicalForm {
+        let node_count = self.node_ids.len();
+        let mut nodes = vec![Vec::new(); node_count];
+        for node in 0..node_count {
+            nodes[colors[node]] = self.node_labels[node].clone();
+        }
+
+        let mut edges: Vec<Option<CanonicalEdge>> = vec![None; self.edge_ids.len()];
+        for edge in 0..self.edge_ids.len() {
+            let vertex = node_count + edge;
+            let members = |role: u8| {
+                let mut members: Vec<usize> = self.adjacency[vertex].iter().filter(|entry| entry.0 == role).map(|&(_, node)| colors[node]).collect();
+                members.sort_unstable();
+                members
+            };
+            edges[colors[vertex] - node_count] = Some(CanonicalEdge {
+                directed: self.directed[edge],
+                labels: self.edge_labels[edge].clone(),
+                head: members(HEAD),
+                tail: members(TAIL),
+            });
+        }
+        CanonicalForm { nodes, edges: edges.into_iter().flatten().collect() }
+    }
+}
+
+// Individualization-refinement search for the smallest canonical form
+struct Search<'i> {
+    incidence: &'i LabeledIncidence,
+    // The first leaf reached, with the individualized vertices leading to it
+    first: Option<(CanonicalForm, Vec<usize>, Vec<usize>)>,
+    best: Option<(CanonicalForm, Vec<usize>)>,
+    // Automorphisms found so far, as vertex permutations
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<'i> Search<'i> {
+    // Returns the depth to backtrack to when the rest of a subtree is known to be a copy of an explored one
+    fn explore(&mut self, colors: Vec<usize>, fixed: &mut Vec<usize>) -> Option<usize> {
+        let vertex_count = self.incidence.vertex_count();
+        if cell_count(&colors) == vertex_count {
+            return self.leaf(colors, fixed);
+        }
+
+        // First non-singleton cell
+        let mut sizes = vec![0; vertex_count];
+        for &color in &colors {
+            sizes[color] += 1;
+        }
+        let target = (0..vertex_count).find(|&color| sizes[color] > 1).expect("a non-discrete coloring has a larger cell");
+        let cell: Vec<usize> = (0..vertex_count).filter(|&vertex| colors[vertex] == target).collect();
+
+        let mut explored: Vec<usize> = Vec::new();
+        for vertex in cell {
+            // A known automorphism fixing the individualized vertices and mapping an explored vertex here makes
+            // this branch a copy of that one
+            let orbits = self.orbits(fixed);
+            if explored.iter().any(|&other| orbits.find(other) == orbits.find(vertex)) {
+                continue;
+            }
+            explored.push(vertex);
+
+            let signatures: Vec<(usize, bool)> = colors.iter().enumerate().map(|(other, &color)| (color, other != vertex)).collect();
+            let individualized = self.incidence.refine(rank(&signatures));
+            fixed.push(vertex);
+            let backtrack = self.explore(individualized, fixed);
+            fixed.pop();
+            if let Some(depth) = backtrack.filter(|&depth| depth < fixed.len()) {
+                return Some(depth);
+            }
+        }
+        None
+    }
+
+    fn leaf(&mut self, colors: Vec<usize>, path: &[usize]) -> Option<usize> {
+        let form = self.incidence.form(&colors);
+        let Some((first, first_colors, first_path)) = &self.first else {
+            self.first = Some((form.clone(), colors.clone(), path.to_vec()));
+            self.best = Some((form, colors));
+            return None;
+        };
+
+        if *first == form {
+            // The automorphism maps the first path onto this one, so everything below the point where they part
+            // mirrors the subtree of the first leaf
+            self.automorphisms.push(automorphism(first_colors, &colors));
+            return Some(path.iter().zip(first_path).take_while(|(a, b)| a == b).count());
+        }
+        match &self.best {
+            Some((best, best_colors)) if *best == form => self.automorphisms.push(automorphism(best_colors, &colors)),
+            Some((best, _)) if *best < form => {}
+            _ => self.best = Some((form, colors)),
+        }
+        None
+    }
+
+    // Orbits of the group generated by the found automorphisms that fix every vertex of `fixed`
+    fn orbits(&self, fixed: &[usize]) -> Orbits {
+        let mut orbits = Orbits { parent: (0..self.incidence.vertex_count()).collect() };
+        for automorphism in self.automorphisms.iter().filter(|automorphism| fixed.iter().all(|&vertex| automorphism[vertex] == vertex)) {
+            for (vertex, &image) in automorphism.iter().enumerate() {
+                orbits.union(vertex, image);
+            }
+        }
+        orbits
+    }
+}
+
+// Two leaves with the same form: mapping every vertex to the vertex of the same color in the reference leaf is an
+// automorphism
+fn automorphism(reference: &[usize], colors: &[usize]) -> Vec<usize> {
+    let mut vertex_of = vec![0; colors.len()];
+    for (vertex, &color) in reference.iter().enumerate() {
+        vertex_of[color] = vertex;
+    }
+    colors.iter().map(|&color| vertex_of[color]).collect()
+}
+
+struct Orbits {
+    parent: Vec<usize>,
+}
+
+impl Orbits {
+    fn find(&self, mut vertex: usize) -> usize {
+        while self.parent[vertex] != vertex {
+            vertex = self.parent[vertex];
+        }
+        vertex
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[a.max(b)] = a.min(b);
+        }
+    }
+}
+
+// Dense ranks of the signatures: equal signatures share a rank and smaller signatures get smaller ranks
+fn rank<T: Ord>(signatures: &[T]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..signatures.len()).collect();
+    order.sort_by(|&a, &b| signatures[a].cmp(&signatures[b]));
+    let mut ranks = vec![0; signatures.len()];
+    for pair in order.windows(2) {
+        ranks[pair[1]] = ranks[pair[0]] + usize::from(signatures[pair[1]] != signatures[pair[0]]);
+    }
+    ranks
+}
+
+fn cell_count(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |color| color + 1)
+}
+
+/// Isomorphism tests and fingerprints of the named hypergraphs
+pub struct IsomorphismService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> IsomorphismService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        IsomorphismService { repository }
+    }
+
+    pub fn canonical_labeling(&self, key: &str, label_keys: &[&str]) -> Result<CanonicalLabeling, Box<dyn Error>> {
+        Ok(canonical_labeling(&self.graph(key)?, label_keys))
+    }
+
+    pub fn fingerprint(&self, key: &str, label_keys: &[&str]) -> Result<String, Box<dyn Error>> {
+        Ok(canonical_form(&self.graph(key)?, label_keys).fingerprint())
+    }
+
+    pub fn isomorphism(&self, left: &str, right: &str, label_keys: &[&str]) -> Result<Option<Isomorphism>, Box<dyn Error>> {
+        let found = isomorphism(&self.graph(left)?, &self.graph(right)?, label_keys);
+        println!("🔍 '{}' and '{}' are {}isomorphic", left, right, if found.is_some() { "" } else { "not " });
+        Ok(found)
+    }
+
+    pub fn are_isomorphic(&self, left: &str, right: &str, label_keys: &[&str]) -> Result<bool, Box<dyn Error>> {
+        Ok(self.isomorphism(left, right, label_keys)?.is_some())
+    }
+
+    /// Groups the stored hypergraphs by canonical form and returns the groups of two or more keys, in key order
+    pub fn duplicates(&self, label_keys: &[&str]) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+        let mut groups: BTreeMap<CanonicalForm, Vec<String>> = BTreeMap::new();
+        let mut names = self.repository.get_names()?;
+        names.sort();
+        for name in names {
+            groups.entry(canonical_form(&self.graph(&name)?, label_keys)).or_default().push(name);
+        }
+
+        let mut duplicates: Vec<Vec<String>> = groups.into_values().filter(|group| group.len() > 1).collect();
+        duplicates.sort();
+        println!("🔍 Found {} groups of isomorphic hypergraphs", duplicates.len());
+        Ok(duplicates)
+    }
+
+    fn graph(&self, key: &str) -> Result<Graph, Box<dyn Error>> {
+        Ok(self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?)
+    }
+}
@@ -1 +1,29 @@
-pub mod simple_h_edge_service;
\ No newline at end of file
+pub mod simple_h_edge_service;
+pub mod h_graph_service;
+pub mod hql;
+pub mod hql_service;
+pub mod datalog;
+pub mod datalog_service;
+pub mod dual_h_graph_service;
+pub mod sparse;
+pub mod incidence_service;
+pub mod clique_expansion_service;
+pub mod s_line_graph_service;
+pub mod star_expansion_service;
+pub mod traversal_service;
+pub mod b_hyperpath_service;
+pub mod s_connectivity_service;
+pub mod centrality_service;
+pub mod random_walk_service;
+pub mod community_service;
+pub mod core_service;
+pub mod transversal_service;
+pub mod coloring_service;
+pub mod sub_h_graph_service;
+pub mod simplify_service;
+pub mod h_graph_algebra_service;
+pub mod diff_service;
+pub mod isomorphism_service;
+pub mod motif_service;
+pub mod generator_service;
+pub mod hif_service;
\ No newline at end of file
@@ -0,0 +1,361 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::generator_service::WeightedSampler;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashMap;
+use std::error::Error;
+
+// Regions of the Venn diagram of three hyperedges a, b and c, one bit each in a region code
+const REGIONS: [&str; 7] = ["a", "b", "c", "ab", "ac", "bc", "abc"];
+// Regions of two hyperedges
+const PAIR_REGIONS: [&str; 3] = ["a", "b", "ab"];
+
+/// An h-motif: the pattern of empty and non-empty regions of the Venn diagram of 2 or 3 connected, distinct
+/// hyperedges, up to their order. Bit i of `regions` is set when region i is non-empty, regions being
+/// `a, b, ab` for pairs and `a, b, c, ab, ac, bc, abc` for triples. Of the latter there are the 26 motifs of
+/// Lee et al.; they are numbered here by their smallest region code rather than by the figure of the paper
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Motif {
+    pub hyperedges: usize,
+    pub id: usize,
+    pub regions: u8,
+}
+
+impl Motif {
+    /// `p1`, `p2` for pairs and `h1` to `h26` for triples
+    pub fn name(&self) -> String {
+        format!("{}{}", if self.hyperedges == 2 { "p" } else { "h" }, self.id)
+    }
+
+    /// Open triples have two hyperedges that do not overlap
+    pub fn is_open(&self) -> bool {
+        self.hyperedges == 3 && ["ab", "ac", "bc"].iter().any(|pair| {
+            let (x, y) = (&pair[..1], &pair[1..]);
+            !REGIONS.iter().enumerate().any(|(bit, region)| self.regions >> bit & 1 == 1 && region.contains(x) && region.contains(y))
+        })
+    }
+}
+
+/// Every motif, pairs first, in id order
+pub fn motif_catalog() -> Vec<Motif> {
+    let mut catalog: Vec<Motif> = Vec::new();
+    for (hyperedges, regions) in [(2, &PAIR_REGIONS[..]), (3, &REGIONS[..])] {
+        let mut codes: Vec<u8> = classes(regions).into_iter().flatten().collect();
+        codes.sort_unstable();
+        codes.dedup();
+        catalog.extend(codes.into_iter().enumerate().map(|(position, regions)| Motif { hyperedges, id: position + 1, regions }));
+    }
+    catalog
+}
+
+// The motif class (smallest equivalent code) of every region code, `None` for codes of disconnected, empty or
+// repeated hyperedges
+fn classes(regions: &[&str]) -> Vec<Option<u8>> {
+    let letters: Vec<char> = if regions.len() == 3 { vec!['a', 'b'] } else { vec!['a', 'b', 'c'] };
+    let has = |code: usize, predicate: &dyn Fn(&str) -> bool| regions.iter().enumerate().any(|(bit, region)| code >> bit & 1 == 1 && predicate(region));
+    let pairs: Vec<(char, char)> = letters.iter().enumerate().flat_map(|(i, &x)| letters[i + 1..].iter().map(move |&y| (x, y))).collect();
+
+    (0..1usize << regions.len())
+        .map(|code| {
+            let nonempty = letters.iter().all(|&x| has(code, &|region| region.contains(x)));
+            let distinct = pairs.iter().all(|&(x, y)| has(code, &|region| region.contains(x) != region.contains(y)));
+            let overlaps = pairs.iter().filter(|&&(x, y)| has(code, &|region| region.contains(x) && region.contains(y))).count();
+            if !nonempty || !distinct || overlaps + 1 < letters.len() {
+                return None;
+            }
+            permutations(&letters).iter().map(|permutation| relabel(code, regions, &letters, permutation)).min()
+        })
+        .collect()
+}
+
+fn permutations(letters: &[char]) -> Vec<Vec<char>> {
+    if letters.len() <= 1 {
+        return vec![letters.to_vec()];
+    }
+    (0..letters.len())
+        .flat_map(|first| {
+            let mut rest = letters.to_vec();
+            let head = rest.remove(first);
+            permutations(&rest).into_iter().map(move |mut tail| {
+                tail.insert(0, head);
+                tail
+            })
+        })
+        .collect()
+}
+
+// The code of the same pattern once hyperedge letters[i] is renamed permutation[i]
+fn relabel(code: usize, regions: &[&str], letters: &[char], permutation: &[char]) -> u8 {
+    let mut relabeled = 0u8;
+    for region in regions.iter().enumerate().filter(|(bit, _)| code >> bit & 1 == 1).map(|(_, region)| region) {
+        let mut renamed: Vec<char> = region.chars().map(|letter| permutation[letters.iter().position(|&l| l == letter).unwrap()]).collect();
+        renamed.sort_unstable();
+        let renamed: String = renamed.into_iter().collect();
+        relabeled |= 1 << regions.iter().position(|region| *region == renamed).unwrap();
+    }
+    relabeled
+}
+
+/// How motif instances are counted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Counting {
+    /// Every instance is enumerated
+    Exact,
+    /// MoCHy-A+: `samples` hyperwedges (overlapping pairs) drawn uniformly, every instance containing one of them
+    /// counted with the inverse of its sampling probability. Unbiased; the error shrinks with more samples
+    Sampled { samples: usize },
+}
+
+/// A count (or estimate) for every motif of the catalog
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotifCounts {
+    pub motifs: Vec<Motif>,
+    pub counts: Vec<f64>,
+}
+
+impl MotifCounts {
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.motifs.iter().position(|motif| motif.name() == name).map(|position| self.counts[position])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Motif, f64)> {
+        self.motifs.iter().zip(self.counts.iter().copied())
+    }
+}
+
+/// The count of a motif next to its counts in randomized hypergraphs
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotifSignificance {
+    pub motif: Motif,
+    pub count: f64,
+    pub null_mean: f64,
+    pub null_std: f64,
+    /// (count - null mean) / (count + null mean + 1), as in Lee et al.; between -1 and 1
+    pub relative: f64,
+    /// (count - null mean) / null std, `None` when the null counts do not vary
+    pub z_score: Option<f64>,
+}
+
+/// h-motif counting over the incidence of a hypergraph. Membership alone counts: head and tail members alike,
+/// and nodes listed twice count once
+pub struct MotifCounter {
+    node_count: usize,
+    // Sorted members of every hyperedge
+    members: Vec<Vec<usize>>,
+    // Hyperedges of every node
+    node_edges: Vec<Vec<usize>>,
+}
+
+impl MotifCounter {
+    pub fn new(incidence: &IncidenceMatrix) -> Self {
+        let members = (0..incidence.edge_count()).map(|edge| incidence.edge_nodes(edge).to_vec()).collect();
+        MotifCounter::from_members(incidence.node_count(), members)
+    }
+
+    fn from_members(node_count: usize, mut members: Vec<Vec<usize>>) -> Self {
+        let mut node_edges = vec![Vec::new(); node_count];
+        for (edge, nodes) in members.iter_mut().enumerate() {
+            nodes.sort_unstable();
+            nodes.dedup();
+            for &node in nodes.iter() {
+                node_edges[node].push(edge);
+            }
+        }
+        MotifCounter { node_count, members, node_edges }
+    }
+
+    pub fn count(&self, counting: Counting, seed: u64) -> MotifCounts {
+        let catalog = motif_catalog();
+        let tables = Tables::new(&catalog);
+        let neighbors = self.neighbors();
+        let mut counts = vec![0.0; catalog.len()];
+        let mut marks = vec![0u8; self.node_count];
+
+        match counting {
+            Counting::Exact => {
+                for (a, around) in neighbors.iter().enumerate() {
+                    for (i, &b) in around.iter().enumerate() {
+                        if a < b {
+                            tables.add_pair(&mut counts, self.code(&[a, b], &mut marks), 1.0);
+                        }
+                        for &c in &around[i + 1..] {
+                            let closed = neighbors[b].binary_search(&c).is_ok();
+                            // Closed triples are found from each of their hyperedges, open ones only from the middle one
+                            if !closed || (a < b && a < c) {
+                                tables.add_triple(&mut counts, self.code(&[a, b, c], &mut marks), 1.0);
+                            }
+                        }
+                    }
+                }
+            }
+            Counting::Sampled { samples } => {
+                let wedges: Vec<(usize, usize)> = neighbors.iter().enumerate()
+                    .flat_map(|(a, around)| around.iter().filter(move |&&b| a < b).map(move |&b| (a, b)))
+                    .collect();
+                if !wedges.is_empty() && samples > 0 {
+                    let scale = wedges.len() as f64 / samples as f64;
+                    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                    for _ in 0..samples {
+                        let (a, b) = wedges[rng.gen_range(0..wedges.len())];
+                        tables.add_pair(&mut counts, self.code(&[a, b], &mut marks), scale);
+                        for c in union(&neighbors[a], &neighbors[b]).into_iter().filter(|&c| c != a && c != b) {
+                            let closed = neighbors[a].binary_search(&c).is_ok() && neighbors[b].binary_search(&c).is_ok();
+                            // An instance holds three hyperwedges when closed and two when open
+                            let wedges_in_instance = if closed { 3.0 } else { 2.0 };
+                            tables.add_triple(&mut counts, self.code(&[a, b, c], &mut marks), scale / wedges_in_instance);
+                        }
+                    }
+                }
+            }
+        }
+
+        MotifCounts { motifs: catalog, counts }
+    }
+
+    /// Compares the counts with those of `randomizations` randomized hypergraphs (see `randomize`), counted the
+    /// same way. Randomizations are drawn from `seed`
+    pub fn compare(&self, counting: Counting, randomizations: usize, seed: u64) -> Vec<MotifSignificance> {
+        let real = self.count(counting, seed);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let null: Vec<MotifCounts> = (0..randomizations).map(|_| self.randomize(&mut rng).count(counting, rng.gen())).collect();
+
+        real.iter()
+            .enumerate()
+            .map(|(position, (motif, count))| {
+                let samples: Vec<f64> = null.iter().map(|counts| counts.counts[position]).collect();
+                let null_mean = if samples.is_empty() { 0.0 } else { samples.iter().sum::<f64>() / samples.len() as f64 };
+                let null_std = if samples.is_empty() { 0.0 } else { (samples.iter().map(|x| (x - null_mean).powi(2)).sum::<f64>() / samples.len() as f64).sqrt() };
+                MotifSignificance {
+                    motif: *motif,
+                    count,
+                    null_mean,
+                    null_std,
+                    relative: (count - null_mean) / (count + null_mean + 1.0),
+                    z_score: (null_std > 0.0).then(|| (count - null_mean) / null_std),
+                }
+            })
+            .collect()
+    }
+
+    /// The HyperCL null model: every hyperedge keeps its size and draws that many distinct nodes with probability
+    /// proportional to their degree, so node degrees are kept in expectation
+    pub fn randomize<R: Rng>(&self, rng: &mut R) -> MotifCounter {
+        let degrees: Vec<f64> = self.node_edges.iter().map(|edges| edges.len() as f64).collect();
+        let mut sampler = WeightedSampler::new(&degrees);
+        let members = self.members.iter().map(|nodes| sampler.sample_distinct(rng, nodes.len())).collect();
+        MotifCounter::from_members(self.node_count, members)
+    }
+
+    // The sorted hyperedges overlapping every hyperedge
+    fn neighbors(&self) -> Vec<Vec<usize>> {
+        self.members.iter()
+            .enumerate()
+            .map(|(edge, nodes)| {
+                let mut around: Vec<usize> = nodes.iter().flat_map(|&node| self.node_edges[node].iter().copied()).filter(|&other| other != edge).collect();
+                around.sort_unstable();
+                around.dedup();
+                around
+            })
+            .collect()
+    }
+
+    // The region code of 2 or 3 hyperedges: every node gets a bit per hyperedge it belongs to
+    fn code(&self, edges: &[usize], marks: &mut [u8]) -> usize {
+        for (bit, &edge) in edges.iter().enumerate() {
+            for &node in &self.members[edge] {
+                marks[node] |= 1 << bit;
+            }
+        }
+
+        let mut code = 0;
+        for &edge in edges {
+            for &node in &self.members[edge] {
+                if marks[node] != 0 {
+                    code |= 1 << region_bit(marks[node], edges.len());
+                    marks[node] = 0;
+                }
+            }
+        }
+        code
+    }
+}
+
+// The region of a membership mask (bit i for hyperedge i)
+fn region_bit(mask: u8, hyperedges: usize) -> usize {
+    match (hyperedges, mask) {
+        (2, 0b01) => 0,
+        (2, 0b10) => 1,
+        (2, _) => 2,
+        (_, 0b001) => 0,
+        (_, 0b010) => 1,
+        (_, 0b100) => 2,
+        (_, 0b011) => 3,
+        (_, 0b101) => 4,
+        (_, 0b110) => 5,
+        _ => 6,
+    }
+}
+
+fn union(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let mut union: Vec<usize> = a.iter().chain(b).copied().collect();
+    union.sort_unstable();
+    union.dedup();
+    union
+}
+
+// Position in the catalog of every region code, for pairs and triples
+struct Tables {
+    pairs: Vec<Option<usize>>,
+    triples: Vec<Option<usize>>,
+}
+
+impl Tables {
+    fn new(catalog: &[Motif]) -> Self {
+        let positions: HashMap<(usize, u8), usize> = catalog.iter().enumerate().map(|(position, motif)| ((motif.hyperedges, motif.regions), position)).collect();
+        let table = |hyperedges: usize, regions: &[&str]| -> Vec<Option<usize>> {
+            classes(regions).into_iter().map(|class| class.map(|code| positions[&(hyperedges, code)])).collect()
+        };
+        Tables { pairs: table(2, &PAIR_REGIONS), triples: table(3, &REGIONS) }
+    }
+
+    // Repeated hyperedges have no motif and are skipped
+    fn add_pair(&self, counts: &mut [f64], code: usize, amount: f64) {
+        if let Some(position) = self.pairs[code] {
+            counts[position] += amount;
+        }
+    }
+
+    fn add_triple(&self, counts: &mut [f64], code: usize, amount: f64) {
+        if let Some(position) = self.triples[code] {
+            counts[position] += amount;
+        }
+    }
+}
+
+/// h-motif statistics of the named hypergraphs
+pub struct MotifService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> MotifService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        MotifService { repository }
+    }
+
+    pub fn count(&self, key: &str, counting: Counting, seed: u64) -> Result<MotifCounts, Box<dyn Error>> {
+        let counts = MotifCounter::new(&self.incidence(key)?).count(counting, seed);
+        println!("🔍 Counted {:.0} motif instances in '{}'", counts.counts.iter().sum::<f64>(), key);
+        Ok(counts)
+    }
+
+    pub fn compare(&self, key: &str, counting: Counting, randomizations: usize, seed: u64) -> Result<Vec<MotifSignificance>, Box<dyn Error>> {
+        Ok(MotifCounter::new(&self.incidence(key)?).compare(counting, randomizations, seed))
+    }
+
+    fn incidence(&self, key: &str) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, None))
+    }
+}
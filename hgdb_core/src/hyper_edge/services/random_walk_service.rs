@@ -0,0 +1,200 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::services::centrality_service::ScoreTable;
+use crate::hyper_edge::services::sparse::incidence::{IncidenceBuilder, IncidenceMatrix};
+use crate::hyper_edge::services::sparse::matrix::SparseMatrix;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// `main_properties` key holding the edge-dependent vertex weights of a hyperedge as `node:weight` values
+pub const VERTEX_WEIGHTS_KEY: &str = "vertex_weights";
+
+/// Edge-dependent vertex weights keyed by (edge id, node id)
+pub type VertexWeights = HashMap<(String, String), f64>;
+
+/// Reads the edge-dependent vertex weights of a hyperedge from its `vertex_weights` property
+pub fn vertex_weights(edge: &SimpleHyperEdge<String, String, String>) -> Vec<(String, f64)> {
+    edge.main_properties.iter()
+        .filter(|property| property.key == VERTEX_WEIGHTS_KEY)
+        .flat_map(|property| property.value.iter())
+        .filter_map(|entry| {
+            let (node, weight) = entry.rsplit_once(':')?;
+            Some((node.trim().to_string(), weight.trim().parse::<f64>().ok()?))
+        })
+        .collect()
+}
+
+/// A random walk on a hypergraph. From node v the walker picks one of the hyperedges it can leave through, with
+/// probability proportional to the edge weight, then moves to one of the nodes that edge leads to, with probability
+/// proportional to the edge-dependent vertex weight gamma_e(u) (1 unless given). Undirected hyperedges can be left
+/// from any member towards any member; directed ones only from a tail node towards a head node. Non-traversable
+/// hyperedges are never used
+pub struct RandomWalk<'g> {
+    incidence: &'g IncidenceMatrix,
+    transitions: SparseMatrix,
+}
+
+impl<'g> RandomWalk<'g> {
+    /// Builds the walk with gamma_e(u) = 1 everywhere
+    pub fn new(incidence: &'g IncidenceMatrix) -> Self {
+        Self::with_vertex_weights(incidence, &HashMap::new())
+    }
+
+    /// Builds the walk with edge-dependent vertex weights keyed by (edge id, node id)
+    pub fn with_vertex_weights(incidence: &'g IncidenceMatrix, gamma: &VertexWeights) -> Self {
+        let gamma_of = |edge: usize, node: usize| {
+            gamma.get(&(incidence.edges.id(edge).to_string(), incidence.nodes.id(node).to_string())).copied().unwrap_or(1.0)
+        };
+        // edge -> node: enter a target node with probability gamma_e(u) / sum of gamma_e over the targets
+        let enter_rows: Vec<Vec<(usize, f64)>> = (0..incidence.edge_count())
+            .map(|edge| {
+                let targets = if incidence.is_directed(edge) { incidence.edge_head(edge) } else { incidence.edge_nodes(edge) };
+                let total: f64 = targets.iter().map(|&node| gamma_of(edge, node)).sum();
+                targets.iter()
+                    .filter(|_| total > 0.0)
+                    .map(|&node| (node, gamma_of(edge, node) / total))
+                    .collect()
+            })
+            .collect();
+        // Edges leading nowhere (an empty head, or no target weight) are not left through, so no mass is lost in
+        // them and nodes with only such edges are dangling
+        let usable = |edge: usize| incidence.is_traversable(edge) && !enter_rows[edge].is_empty();
+
+        // node -> edge: leave through an edge with probability w(e) / sum of the weights of the leavable edges
+        let leave_rows: Vec<Vec<(usize, f64)>> = (0..incidence.node_count())
+            .map(|node| {
+                let edges: Vec<usize> = incidence.node_edges(node).iter().copied()
+                    .filter(|&edge| usable(edge) && (!incidence.is_directed(edge) || incidence.edge_tail(edge).contains(&node)))
+                    .collect();
+                let total: f64 = edges.iter().map(|&edge| incidence.edge_weight(edge)).sum();
+                edges.into_iter()
+                    .filter(|_| total > 0.0)
+                    .map(|edge| (edge, incidence.edge_weight(edge) / total))
+                    .collect()
+            })
+            .collect();
+        let leave = SparseMatrix::from_rows(incidence.edge_count(), leave_rows);
+        let enter = SparseMatrix::from_rows(incidence.node_count(), enter_rows);
+
+        RandomWalk { incidence, transitions: leave.multiply(&enter) }
+    }
+
+    /// The node x node transition matrix; rows of nodes that cannot leave (dangling nodes) are empty
+    pub fn transitions(&self) -> &SparseMatrix {
+        &self.transitions
+    }
+
+    /// The probability of moving from one node to another in one step
+    pub fn probability(&self, from: &str, to: &str) -> f64 {
+        match (self.incidence.nodes.position(from), self.incidence.nodes.position(to)) {
+            (Some(from), Some(to)) => self.transitions.get(from, to),
+            _ => 0.0,
+        }
+    }
+
+    /// The distribution of the walker's position after `steps` steps from `start`. Mass reaching a dangling node
+    /// stays there
+    pub fn distribution_after(&self, start: &str, steps: usize) -> Vec<(String, f64)> {
+        let mut x = vec![0.0; self.incidence.node_count()];
+        if let Some(start) = self.incidence.nodes.position(start) {
+            x[start] = 1.0;
+        }
+        for _ in 0..steps {
+            let mut next = self.transitions.transpose_mul_vec(&x);
+            for (node, mass) in x.iter().enumerate() {
+                if self.transitions.row(node).0.is_empty() {
+                    next[node] += mass;
+                }
+            }
+            x = next;
+        }
+        self.incidence.nodes.ids().iter().cloned().zip(x).collect()
+    }
+
+    /// PageRank of the walk: with probability `alpha` the walker follows the walk, otherwise (and always from a
+    /// dangling node) it jumps to a uniformly chosen node
+    pub fn pagerank(&self, alpha: f64, tolerance: f64, max_iterations: usize) -> Result<ScoreTable, Box<dyn Error>> {
+        let count = self.incidence.node_count();
+        let teleport = vec![1.0 / count.max(1) as f64; count];
+        let scores = self.iterate(&teleport, alpha, tolerance, max_iterations)?;
+        Ok(ScoreTable::new("pagerank", self.incidence.nodes.ids().iter().cloned().zip(scores).collect()))
+    }
+
+    /// Personalized PageRank: jumps (and dangling mass) go back to the seed nodes, uniformly
+    pub fn personalized_pagerank(&self, seeds: &[&str], alpha: f64, tolerance: f64, max_iterations: usize) -> Result<ScoreTable, Box<dyn Error>> {
+        let seeds: Vec<usize> = seeds.iter().filter_map(|seed| self.incidence.nodes.position(seed)).collect();
+        if seeds.is_empty() {
+            return Err("Personalized PageRank needs at least one known seed node".into());
+        }
+
+        let mut teleport = vec![0.0; self.incidence.node_count()];
+        for &seed in &seeds {
+            teleport[seed] += 1.0 / seeds.len() as f64;
+        }
+        let scores = self.iterate(&teleport, alpha, tolerance, max_iterations)?;
+        Ok(ScoreTable::new("personalized_pagerank", self.incidence.nodes.ids().iter().cloned().zip(scores).collect()))
+    }
+
+    // Power iteration x <- alpha P^T x + (alpha * dangling mass + 1 - alpha) teleport
+    fn iterate(&self, teleport: &[f64], alpha: f64, tolerance: f64, max_iterations: usize) -> Result<Vec<f64>, Box<dyn Error>> {
+        if !(0.0..1.0).contains(&alpha) {
+            return Err(format!("Damping factor {} must be in [0, 1)", alpha).into());
+        }
+        let mut x = teleport.to_vec();
+
+        for _ in 0..max_iterations {
+            let dangling: f64 = x.iter().enumerate()
+                .filter(|(node, _)| self.transitions.row(*node).0.is_empty())
+                .map(|(_, mass)| mass)
+                .sum();
+            let jump = alpha * dangling + 1.0 - alpha;
+            let next: Vec<f64> = self.transitions.transpose_mul_vec(&x).into_iter()
+                .zip(teleport)
+                .map(|(walked, teleport)| alpha * walked + jump * teleport)
+                .collect();
+
+            let change: f64 = next.iter().zip(&x).map(|(next, previous)| (next - previous).abs()).sum();
+            x = next;
+            if change < tolerance {
+                return Ok(x);
+            }
+        }
+
+        Err(format!("PageRank did not converge in {} iterations", max_iterations).into())
+    }
+}
+
+/// Random-walk rankings of the stored hyperedges
+pub struct RandomWalkService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> RandomWalkService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        RandomWalkService { repository }
+    }
+
+    /// Builds the incidence of the stored hyperedges (edge weights from `weight_key`) together with their
+    /// edge-dependent vertex weights
+    pub fn load(&self, weight_key: Option<&str>) -> Result<(IncidenceMatrix, VertexWeights), Box<dyn Error>> {
+        let mut builder = IncidenceBuilder::new(weight_key);
+        let mut gamma = VertexWeights::new();
+        self.repository.scan(|edge| {
+            for (node, weight) in vertex_weights(&edge) {
+                gamma.insert((edge.id.clone(), node), weight);
+            }
+            builder.add_edge(&edge);
+        })?;
+        Ok((builder.build(), gamma))
+    }
+
+    pub fn pagerank(&self, weight_key: Option<&str>, alpha: f64, tolerance: f64) -> Result<ScoreTable, Box<dyn Error>> {
+        let (incidence, gamma) = self.load(weight_key)?;
+        RandomWalk::with_vertex_weights(&incidence, &gamma).pagerank(alpha, tolerance, 1000)
+    }
+
+    pub fn personalized_pagerank(&self, seeds: &[&str], weight_key: Option<&str>, alpha: f64, tolerance: f64) -> Result<ScoreTable, Box<dyn Error>> {
+        let (incidence, gamma) = self.load(weight_key)?;
+        RandomWalk::with_vertex_weights(&incidence, &gamma).personalized_pagerank(seeds, alpha, tolerance, 1000)
+    }
+}
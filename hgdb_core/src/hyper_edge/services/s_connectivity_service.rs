@@ -0,0 +1,185 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::h_graph_service::{set_node_properties, set_property};
+use crate::hyper_edge::services::sparse::adjacency::WeightedAdjacency;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use crate::hyper_edge::services::sparse::line_graph::{dual_s_line_graph, s_line_graph};
+use std::collections::VecDeque;
+use std::error::Error;
+
+/// s-connectivity of the hyperedges (or of the nodes) of a hypergraph, computed on its s-line graph. Two edges are
+/// s-connected when a walk of edges, each sharing at least s nodes with the next, links them; the s-distance is the
+/// length of the shortest such walk. Vertices without any s-neighbor form singleton components
+pub struct SConnectivity {
+    pub s: usize,
+    pub line_graph: WeightedAdjacency,
+    component: Vec<usize>,
+    members: Vec<Vec<usize>>,
+}
+
+impl SConnectivity {
+    /// s-connectivity between hyperedges
+    pub fn edges(incidence: &IncidenceMatrix, s: usize) -> Self {
+        Self::new(s_line_graph(incidence, s), s)
+    }
+
+    /// s-connectivity between nodes (through hyperedges they share)
+    pub fn nodes(incidence: &IncidenceMatrix, s: usize) -> Self {
+        Self::new(dual_s_line_graph(incidence, s), s)
+    }
+
+    pub fn new(line_graph: WeightedAdjacency, s: usize) -> Self {
+        let count = line_graph.index.len();
+        let mut component = vec![usize::MAX; count];
+        let mut members = Vec::new();
+
+        for start in 0..count {
+            if component[start] != usize::MAX {
+                continue;
+            }
+            let id = members.len();
+            let mut queue = VecDeque::from([start]);
+            let mut current = Vec::new();
+            component[start] = id;
+            while let Some(vertex) = queue.pop_front() {
+                current.push(vertex);
+                for &next in line_graph.matrix.row(vertex).0 {
+                    if component[next] == usize::MAX {
+                        component[next] = id;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            current.sort_unstable();
+            members.push(current);
+        }
+
+        SConnectivity { s, line_graph, component, members }
+    }
+
+    /// The s-connected components as lists of ids, ordered by their first member
+    pub fn components(&self) -> Vec<Vec<String>> {
+        self.members.iter()
+            .map(|members| members.iter().map(|&vertex| self.line_graph.index.id(vertex).to_string()).collect())
+            .collect()
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns the number of the component an id belongs to
+    pub fn component_of(&self, id: &str) -> Option<usize> {
+        self.line_graph.index.position(id).map(|vertex| self.component[vertex])
+    }
+
+    // method to get the ids of the component an id belongs to
+    pub fn members_of(&self, id: &str) -> Vec<String> {
+        self.component_of(id).map_or(Vec::new(), |component| {
+            self.members[component].iter().map(|&vertex| self.line_graph.index.id(vertex).to_string()).collect()
+        })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.members.len() == 1
+    }
+
+    /// The s-distance between two ids, or None when they are not s-connected
+    pub fn distance(&self, from: &str, to: &str) -> Option<usize> {
+        let (from, to) = (self.line_graph.index.position(from)?, self.line_graph.index.position(to)?);
+        self.distances(from)[to]
+    }
+
+    /// The largest s-distance from an id to another member of its component
+    pub fn eccentricity(&self, id: &str) -> Option<usize> {
+        let vertex = self.line_graph.index.position(id)?;
+        self.distances(vertex).into_iter().flatten().max()
+    }
+
+    /// The s-diameter: the largest s-distance between two ids. None when the hypergraph is empty or not s-connected
+    pub fn diameter(&self) -> Option<usize> {
+        if !self.is_connected() {
+            return None;
+        }
+        self.component_diameters().into_iter().next()
+    }
+
+    /// The diameter of every component, in component order
+    pub fn component_diameters(&self) -> Vec<usize> {
+        self.members.iter()
+            .map(|members| members.iter().map(|&vertex| self.distances(vertex).into_iter().flatten().max().unwrap_or(0)).max().unwrap_or(0))
+            .collect()
+    }
+
+    // BFS distances in the s-line graph
+    fn distances(&self, start: usize) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.line_graph.index.len()];
+        let mut queue = VecDeque::from([start]);
+        distances[start] = Some(0);
+        while let Some(vertex) = queue.pop_front() {
+            let distance = distances[vertex].unwrap_or(0);
+            for &next in self.line_graph.matrix.row(vertex).0 {
+                if distances[next].is_none() {
+                    distances[next] = Some(distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+}
+
+/// s-connectivity analysis of the named hypergraphs
+pub struct SConnectivityService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> SConnectivityService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        SConnectivityService { repository }
+    }
+
+    pub fn edge_connectivity(&self, key: &str, s: usize) -> Result<SConnectivity, Box<dyn Error>> {
+        Ok(SConnectivity::edges(&self.incidence(key)?, s))
+    }
+
+    pub fn node_connectivity(&self, key: &str, s: usize) -> Result<SConnectivity, Box<dyn Error>> {
+        Ok(SConnectivity::nodes(&self.incidence(key)?, s))
+    }
+
+    /// Stores the component numbers as the `s<s>_component` property of every hyperedge and node, and the
+    /// component counts and s-diameters (when connected) as properties of the graph
+    pub fn save_components(&self, key: &str, s: usize) -> Result<(), Box<dyn Error>> {
+        let mut graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let incidence = IncidenceMatrix::from_graph(&graph, None);
+        let edges = SConnectivity::edges(&incidence, s);
+        let nodes = SConnectivity::nodes(&incidence, s);
+        let component_key = format!("s{}_component", s);
+
+        for edge in graph.hyper_edges.iter_mut() {
+            if let Some(component) = edges.component_of(&edge.id) {
+                set_property(&mut edge.main_properties, &component_key, component.to_string());
+            }
+        }
+        let node_components: Vec<(String, String)> = incidence.nodes.ids().iter()
+            .map(|id| (id.clone(), nodes.component_of(id).unwrap_or_default().to_string()))
+            .collect();
+        set_node_properties(&mut graph, &component_key, node_components);
+
+        set_property(&mut graph.properties, &format!("s{}_edge_components", s), edges.component_count().to_string());
+        set_property(&mut graph.properties, &format!("s{}_node_components", s), nodes.component_count().to_string());
+        if let Some(diameter) = edges.diameter() {
+            set_property(&mut graph.properties, &format!("s{}_diameter", s), diameter.to_string());
+        }
+
+        self.repository.update(key, &graph)?;
+        println!("✅ Saved {}-components of '{}': {} edge components, {} node components", s, key, edges.component_count(), nodes.component_count());
+        Ok(())
+    }
+
+    fn incidence(&self, key: &str) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, None))
+    }
+}
@@ -0,0 +1,32 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::services::incidence_service::IncidenceService;
+use crate::hyper_edge::services::sparse::adjacency::WeightedAdjacency;
+use crate::hyper_edge::services::sparse::line_graph::{dual_s_line_graph, s_line_graph};
+use std::error::Error;
+
+/// Builds s-line graphs of the stored hyperedges through the sparse incidence index
+pub struct SLineGraphService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> SLineGraphService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        SLineGraphService { repository }
+    }
+
+    /// Hyperedges are adjacent when they share at least `s` nodes
+    pub fn line_graph(&self, s: usize) -> Result<WeightedAdjacency, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(None)?;
+        let line_graph = s_line_graph(&incidence, s);
+        println!("✅ Built {}-line graph: {} hyperedges, {} links", s, line_graph.index.len(), line_graph.link_count());
+        Ok(line_graph)
+    }
+
+    /// Nodes are adjacent when they share at least `s` hyperedges
+    pub fn dual_line_graph(&self, s: usize) -> Result<WeightedAdjacency, Box<dyn Error>> {
+        let incidence = IncidenceService::new(self.repository).build(None)?;
+        let line_graph = dual_s_line_graph(&incidence, s);
+        println!("✅ Built dual {}-line graph: {} nodes, {} links", s, line_graph.index.len(), line_graph.link_count());
+        Ok(line_graph)
+    }
+}
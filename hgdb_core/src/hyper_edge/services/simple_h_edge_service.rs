@@ -101,13 +101,19 @@ impl<'a> DualHyperEdgeService<'a> {
 
     // method to print the matrix information
     pub fn print_matrix(&self, matrix: &Vec<Vec<bool>>) {
-        println!("🔢 Matrix [{}x{}]:", matrix.len(), if matrix.is_empty() { 0 } else { matrix[0].len() });
+        print!("{}", self.format_matrix(matrix));
+    }
+
+    // method to render the matrix information in the same layout `print_matrix` prints
+    pub fn format_matrix(&self, matrix: &[Vec<bool>]) -> String {
+        let mut output = format!("🔢 Matrix [{}x{}]:\n", matrix.len(), if matrix.is_empty() { 0 } else { matrix[0].len() });
         for row in matrix {
             let row_str: String = row.iter()
                 .map(|&val| if val { "1" } else { "0" })
                 .collect::<Vec<&str>>()
                 .join(" ");
-            println!("[ {} ]", row_str);
+            output.push_str(&format!("[ {} ]\n", row_str));
         }
-    } 
+        output
+    }
 }
@@ -0,0 +1,242 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::h_graph_service::edge_members;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::error::Error;
+
+/// Property listing the ids merged into a representative node or hyperedge
+pub const MERGED_IDS_KEY: &str = "merged_ids";
+
+type Graph = HyperGraph<String, String, String>;
+type Edge = SimpleHyperEdge<String, String, String>;
+/// What makes two hyperedges duplicates: direction, head set and tail set
+pub type EdgeShape<'g> = (bool, BTreeSet<&'g String>, Option<BTreeSet<&'g String>>);
+
+/// One simplification step; steps run in the order they are given
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Simplification {
+    /// Hyperedges with the same direction and the same head and tail sets become one
+    CollapseEdges,
+    /// Nodes lying in exactly the same hyperedges, with the same roles, become one
+    CollapseNodes,
+    /// Hyperedges with fewer than two distinct members are dropped
+    RemoveSingletons,
+}
+
+/// Ids folded into a representative, which keeps its own id
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge {
+    pub representative: String,
+    pub merged: Vec<String>,
+}
+
+/// What a simplification merged and removed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SimplificationReport {
+    pub merged_edges: Vec<Merge>,
+    pub merged_nodes: Vec<Merge>,
+    pub removed_edges: Vec<String>,
+}
+
+impl SimplificationReport {
+    pub fn is_empty(&self) -> bool {
+        self.merged_edges.is_empty() && self.merged_nodes.is_empty() && self.removed_edges.is_empty()
+    }
+}
+
+/// Runs the given steps on a copy of the graph and reports what changed
+pub fn simplify(graph: &Graph, steps: &[Simplification]) -> (Graph, SimplificationReport) {
+    let mut graph = graph.clone();
+    let mut report = SimplificationReport::default();
+
+    for step in steps {
+        match step {
+            Simplification::CollapseEdges => {
+                let (collapsed, merges) = collapse_edges(&graph);
+                graph = collapsed;
+                report.merged_edges.extend(merges);
+            }
+            Simplification::CollapseNodes => {
+                let (collapsed, merges) = collapse_nodes(&graph);
+                graph = collapsed;
+                report.merged_nodes.extend(merges);
+            }
+            Simplification::RemoveSingletons => {
+                let (pruned, removed) = remove_singletons(&graph);
+                graph = pruned;
+                report.removed_edges.extend(removed);
+            }
+        }
+    }
+
+    (graph, report)
+}
+
+pub fn edge_shape(edge: &Edge) -> EdgeShape<'_> {
+    (edge.directed, edge.head_hyper_nodes.iter().collect(), edge.tail_hyper_nodes.as_ref().map(|tail| tail.iter().collect()))
+}
+
+/// Collapses hyperedges with identical members: the first of them stays, with the `main_properties` of all of them
+/// merged (values of a shared key are united) and the other ids listed under `merged_ids`
+pub fn collapse_edges(graph: &Graph) -> (Graph, Vec<Merge>) {
+    let mut groups: Vec<Vec<&Edge>> = Vec::new();
+    let mut positions: HashMap<EdgeShape, usize> = HashMap::new();
+    for edge in &graph.hyper_edges {
+        let position = *positions.entry(edge_shape(edge)).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[position].push(edge);
+    }
+
+    let mut merges = Vec::new();
+    let hyper_edges = groups.into_iter()
+        .map(|group| {
+            let mut representative = group[0].clone();
+            if group.len() > 1 {
+                for duplicate in &group[1..] {
+                    merge_properties(&mut representative.main_properties, &duplicate.main_properties);
+                }
+                let merged: Vec<String> = group[1..].iter().map(|edge| edge.id.clone()).collect();
+                merge_properties(&mut representative.main_properties, &[Property { key: MERGED_IDS_KEY.to_string(), value: merged.clone() }]);
+                merges.push(Merge { representative: representative.id.clone(), merged });
+            }
+            representative
+        })
+        .collect();
+
+    (HyperGraph { hyper_edges, ..graph.clone() }, merges)
+}
+
+/// Collapses nodes that occur in exactly the same hyperedges with the same roles into the first of them, which
+/// takes over their properties and lists them under `merged_ids`. Nodes outside every hyperedge are left alone
+pub fn collapse_nodes(graph: &Graph) -> (Graph, Vec<Merge>) {
+    // node id -> its (edge, role) memberships, role true for the tail
+    let mut memberships: HashMap<&String, BTreeSet<(usize, bool)>> = HashMap::new();
+    let mut order: Vec<&String> = graph.hyper_nodes.iter().map(|node| &node.id).collect();
+    for (position, edge) in graph.hyper_edges.iter().enumerate() {
+        let roles = edge.head_hyper_nodes.iter().map(|node| (node, false))
+            .chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()).map(|node| (node, true)));
+        for (node, tail) in roles {
+            order.push(node);
+            memberships.entry(node).or_default().insert((position, tail));
+        }
+    }
+
+    let mut seen: HashSet<&String> = HashSet::new();
+    let mut classes: Vec<Vec<&String>> = Vec::new();
+    let mut class_of: HashMap<&BTreeSet<(usize, bool)>, usize> = HashMap::new();
+    for node in order {
+        let Some(membership) = memberships.get(node) else {
+            continue;
+        };
+        if !seen.insert(node) {
+            continue;
+        }
+        let class = *class_of.entry(membership).or_insert_with(|| {
+            classes.push(Vec::new());
+            classes.len() - 1
+        });
+        classes[class].push(node);
+    }
+
+    let mut representative_of: HashMap<&String, &String> = HashMap::new();
+    let mut merges = Vec::new();
+    for class in classes.iter().filter(|class| class.len() > 1) {
+        for node in &class[1..] {
+            representative_of.insert(node, class[0]);
+        }
+        merges.push(Merge { representative: class[0].clone(), merged: class[1..].iter().map(|node| node.to_string()).collect() });
+    }
+
+    let mut collapsed = graph.clone();
+    for edge in collapsed.hyper_edges.iter_mut() {
+        edge.head_hyper_nodes.retain(|node| !representative_of.contains_key(node));
+        if let Some(tail) = edge.tail_hyper_nodes.as_mut() {
+            tail.retain(|node| !representative_of.contains_key(node));
+        }
+    }
+
+    let properties: HashMap<&String, &Vec<Property<String, String>>> = graph.hyper_nodes.iter().map(|node| (&node.id, &node.properties)).collect();
+    collapsed.hyper_nodes.retain(|node| !representative_of.contains_key(&node.id));
+    for merge in &merges {
+        let position = match collapsed.hyper_nodes.iter().position(|node| node.id == merge.representative) {
+            Some(position) => position,
+            None => {
+                collapsed.hyper_nodes.push(HyperNode { id: merge.representative.clone(), properties: Vec::new() });
+                collapsed.hyper_nodes.len() - 1
+            }
+        };
+        let node = &mut collapsed.hyper_nodes[position];
+        for merged in &merge.merged {
+            if let Some(merged_properties) = properties.get(merged) {
+                merge_properties(&mut node.properties, merged_properties);
+            }
+        }
+        merge_properties(&mut node.properties, &[Property { key: MERGED_IDS_KEY.to_string(), value: merge.merged.clone() }]);
+    }
+
+    (collapsed, merges)
+}
+
+/// Drops the hyperedges with fewer than two distinct members and returns their ids
+pub fn remove_singletons(graph: &Graph) -> (Graph, Vec<String>) {
+    let (kept, removed): (Vec<Edge>, Vec<Edge>) = graph.hyper_edges.iter().cloned().partition(|edge| edge_members(edge).len() > 1);
+    (HyperGraph { hyper_edges: kept, ..graph.clone() }, removed.into_iter().map(|edge| edge.id).collect())
+}
+
+// Adds the values of `other` to `properties`, uniting the values of keys present in both
+fn merge_properties(properties: &mut Vec<Property<String, String>>, other: &[Property<String, String>]) {
+    for property in other {
+        match properties.iter_mut().find(|existing| existing.key == property.key) {
+            Some(existing) => {
+                for value in &property.value {
+                    if !existing.value.contains(value) {
+                        existing.value.push(value.clone());
+                    }
+                }
+            }
+            None => properties.push(property.clone()),
+        }
+    }
+}
+
+/// Simplifies the named hypergraphs in place, or previews what a simplification would do
+pub struct SimplifyService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> SimplifyService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        SimplifyService { repository }
+    }
+
+    /// Dry run: reports what the steps would merge and remove without writing anything
+    pub fn preview(&self, key: &str, steps: &[Simplification]) -> Result<SimplificationReport, Box<dyn Error>> {
+        let (_, report) = simplify(&self.graph(key)?, steps);
+        println!("🔍 Simplifying '{}' would merge {} hyperedges and {} nodes and remove {} hyperedges",
+                 key, merged_count(&report.merged_edges), merged_count(&report.merged_nodes), report.removed_edges.len());
+        Ok(report)
+    }
+
+    /// Applies the steps and stores the simplified graph under the same key
+    pub fn apply(&self, key: &str, steps: &[Simplification]) -> Result<SimplificationReport, Box<dyn Error>> {
+        let (simplified, report) = simplify(&self.graph(key)?, steps);
+        if !report.is_empty() {
+            self.repository.create(key, &simplified)?;
+        }
+        println!("✅ Simplified '{}': merged {} hyperedges and {} nodes, removed {} hyperedges",
+                 key, merged_count(&report.merged_edges), merged_count(&report.merged_nodes), report.removed_edges.len());
+        Ok(report)
+    }
+
+    fn graph(&self, key: &str) -> Result<Graph, Box<dyn Error>> {
+        Ok(self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?)
+    }
+}
+
+fn merged_count(merges: &[Merge]) -> usize {
+    merges.iter().map(|merge| merge.merged.len()).sum()
+}
@@ -0,0 +1,99 @@
+use crate::hyper_edge::entity::simple_h_edge::Property;
+use crate::hyper_edge::entity::relationship::relationship::Relationship;
+use crate::hyper_edge::services::sparse::incidence::IdIndex;
+use crate::hyper_edge::services::sparse::matrix::SparseMatrix;
+
+/// A weighted pairwise graph over the ids of `index`, stored as a sparse square matrix where entry (a, b) is the
+/// weight of the link a -> b. Undirected adjacencies are symmetric
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedAdjacency {
+    pub index: IdIndex,
+    pub matrix: SparseMatrix,
+    pub directed: bool,
+}
+
+impl WeightedAdjacency {
+    pub fn new(index: IdIndex, matrix: SparseMatrix, directed: bool) -> Self {
+        WeightedAdjacency { index, matrix, directed }
+    }
+
+    /// Returns the weight of the link between two ids, or 0 when they are not linked
+    pub fn weight(&self, from: &str, to: &str) -> f64 {
+        match (self.index.position(from), self.index.position(to)) {
+            (Some(from), Some(to)) => self.matrix.get(from, to),
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the ids linked from `id` with their weights
+    pub fn neighbors(&self, id: &str) -> Vec<(String, f64)> {
+        self.index.position(id).map_or(Vec::new(), |position| {
+            self.matrix.row_entries(position)
+                .map(|(neighbor, weight)| (self.index.id(neighbor).to_string(), weight))
+                .collect()
+        })
+    }
+
+    // number of links (pairs are counted once when undirected)
+    pub fn link_count(&self) -> usize {
+        self.links().count()
+    }
+
+    /// Iterates over the links as (from, to, weight); undirected pairs are given once, with from before to
+    pub fn links(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        self.matrix.entries().filter(move |&(from, to, _)| self.directed || from < to)
+    }
+
+    /// Returns the dense weight matrix, rows and columns in index order
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        let size = self.index.len();
+        let mut dense = vec![vec![0.0; size]; size];
+        for (from, to, weight) in self.matrix.entries() {
+            dense[from][to] = weight;
+        }
+        dense
+    }
+
+    // method to render the weight matrix with the ids as row and column labels
+    pub fn format_matrix(&self) -> String {
+        let width = self.index.ids().iter().map(|id| id.len()).max().unwrap_or(0).max(6);
+        let mut output = format!("{:width$}", "", width = width);
+        for id in self.index.ids() {
+            output.push_str(&format!(" {:>width$}", id, width = width));
+        }
+        output.push('\n');
+
+        for (row, weights) in self.to_dense().iter().enumerate() {
+            output.push_str(&format!("{:width$}", self.index.id(row), width = width));
+            for weight in weights {
+                output.push_str(&format!(" {:>width$}", format_weight(*weight), width = width));
+            }
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Converts the links into pairwise relationships carrying a `weight` property plus the given extra properties
+    pub fn to_relationships(&self, extra: &[Property<String, String>]) -> Vec<Relationship<String, String, Property<String, String>>> {
+        self.links()
+            .map(|(from, to, weight)| {
+                let mut edge_properties = vec![Property { key: "weight".to_string(), value: vec![format_weight(weight)] }];
+                edge_properties.extend(extra.iter().cloned());
+                Relationship {
+                    node_1: self.index.id(from).to_string(),
+                    node_2: self.index.id(to).to_string(),
+                    directed: self.directed,
+                    edge_properties,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Formats a weight rounded to three decimals, without trailing zeros (2 instead of 2.0 or 1.9999, but 0.333 for
+/// a third)
+pub fn format_weight(weight: f64) -> String {
+    let rounded = (weight * 1000.0).round() / 1000.0;
+    // `{}` prints the shortest form, and no sign for a weight rounded to zero
+    format!("{}", if rounded == 0.0 { 0.0 } else { rounded })
+}
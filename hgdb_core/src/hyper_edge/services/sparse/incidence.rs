@@ -0,0 +1,306 @@
+use crate::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::sparse::matrix::SparseMatrix;
+use std::collections::HashMap;
+
+/// Reads the first value of a property as a number
+pub fn property_number(properties: &[Property<String, String>], key: &str) -> Option<f64> {
+    properties.iter()
+        .find(|property| property.key == key)
+        .and_then(|property| property.value.first())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+}
+
+/// Stable mapping between ids and matrix positions: ids keep the position they were first inserted at
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdIndex {
+    ids: Vec<String>,
+    positions: HashMap<String, usize>,
+}
+
+impl IdIndex {
+    pub fn new() -> Self {
+        IdIndex::default()
+    }
+
+    /// Adds an id and returns its position (the existing one if the id is already known)
+    pub fn insert(&mut self, id: &str) -> usize {
+        if let Some(&position) = self.positions.get(id) {
+            return position;
+        }
+        self.ids.push(id.to_string());
+        self.positions.insert(id.to_string(), self.ids.len() - 1);
+        self.ids.len() - 1
+    }
+
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.positions.get(id).copied()
+    }
+
+    pub fn id(&self, position: usize) -> &str {
+        &self.ids[position]
+    }
+
+    pub fn ids(&self) -> &[String] {
+        &self.ids
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// The sparse incidence matrix of a whole hypergraph. Rows are nodes and columns are hyperedges; the matrix is
+/// kept both in CSR layout (`by_node`, the edges of every node) and in CSC layout (`by_edge`, the members of every
+/// edge). Head and tail roles are kept as separate edge-major matrices, so directed algorithms can follow edges
+/// from tail to head. Entries hold the edge weight, which is 1 unless a weight key is given
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncidenceMatrix {
+    pub nodes: IdIndex,
+    pub edges: IdIndex,
+    by_node: SparseMatrix,
+    by_edge: SparseMatrix,
+    heads: SparseMatrix,
+    tails: SparseMatrix,
+    directed: Vec<bool>,
+    traversable: Vec<bool>,
+    node_weights: Vec<f64>,
+    edge_weights: Vec<f64>,
+}
+
+impl IncidenceMatrix {
+    /// Builds the incidence matrix of a hypergraph. Nodes are numbered in the order of `hyper_nodes` followed by
+    /// nodes only referenced by edges, edges in the order of `hyper_edges`. With a weight key, edge and node weights
+    /// are read from the numeric property of that name (missing or non-numeric values count as 1)
+    pub fn from_graph(graph: &HyperGraph<String, String, String>, weight_key: Option<&str>) -> Self {
+        let mut builder = IncidenceBuilder::new(weight_key);
+        for node in &graph.hyper_nodes {
+            builder.add_node(node);
+        }
+        for edge in &graph.hyper_edges {
+            builder.add_edge(edge);
+        }
+        builder.build()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// The node x edge matrix in CSR layout
+    pub fn csr(&self) -> &SparseMatrix {
+        &self.by_node
+    }
+
+    /// The node x edge matrix in CSC layout (stored as its edge x node transpose)
+    pub fn csc(&self) -> &SparseMatrix {
+        &self.by_edge
+    }
+
+    // edge x node matrix of the head memberships
+    pub fn heads(&self) -> &SparseMatrix {
+        &self.heads
+    }
+
+    // edge x node matrix of the tail memberships (empty rows for undirected edges)
+    pub fn tails(&self) -> &SparseMatrix {
+        &self.tails
+    }
+
+    /// Positions of the edges containing node `node`
+    pub fn node_edges(&self, node: usize) -> &[usize] {
+        self.by_node.row(node).0
+    }
+
+    /// Positions of the members of edge `edge`
+    pub fn edge_nodes(&self, edge: usize) -> &[usize] {
+        self.by_edge.row(edge).0
+    }
+
+    pub fn edge_head(&self, edge: usize) -> &[usize] {
+        self.heads.row(edge).0
+    }
+
+    pub fn edge_tail(&self, edge: usize) -> &[usize] {
+        self.tails.row(edge).0
+    }
+
+    pub fn is_directed(&self, edge: usize) -> bool {
+        self.directed[edge]
+    }
+
+    pub fn is_traversable(&self, edge: usize) -> bool {
+        self.traversable[edge]
+    }
+
+    pub fn node_weight(&self, node: usize) -> f64 {
+        self.node_weights[node]
+    }
+
+    pub fn edge_weight(&self, edge: usize) -> f64 {
+        self.edge_weights[edge]
+    }
+
+    pub fn node_weights(&self) -> &[f64] {
+        &self.node_weights
+    }
+
+    pub fn edge_weights(&self) -> &[f64] {
+        &self.edge_weights
+    }
+
+    /// Number of edges containing each node
+    pub fn node_degrees(&self) -> Vec<usize> {
+        self.by_node.row_counts()
+    }
+
+    /// Sum of the weights of the edges containing each node
+    pub fn weighted_node_degrees(&self) -> Vec<f64> {
+        self.by_node.row_sums()
+    }
+
+    /// Number of members of each edge
+    pub fn edge_sizes(&self) -> Vec<usize> {
+        self.by_edge.row_counts()
+    }
+
+    /// Returns the node x edge matrix restricted to the given nodes (rows), in the given order
+    pub fn node_rows(&self, nodes: &[usize]) -> SparseMatrix {
+        self.by_node.row_slice(nodes)
+    }
+
+    /// Returns the node x edge matrix restricted to the given edges (columns), in the given order
+    pub fn edge_columns(&self, edges: &[usize]) -> SparseMatrix {
+        self.by_edge.row_slice(edges).transpose()
+    }
+
+    /// Returns the transposed incidence, i.e. the incidence of the dual hypergraph: edges become nodes and nodes
+    /// become edges, keeping head and tail roles
+    pub fn transpose(&self) -> IncidenceMatrix {
+        // Entries carry the weight of their edge, which is now the weight of the former node
+        let reweight = |matrix: &SparseMatrix| matrix.map_values(|_, node, _| self.node_weights[node]).transpose();
+        let by_edge = reweight(&self.by_edge);
+        let tails = reweight(&self.tails);
+        let directed = (0..tails.rows()).map(|node| !tails.row(node).0.is_empty()).collect();
+
+        IncidenceMatrix {
+            nodes: self.edges.clone(),
+            edges: self.nodes.clone(),
+            by_node: by_edge.transpose(),
+            by_edge,
+            heads: reweight(&self.heads),
+            tails,
+            directed,
+            traversable: vec![true; self.node_count()],
+            node_weights: self.edge_weights.clone(),
+            edge_weights: self.node_weights.clone(),
+        }
+    }
+}
+
+/// Builds an incidence matrix one node or hyperedge at a time, so it can be fed straight from a storage scan
+pub struct IncidenceBuilder {
+    weight_key: Option<String>,
+    nodes: IdIndex,
+    edges: IdIndex,
+    head_entries: Vec<(usize, usize, f64)>,
+    tail_entries: Vec<(usize, usize, f64)>,
+    directed: Vec<bool>,
+    traversable: Vec<bool>,
+    node_weights: Vec<f64>,
+    edge_weights: Vec<f64>,
+}
+
+impl IncidenceBuilder {
+    pub fn new(weight_key: Option<&str>) -> Self {
+        IncidenceBuilder {
+            weight_key: weight_key.map(|key| key.to_string()),
+            nodes: IdIndex::new(),
+            edges: IdIndex::new(),
+            head_entries: Vec::new(),
+            tail_entries: Vec::new(),
+            directed: Vec::new(),
+            traversable: Vec::new(),
+            node_weights: Vec::new(),
+            edge_weights: Vec::new(),
+        }
+    }
+
+    // method to register a node (and its weight) before the edges that use it
+    pub fn add_node(&mut self, node: &HyperNode<String, String, String>) {
+        let position = self.node_position(&node.id);
+        if let Some(weight) = self.weight(&node.properties) {
+            self.node_weights[position] = weight;
+        }
+    }
+
+    /// Adds a hyperedge; an edge id seen before is ignored
+    pub fn add_edge(&mut self, edge: &SimpleHyperEdge<String, String, String>) {
+        if self.edges.position(&edge.id).is_some() {
+            return;
+        }
+        let position = self.edges.insert(&edge.id);
+        let weight = self.weight(&edge.main_properties).unwrap_or(1.0);
+
+        for node in edge.head_hyper_nodes.iter() {
+            let node = self.node_position(node);
+            self.head_entries.push((position, node, weight));
+        }
+        for node in edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()) {
+            let node = self.node_position(node);
+            self.tail_entries.push((position, node, weight));
+        }
+
+        self.directed.push(edge.directed);
+        self.traversable.push(edge.traversable);
+        self.edge_weights.push(weight);
+    }
+
+    pub fn build(self) -> IncidenceMatrix {
+        let (node_count, edge_count) = (self.nodes.len(), self.edges.len());
+
+        // A node listed twice in an edge (or in both its head and tail) is still a single incidence
+        let dedup = |entries: &[(usize, usize, f64)]| {
+            let mut entries = entries.to_vec();
+            entries.sort_by_key(|&(edge, node, _)| (edge, node));
+            entries.dedup_by_key(|&mut (edge, node, _)| (edge, node));
+            entries
+        };
+        let heads = SparseMatrix::from_triplets(edge_count, node_count, dedup(&self.head_entries));
+        let tails = SparseMatrix::from_triplets(edge_count, node_count, dedup(&self.tail_entries));
+        let by_edge = SparseMatrix::from_triplets(edge_count, node_count, dedup(&[self.head_entries, self.tail_entries].concat()));
+
+        IncidenceMatrix {
+            nodes: self.nodes,
+            edges: self.edges,
+            by_node: by_edge.transpose(),
+            by_edge,
+            heads,
+            tails,
+            directed: self.directed,
+            traversable: self.traversable,
+            node_weights: self.node_weights,
+            edge_weights: self.edge_weights,
+        }
+    }
+
+    fn node_position(&mut self, id: &str) -> usize {
+        let position = self.nodes.insert(id);
+        if position == self.node_weights.len() {
+            self.node_weights.push(1.0);
+        }
+        position
+    }
+
+    fn weight(&self, properties: &[Property<String, String>]) -> Option<f64> {
+        self.weight_key.as_deref().and_then(|key| property_number(properties, key))
+    }
+}
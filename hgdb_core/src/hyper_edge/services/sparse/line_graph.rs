@@ -0,0 +1,26 @@
+use crate::hyper_edge::services::sparse::adjacency::WeightedAdjacency;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use crate::hyper_edge::services::sparse::matrix::SparseMatrix;
+
+/// Builds the s-line graph of a hypergraph: one vertex per hyperedge, two hyperedges being adjacent when they share
+/// at least `s` nodes. Links are undirected and weighted with the number of shared nodes
+pub fn s_line_graph(incidence: &IncidenceMatrix, s: usize) -> WeightedAdjacency {
+    WeightedAdjacency::new(incidence.edges.clone(), overlap_graph(incidence.csc(), s), false)
+}
+
+/// Builds the dual s-line graph: one vertex per node, two nodes being adjacent when they share at least `s`
+/// hyperedges. Links are undirected and weighted with the number of shared hyperedges
+pub fn dual_s_line_graph(incidence: &IncidenceMatrix, s: usize) -> WeightedAdjacency {
+    WeightedAdjacency::new(incidence.nodes.clone(), overlap_graph(incidence.csr(), s), false)
+}
+
+// Overlaps of the rows of an incidence layout (M M^T), keeping the off-diagonal entries of at least s. Rows with
+// fewer than s entries cannot reach s with any other row, so they are dropped before multiplying
+fn overlap_graph(rows: &SparseMatrix, s: usize) -> SparseMatrix {
+    let s = s.max(1);
+    let counts = rows.row_counts();
+    let members = rows.retain(|row, _, _| counts[row] >= s).map_values(|_, _, _| 1.0);
+
+    members.multiply(&members.transpose())
+        .retain(|row, col, shared| row != col && shared >= s as f64)
+}
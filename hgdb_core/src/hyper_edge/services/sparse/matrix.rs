@@ -0,0 +1,302 @@
+use rayon::prelude::*;
+use std::ops::Range;
+
+// Fewest rows multiplied per parallel block, so small products are not split up
+const MULTIPLY_BLOCK_ROWS: usize = 256;
+
+/// A sparse matrix in compressed sparse row (CSR) layout: the entries of row `i` are
+/// `indices[indptr[i]..indptr[i + 1]]` (column numbers, ascending) with the matching `values`.
+/// The CSC layout of a matrix is the CSR layout of its transpose
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    rows: usize,
+    cols: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// Creates a matrix without entries
+    pub fn new(rows: usize, cols: usize) -> Self {
+        SparseMatrix { rows, cols, indptr: vec![0; rows + 1], indices: Vec::new(), values: Vec::new() }
+    }
+
+    /// Builds a matrix from (row, column, value) entries. Entries at the same position are summed
+    pub fn from_triplets(rows: usize, cols: usize, mut triplets: Vec<(usize, usize, f64)>) -> Self {
+        triplets.retain(|&(row, col, _)| row < rows && col < cols);
+        triplets.sort_unstable_by_key(|&(row, col, _)| (row, col));
+
+        let mut indptr = vec![0; rows + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+        let mut values: Vec<f64> = Vec::with_capacity(triplets.len());
+        let mut last: Option<(usize, usize)> = None;
+
+        for (row, col, value) in triplets {
+            if last == Some((row, col)) {
+                *values.last_mut().unwrap() += value;
+                continue;
+            }
+            last = Some((row, col));
+            indptr[row + 1] += 1;
+            indices.push(col);
+            values.push(value);
+        }
+        for row in 0..rows {
+            indptr[row + 1] += indptr[row];
+        }
+
+        SparseMatrix { rows, cols, indptr, indices, values }
+    }
+
+    /// Builds a matrix from one list of (column, value) entries per row
+    pub fn from_rows(cols: usize, rows: Vec<Vec<(usize, f64)>>) -> Self {
+        let row_count = rows.len();
+        let triplets = rows.into_iter()
+            .enumerate()
+            .flat_map(|(row, entries)| entries.into_iter().map(move |(col, value)| (row, col, value)))
+            .collect();
+        Self::from_triplets(row_count, cols, triplets)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    // number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns the column numbers and values of row `row`
+    pub fn row(&self, row: usize) -> (&[usize], &[f64]) {
+        let range = self.row_range(row);
+        (&self.indices[range.clone()], &self.values[range])
+    }
+
+    // method to iterate over the (column, value) entries of a row
+    pub fn row_entries(&self, row: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let (indices, values) = self.row(row);
+        indices.iter().copied().zip(values.iter().copied())
+    }
+
+    /// Returns the value at (row, col), or 0 when nothing is stored there
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        if row >= self.rows {
+            return 0.0;
+        }
+        let (indices, values) = self.row(row);
+        indices.binary_search(&col).map_or(0.0, |position| values[position])
+    }
+
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.rows && self.row(row).0.binary_search(&col).is_ok()
+    }
+
+    /// Returns the transposed matrix, i.e. the CSC layout of this matrix
+    pub fn transpose(&self) -> SparseMatrix {
+        let mut indptr = vec![0; self.cols + 1];
+        for &col in &self.indices {
+            indptr[col + 1] += 1;
+        }
+        for col in 0..self.cols {
+            indptr[col + 1] += indptr[col];
+        }
+
+        // Rows are visited in order, so every transposed row ends up sorted
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for row in 0..self.rows {
+            for (col, value) in self.row_entries(row) {
+                indices[next[col]] = row;
+                values[next[col]] = value;
+                next[col] += 1;
+            }
+        }
+
+        SparseMatrix { rows: self.cols, cols: self.rows, indptr, indices, values }
+    }
+
+    /// Returns a matrix made of the given rows, in the given order
+    pub fn row_slice(&self, rows: &[usize]) -> SparseMatrix {
+        let mut indptr = Vec::with_capacity(rows.len() + 1);
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+        indptr.push(0);
+
+        for &row in rows {
+            if row < self.rows {
+                let (row_indices, row_values) = self.row(row);
+                indices.extend_from_slice(row_indices);
+                values.extend_from_slice(row_values);
+            }
+            indptr.push(indices.len());
+        }
+
+        SparseMatrix { rows: rows.len(), cols: self.cols, indptr, indices, values }
+    }
+
+    /// Returns a matrix made of the given columns, in the given order
+    pub fn column_slice(&self, cols: &[usize]) -> SparseMatrix {
+        self.transpose().row_slice(cols).transpose()
+    }
+
+    // method to count the stored entries of every row
+    pub fn row_counts(&self) -> Vec<usize> {
+        (0..self.rows).map(|row| self.indptr[row + 1] - self.indptr[row]).collect()
+    }
+
+    // method to count the stored entries of every column
+    pub fn column_counts(&self) -> Vec<usize> {
+        let mut counts = vec![0; self.cols];
+        for &col in &self.indices {
+            counts[col] += 1;
+        }
+        counts
+    }
+
+    pub fn row_sums(&self) -> Vec<f64> {
+        (0..self.rows).map(|row| self.row(row).1.iter().sum()).collect()
+    }
+
+    pub fn column_sums(&self) -> Vec<f64> {
+        let mut sums = vec![0.0; self.cols];
+        for (&col, &value) in self.indices.iter().zip(&self.values) {
+            sums[col] += value;
+        }
+        sums
+    }
+
+    /// Computes `A * x`
+    pub fn mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        (0..self.rows)
+            .map(|row| self.row_entries(row).map(|(col, value)| value * x[col]).sum())
+            .collect()
+    }
+
+    /// Computes `A^T * x` without building the transpose
+    pub fn transpose_mul_vec(&self, x: &[f64]) -> Vec<f64> {
+        let mut result = vec![0.0; self.cols];
+        for (row, &factor) in x.iter().enumerate().take(self.rows) {
+            for (col, value) in self.row_entries(row) {
+                result[col] += value * factor;
+            }
+        }
+        result
+    }
+
+    /// Returns a copy of the matrix with every stored value replaced by `f(row, col, value)`
+    pub fn map_values<F: Fn(usize, usize, f64) -> f64>(&self, f: F) -> SparseMatrix {
+        let mut values = Vec::with_capacity(self.nnz());
+        for row in 0..self.rows {
+            for (col, value) in self.row_entries(row) {
+                values.push(f(row, col, value));
+            }
+        }
+        SparseMatrix { values, ..self.clone() }
+    }
+
+    /// Returns a copy of the matrix keeping only the entries for which `keep(row, col, value)` holds
+    pub fn retain<F: Fn(usize, usize, f64) -> bool>(&self, keep: F) -> SparseMatrix {
+        let rows = (0..self.rows)
+            .map(|row| self.row_entries(row).filter(|&(col, value)| keep(row, col, value)).collect())
+            .collect();
+        SparseMatrix::from_rows(self.cols, rows)
+    }
+
+    // method to iterate over all stored (row, column, value) entries in row order
+    pub fn entries(&self) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        (0..self.rows).flat_map(move |row| self.row_entries(row).map(move |(col, value)| (row, col, value)))
+    }
+
+    /// Computes the sum `A + B` of two matrices of the same shape
+    pub fn add(&self, other: &SparseMatrix) -> SparseMatrix {
+        let triplets = self.entries().chain(other.entries()).collect();
+        SparseMatrix::from_triplets(self.rows.max(other.rows), self.cols.max(other.cols), triplets)
+    }
+
+    /// Computes the product `A * B` with a sparse accumulator per row. Blocks of rows are multiplied in parallel,
+    /// each with its own accumulator, and joined in row order
+    pub fn multiply(&self, other: &SparseMatrix) -> SparseMatrix {
+        let block = self.rows.div_ceil(rayon::current_num_threads() * 4).max(MULTIPLY_BLOCK_ROWS);
+        let blocks: Vec<(Vec<usize>, Vec<usize>, Vec<f64>)> = (0..self.rows.div_ceil(block)).into_par_iter()
+            .map(|number| self.multiply_rows(other, number * block..((number + 1) * block).min(self.rows)))
+            .collect();
+
+        let mut indptr = Vec::with_capacity(self.rows + 1);
+        let mut indices = Vec::with_capacity(blocks.iter().map(|(_, indices, _)| indices.len()).sum());
+        let mut values = Vec::with_capacity(indices.capacity());
+        indptr.push(0);
+        for (lengths, block_indices, block_values) in blocks {
+            for length in lengths {
+                indptr.push(indptr[indptr.len() - 1] + length);
+            }
+            indices.extend(block_indices);
+            values.extend(block_values);
+        }
+
+        SparseMatrix { rows: self.rows, cols: other.cols, indptr, indices, values }
+    }
+
+    // The rows of `A * B` in `rows`, as (row lengths, columns, values)
+    fn multiply_rows(&self, other: &SparseMatrix, rows: Range<usize>) -> (Vec<usize>, Vec<usize>, Vec<f64>) {
+        let mut accumulator = vec![0.0; other.cols];
+        let mut marked = vec![false; other.cols];
+        let mut touched: Vec<usize> = Vec::new();
+        let mut lengths = Vec::with_capacity(rows.len());
+        let mut indices = Vec::new();
+        let mut values = Vec::new();
+
+        for row in rows {
+            for (middle, left) in self.row_entries(row) {
+                if middle >= other.rows {
+                    continue;
+                }
+                for (col, right) in other.row_entries(middle) {
+                    if !marked[col] {
+                        marked[col] = true;
+                        touched.push(col);
+                    }
+                    accumulator[col] += left * right;
+                }
+            }
+
+            touched.sort_unstable();
+            for &col in &touched {
+                indices.push(col);
+                values.push(accumulator[col]);
+                accumulator[col] = 0.0;
+                marked[col] = false;
+            }
+            lengths.push(touched.len());
+            touched.clear();
+        }
+
+        (lengths, indices, values)
+    }
+
+    /// Returns a copy of the matrix without its diagonal entries
+    pub fn without_diagonal(&self) -> SparseMatrix {
+        self.retain(|row, col, _| row != col)
+    }
+
+    /// Returns the dense form of the matrix, with `true` wherever an entry is stored
+    pub fn to_dense(&self) -> Vec<Vec<bool>> {
+        let mut dense = vec![vec![false; self.cols]; self.rows];
+        for (row, dense_row) in dense.iter_mut().enumerate() {
+            for &col in self.row(row).0 {
+                dense_row[col] = true;
+            }
+        }
+        dense
+    }
+
+    fn row_range(&self, row: usize) -> Range<usize> {
+        self.indptr[row]..self.indptr[row + 1]
+    }
+}
@@ -0,0 +1,4 @@
+pub mod matrix;
+pub mod incidence;
+pub mod adjacency;
+pub mod line_graph;
@@ -0,0 +1,179 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+
+/// A vertex of the star expansion: either a node or a hyperedge of the hypergraph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BipartiteVertex<'a> {
+    Node(&'a str),
+    Edge(&'a str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IncidenceRole {
+    Head,
+    Tail,
+}
+
+/// An incidence of the star expansion. Arcs of directed hyperedges are oriented tail node -> edge -> head node;
+/// arcs of undirected hyperedges (whose members are all in the head) can be walked both ways
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IncidenceArc<'a> {
+    pub node: &'a str,
+    pub edge: &'a str,
+    pub role: IncidenceRole,
+    pub directed: bool,
+}
+
+impl<'a> IncidenceArc<'a> {
+    pub fn source(&self) -> BipartiteVertex<'a> {
+        match self.role {
+            IncidenceRole::Tail => BipartiteVertex::Node(self.node),
+            IncidenceRole::Head => BipartiteVertex::Edge(self.edge),
+        }
+    }
+
+    pub fn target(&self) -> BipartiteVertex<'a> {
+        match self.role {
+            IncidenceRole::Tail => BipartiteVertex::Edge(self.edge),
+            IncidenceRole::Head => BipartiteVertex::Node(self.node),
+        }
+    }
+}
+
+/// Returns the labeled incidences of one hyperedge, head members first
+pub fn edge_arcs<'a>(edge: &'a SimpleHyperEdge<String, String, String>) -> impl Iterator<Item = IncidenceArc<'a>> {
+    let arc = move |node: &'a String, role| IncidenceArc { node: node.as_str(), edge: edge.id.as_str(), role, directed: edge.directed };
+    edge.head_hyper_nodes.iter()
+        .map(move |node| arc(node, IncidenceRole::Head))
+        .chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter()).map(move |node| arc(node, IncidenceRole::Tail)))
+}
+
+/// The star expansion (bipartite incidence graph) of a set of hyperedges. It borrows the hyperedges and only keeps
+/// a node -> edge position index, so every vertex and arc it yields points into the original data
+pub struct StarExpansion<'a> {
+    edges: &'a [SimpleHyperEdge<String, String, String>],
+    edge_positions: HashMap<&'a str, usize>,
+    node_edges: HashMap<&'a str, Vec<usize>>,
+    nodes: Vec<&'a str>,
+}
+
+impl<'a> StarExpansion<'a> {
+    pub fn new(edges: &'a [SimpleHyperEdge<String, String, String>]) -> Self {
+        let mut edge_positions = HashMap::new();
+        let mut node_edges: HashMap<&'a str, Vec<usize>> = HashMap::new();
+        let mut nodes = Vec::new();
+
+        for (position, edge) in edges.iter().enumerate() {
+            edge_positions.entry(edge.id.as_str()).or_insert(position);
+            for arc in edge_arcs(edge) {
+                let positions = node_edges.entry(arc.node).or_insert_with(|| {
+                    nodes.push(arc.node);
+                    Vec::new()
+                });
+                if positions.last() != Some(&position) {
+                    positions.push(position);
+                }
+            }
+        }
+
+        StarExpansion { edges, edge_positions, node_edges, nodes }
+    }
+
+    pub fn from_graph(graph: &'a HyperGraph<String, String, String>) -> Self {
+        Self::new(&graph.hyper_edges)
+    }
+
+    /// Node vertices in order of first appearance
+    pub fn node_vertices(&self) -> impl Iterator<Item = BipartiteVertex<'a>> + '_ {
+        self.nodes.iter().map(|node| BipartiteVertex::Node(node))
+    }
+
+    pub fn edge_vertices(&self) -> impl Iterator<Item = BipartiteVertex<'a>> + '_ {
+        self.edges.iter().map(|edge| BipartiteVertex::Edge(edge.id.as_str()))
+    }
+
+    // method to iterate over both sides, nodes first
+    pub fn vertices(&self) -> impl Iterator<Item = BipartiteVertex<'a>> + '_ {
+        self.node_vertices().chain(self.edge_vertices())
+    }
+
+    pub fn arcs(&self) -> impl Iterator<Item = IncidenceArc<'a>> + 'a {
+        self.edges.iter().flat_map(edge_arcs)
+    }
+
+    /// Returns the incidences of a vertex: the members of an edge vertex or the edges of a node vertex
+    pub fn arcs_of(&self, vertex: BipartiteVertex<'_>) -> Vec<IncidenceArc<'a>> {
+        match vertex {
+            BipartiteVertex::Edge(id) => self.edge_positions.get(id)
+                .map_or(Vec::new(), |&position| edge_arcs(&self.edges[position]).collect()),
+            BipartiteVertex::Node(id) => self.node_edges.get(id).map_or(Vec::new(), |positions| {
+                positions.iter()
+                    .flat_map(|&position| edge_arcs(&self.edges[position]))
+                    .filter(|arc| arc.node == id)
+                    .collect()
+            }),
+        }
+    }
+
+    /// Vertices sharing an incidence with `vertex`, whatever the orientation
+    pub fn neighbors(&self, vertex: BipartiteVertex<'_>) -> Vec<BipartiteVertex<'a>> {
+        let mut seen = HashSet::new();
+        self.arcs_of(vertex).into_iter()
+            .map(|arc| match vertex {
+                BipartiteVertex::Edge(_) => BipartiteVertex::Node(arc.node),
+                BipartiteVertex::Node(_) => BipartiteVertex::Edge(arc.edge),
+            })
+            .filter(|neighbor| seen.insert(*neighbor))
+            .collect()
+    }
+
+    /// Vertices reachable from `vertex` in one step: directed arcs only from source to target, undirected arcs
+    /// both ways
+    pub fn out_neighbors(&self, vertex: BipartiteVertex<'_>) -> Vec<BipartiteVertex<'a>> {
+        let mut seen = HashSet::new();
+        self.arcs_of(vertex).into_iter()
+            .filter_map(|arc| {
+                let (source, target) = (arc.source(), arc.target());
+                if !arc.directed {
+                    Some(if source == vertex { target } else { source })
+                } else if source == vertex {
+                    Some(target)
+                } else {
+                    None
+                }
+            })
+            .filter(|neighbor| seen.insert(*neighbor))
+            .collect()
+    }
+
+    pub fn degree(&self, vertex: BipartiteVertex<'_>) -> usize {
+        self.arcs_of(vertex).len()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// Streams the star expansion of the stored hyperedges
+pub struct StarExpansionService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> StarExpansionService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        StarExpansionService { repository }
+    }
+
+    /// Visits every labeled incidence of the repository one hyperedge at a time, without loading all hyperedges
+    pub fn for_each_arc<F: FnMut(IncidenceArc<'_>)>(&self, mut visit: F) -> Result<(), Box<dyn Error>> {
+        self.repository.scan(|edge| edge_arcs(&edge).for_each(&mut visit))
+    }
+}
@@ -0,0 +1,157 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use crate::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use crate::hyper_edge::services::h_graph_service::{edge_members, set_property};
+use std::collections::HashSet;
+use std::error::Error;
+use std::io::Write;
+
+// Provenance: the graph a sub-hypergraph was cut from, how, and the source id of every node and hyperedge
+pub const SUBGRAPH_OF_KEY: &str = "subgraph_of";
+pub const SUBGRAPH_OPERATION_KEY: &str = "subgraph.operation";
+pub const SOURCE_ID_KEY: &str = "source.id";
+
+type Graph = HyperGraph<String, String, String>;
+type Edge = SimpleHyperEdge<String, String, String>;
+
+/// Selects the hyperedges with one of the given ids
+pub fn with_ids<'p>(ids: &'p [&'p str]) -> impl Fn(&Edge) -> bool + 'p {
+    move |edge| ids.contains(&edge.id.as_str())
+}
+
+/// Selects the hyperedges whose `main_properties` hold `value` under `key`
+pub fn property_equals<'p>(key: &'p str, value: &'p str) -> impl Fn(&Edge) -> bool + 'p {
+    move |edge| edge.main_properties.iter().any(|property| property.key == key && property.value.iter().any(|v| v == value))
+}
+
+/// Selects the hyperedges with at least one member in the given node set
+pub fn touching<'p>(nodes: &'p [&'p str]) -> impl Fn(&Edge) -> bool + 'p {
+    move |edge| edge_members(edge).iter().any(|node| nodes.contains(&node.as_str()))
+}
+
+/// Selects the hyperedges with at least `k` distinct members
+pub fn min_size(k: usize) -> impl Fn(&Edge) -> bool {
+    move |edge| edge_members(edge).len() >= k
+}
+
+/// The sub-hypergraph induced by a node set: every hyperedge restricted to its members in the set (head and tail
+/// roles kept), dropping hyperedges left without members, together with the nodes of the set
+pub fn induced(graph: &Graph, nodes: &[&str]) -> Graph {
+    let selected: HashSet<&str> = nodes.iter().copied().collect();
+    let keep = |list: &[String]| -> Vec<String> { list.iter().filter(|node| selected.contains(node.as_str())).cloned().collect() };
+
+    let hyper_edges = graph.hyper_edges.iter()
+        .filter(|edge| edge_members(edge).iter().any(|node| selected.contains(node.as_str())))
+        .map(|edge| SimpleHyperEdge {
+            head_hyper_nodes: Box::new(keep(&edge.head_hyper_nodes)),
+            tail_hyper_nodes: edge.tail_hyper_nodes.as_ref().map(|tail| Box::new(keep(tail))),
+            ..edge.clone()
+        })
+        .collect();
+
+    let mut hyper_nodes: Vec<HyperNode<String, String, String>> = graph.hyper_nodes.iter().filter(|node| selected.contains(node.id.as_str())).cloned().collect();
+    let listed: HashSet<String> = hyper_nodes.iter().map(|node| node.id.clone()).collect();
+    let referenced: HashSet<String> = graph.hyper_edges.iter().flat_map(edge_members).collect();
+    for node in nodes.iter().filter(|node| referenced.contains(**node) && !listed.contains(**node)) {
+        hyper_nodes.push(HyperNode { id: node.to_string(), properties: Vec::new() });
+    }
+
+    with_provenance(graph, hyper_nodes, hyper_edges, "induced")
+}
+
+/// The partial sub-hypergraph of the hyperedges accepted by `predicate`, kept whole, together with their nodes
+pub fn partial<F: Fn(&Edge) -> bool>(graph: &Graph, predicate: F) -> Graph {
+    let hyper_edges: Vec<Edge> = graph.hyper_edges.iter().filter(|edge| predicate(edge)).cloned().collect();
+    let referenced: HashSet<String> = hyper_edges.iter().flat_map(edge_members).collect();
+
+    let mut hyper_nodes: Vec<HyperNode<String, String, String>> = graph.hyper_nodes.iter().filter(|node| referenced.contains(&node.id)).cloned().collect();
+    let listed: HashSet<String> = hyper_nodes.iter().map(|node| node.id.clone()).collect();
+    let mut missing: Vec<String> = referenced.into_iter().filter(|node| !listed.contains(node)).collect();
+    missing.sort();
+    hyper_nodes.extend(missing.into_iter().map(|node| HyperNode { id: node, properties: Vec::new() }));
+
+    with_provenance(graph, hyper_nodes, hyper_edges, "partial")
+}
+
+// Names the sub-hypergraph after its source and records the source id of every element
+fn with_provenance(graph: &Graph, mut hyper_nodes: Vec<HyperNode<String, String, String>>, mut hyper_edges: Vec<Edge>, operation: &str) -> Graph {
+    for node in hyper_nodes.iter_mut() {
+        set_property(&mut node.properties, SOURCE_ID_KEY, node.id.clone());
+    }
+    for edge in hyper_edges.iter_mut() {
+        set_property(&mut edge.main_properties, SOURCE_ID_KEY, edge.id.clone());
+    }
+
+    let mut properties = graph.properties.clone();
+    set_property(&mut properties, SUBGRAPH_OF_KEY, graph.id.clone());
+    set_property(&mut properties, SUBGRAPH_OPERATION_KEY, operation.to_string());
+    HyperGraph { id: graph.id.clone(), name: graph.name.clone(), properties, hyper_nodes, hyper_edges }
+}
+
+/// Writes the hyperedges of a graph as the JSON export read by the dashboard scripts, `{"<key>": [edges...]}`,
+/// one hyperedge at a time. Returns the number of hyperedges written
+pub fn export_json<W: Write>(graph: &Graph, key: &str, writer: &mut W) -> Result<usize, Box<dyn Error>> {
+    write!(writer, "{{{}: [", serde_json::to_string(key)?)?;
+    for (position, edge) in graph.hyper_edges.iter().enumerate() {
+        if position > 0 {
+            write!(writer, ",")?;
+        }
+        write!(writer, "\n  ")?;
+        serde_json::to_writer(&mut *writer, edge)?;
+    }
+    writeln!(writer, "\n]}}")?;
+    writer.flush()?;
+    Ok(graph.hyper_edges.len())
+}
+
+/// Cuts sub-hypergraphs out of the named hypergraphs
+pub struct SubHyperGraphService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> SubHyperGraphService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        SubHyperGraphService { repository }
+    }
+
+    pub fn induced(&self, key: &str, nodes: &[&str]) -> Result<Graph, Box<dyn Error>> {
+        self.cut(key, |graph| induced(graph, nodes))
+    }
+
+    pub fn partial<F: Fn(&Edge) -> bool>(&self, key: &str, predicate: F) -> Result<Graph, Box<dyn Error>> {
+        self.cut(key, |graph| partial(graph, predicate))
+    }
+
+    /// The partial sub-hypergraph of the hyperedges with at least `k` distinct members
+    pub fn min_size(&self, key: &str, k: usize) -> Result<Graph, Box<dyn Error>> {
+        let mut sub = self.cut(key, |graph| partial(graph, min_size(k)))?;
+        set_property(&mut sub.properties, SUBGRAPH_OPERATION_KEY, format!("min_size {}", k));
+        Ok(sub)
+    }
+
+    /// Stores a sub-hypergraph as the new named hypergraph `new_key`
+    pub fn save_as(&self, sub: &Graph, new_key: &str) -> Result<(), Box<dyn Error>> {
+        let mut graph = sub.clone();
+        graph.id = new_key.to_string();
+        graph.name = new_key.to_string();
+        self.repository.create(new_key, &graph)?;
+        println!("✅ Saved sub-hypergraph '{}' ({} nodes, {} hyperedges)", new_key, graph.hyper_nodes.len(), graph.hyper_edges.len());
+        Ok(())
+    }
+
+    /// Streams a sub-hypergraph to a JSON export under `key`
+    pub fn export<W: Write>(&self, sub: &Graph, key: &str, writer: &mut W) -> Result<usize, Box<dyn Error>> {
+        let written = export_json(sub, key, writer)?;
+        println!("✅ Exported {} hyperedges as '{}'", written, key);
+        Ok(written)
+    }
+
+    // Applies an operation to the stored graph `key`, recording the key as the source
+    fn cut<F: FnOnce(&Graph) -> Graph>(&self, key: &str, operation: F) -> Result<Graph, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        let mut sub = operation(&graph);
+        set_property(&mut sub.properties, SUBGRAPH_OF_KEY, key.to_string());
+        Ok(sub)
+    }
+}
@@ -0,0 +1,244 @@
+use crate::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use crate::hyper_edge::services::sparse::incidence::{IdIndex, IncidenceMatrix};
+use std::error::Error;
+
+/// How a hitting set or set cover is searched for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolverMode {
+    /// Best ratio of newly hit sets to weight first; within a factor H(max degree) of the optimum
+    Greedy,
+    /// Branch and bound seeded with the greedy solution; exponential in the worst case
+    Exact,
+}
+
+/// The chosen ids (nodes for a hitting set, hyperedges for a set cover) and their total weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    pub ids: Vec<String>,
+    pub cost: f64,
+}
+
+/// Transversals of the hyperedges of an incidence: sets of nodes meeting every hyperedge (head and tail members
+/// alike). Weighted problems use the node weights of the incidence for hitting sets and its edge weights for set
+/// covers, which are hitting sets of the dual
+pub struct Transversals<'g> {
+    incidence: &'g IncidenceMatrix,
+}
+
+impl<'g> Transversals<'g> {
+    pub fn new(incidence: &'g IncidenceMatrix) -> Self {
+        Transversals { incidence }
+    }
+
+    /// Enumerates the minimal transversals with Berge's algorithm, adding one hyperedge at a time and keeping only
+    /// the minimal sets. With `max_size`, only the minimal transversals of at most that many nodes are returned,
+    /// and larger partial sets are dropped as soon as they appear. Nodes are listed in index order
+    pub fn minimal_transversals(&self, max_size: Option<usize>) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+        let sets = self.problem(self.incidence, false)?.sets;
+        let mut transversals: Vec<Vec<usize>> = vec![Vec::new()];
+
+        for set in &sets {
+            let mut next: Vec<Vec<usize>> = Vec::new();
+            for transversal in &transversals {
+                if set.iter().any(|node| transversal.binary_search(node).is_ok()) {
+                    next.push(transversal.clone());
+                    continue;
+                }
+                if max_size.is_some_and(|max_size| transversal.len() >= max_size) {
+                    continue;
+                }
+                for &node in set {
+                    let mut extended = transversal.clone();
+                    let position = extended.binary_search(&node).unwrap_err();
+                    extended.insert(position, node);
+                    next.push(extended);
+                }
+            }
+            transversals = minimize(next);
+        }
+
+        transversals.sort();
+        Ok(transversals.into_iter().map(|transversal| ids(&self.incidence.nodes, &transversal)).collect())
+    }
+
+    /// A set of nodes meeting every hyperedge, of small (greedy) or minimum (exact) total node weight
+    pub fn hitting_set(&self, mode: SolverMode) -> Result<Selection, Box<dyn Error>> {
+        let problem = self.problem(self.incidence, false)?;
+        let chosen = problem.solve(mode);
+        Ok(problem.selection(&self.incidence.nodes, chosen))
+    }
+
+    /// A set of hyperedges covering every node that lies in some hyperedge, of small (greedy) or minimum (exact)
+    /// total edge weight
+    pub fn set_cover(&self, mode: SolverMode) -> Result<Selection, Box<dyn Error>> {
+        let dual = self.incidence.transpose();
+        // Isolated nodes become empty sets of the dual: they are not part of the universe to cover
+        let problem = self.problem(&dual, true)?;
+        let chosen = problem.solve(mode);
+        Ok(problem.selection(&self.incidence.edges, chosen))
+    }
+
+    // The sets to hit (the member lists of the hyperedges) and the weight of every element. Empty sets either
+    // make the problem infeasible or, with `skip_empty`, are left out
+    fn problem(&self, incidence: &IncidenceMatrix, skip_empty: bool) -> Result<HittingProblem, Box<dyn Error>> {
+        if let Some(element) = (0..incidence.node_count()).find(|&element| incidence.node_weight(element) <= 0.0) {
+            return Err(format!("'{}' has a non-positive weight", incidence.nodes.id(element)).into());
+        }
+
+        let mut sets: Vec<Vec<usize>> = Vec::new();
+        for edge in 0..incidence.edge_count() {
+            match incidence.edge_nodes(edge) {
+                [] if skip_empty => {}
+                [] => return Err(format!("Hyperedge '{}' has no members and cannot be hit", incidence.edges.id(edge)).into()),
+                members => sets.push(members.to_vec()),
+            }
+        }
+
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); incidence.node_count()];
+        for (set, elements) in sets.iter().enumerate() {
+            for &element in elements {
+                members[element].push(set);
+            }
+        }
+        Ok(HittingProblem { sets, members, weights: incidence.node_weights().to_vec() })
+    }
+}
+
+// A weighted hitting set instance: `sets` lists the elements of every set, `members` the sets of every element
+struct HittingProblem {
+    sets: Vec<Vec<usize>>,
+    members: Vec<Vec<usize>>,
+    weights: Vec<f64>,
+}
+
+impl HittingProblem {
+    fn solve(&self, mode: SolverMode) -> Vec<usize> {
+        let greedy = self.greedy();
+        match mode {
+            SolverMode::Greedy => greedy,
+            SolverMode::Exact => {
+                let mut best = (self.cost(&greedy), greedy);
+                let mut hits = vec![0; self.sets.len()];
+                self.branch(&mut Vec::new(), 0.0, &mut hits, &mut best);
+                best.1
+            }
+        }
+    }
+
+    fn greedy(&self) -> Vec<usize> {
+        let mut hit = vec![false; self.sets.len()];
+        let mut remaining = self.sets.len();
+        let mut chosen = Vec::new();
+
+        while remaining > 0 {
+            let best = (0..self.members.len())
+                .map(|element| (element, self.members[element].iter().filter(|&&set| !hit[set]).count()))
+                .filter(|&(_, gain)| gain > 0)
+                .max_by(|a, b| (a.1 as f64 / self.weights[a.0]).total_cmp(&(b.1 as f64 / self.weights[b.0])).then(b.0.cmp(&a.0)));
+            let Some((element, gain)) = best else {
+                break;
+            };
+            for &set in &self.members[element] {
+                hit[set] = true;
+            }
+            remaining -= gain;
+            chosen.push(element);
+        }
+
+        chosen.sort_unstable();
+        chosen
+    }
+
+    // Branches on the members of the unhit set with the fewest members; `hits` counts the chosen members of
+    // every set
+    fn branch(&self, chosen: &mut Vec<usize>, cost: f64, hits: &mut Vec<usize>, best: &mut (f64, Vec<usize>)) {
+        let unhit = (0..self.sets.len()).filter(|&set| hits[set] == 0).min_by_key(|&set| self.sets[set].len());
+        let Some(set) = unhit else {
+            if cost < best.0 {
+                let mut solution = chosen.clone();
+                solution.sort_unstable();
+                *best = (cost, solution);
+            }
+            return;
+        };
+
+        let mut candidates = self.sets[set].clone();
+        candidates.sort_by(|a, b| self.weights[*a].total_cmp(&self.weights[*b]).then(a.cmp(b)));
+        for element in candidates {
+            let next = cost + self.weights[element];
+            if next >= best.0 {
+                // Candidates are sorted by weight, so the remaining ones cannot do better
+                break;
+            }
+            for &member in &self.members[element] {
+                hits[member] += 1;
+            }
+            chosen.push(element);
+            self.branch(chosen, next, hits, best);
+            chosen.pop();
+            for &member in &self.members[element] {
+                hits[member] -= 1;
+            }
+        }
+    }
+
+    fn cost(&self, chosen: &[usize]) -> f64 {
+        chosen.iter().map(|&element| self.weights[element]).sum()
+    }
+
+    fn selection(&self, index: &IdIndex, chosen: Vec<usize>) -> Selection {
+        Selection { cost: self.cost(&chosen), ids: ids(index, &chosen) }
+    }
+}
+
+// Keeps the sets that contain no other set of the list (sets are sorted and free of duplicates)
+fn minimize(mut sets: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    sets.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    sets.dedup();
+    let mut minimal: Vec<Vec<usize>> = Vec::new();
+    for set in sets {
+        let contains = |smaller: &Vec<usize>| smaller.iter().all(|element| set.binary_search(element).is_ok());
+        if !minimal.iter().any(contains) {
+            minimal.push(set);
+        }
+    }
+    minimal
+}
+
+fn ids(index: &IdIndex, positions: &[usize]) -> Vec<String> {
+    positions.iter().map(|&position| index.id(position).to_string()).collect()
+}
+
+/// Transversal, hitting set and set cover queries over the named hypergraphs
+pub struct TransversalService<'a> {
+    repository: &'a HyperGraphRepository,
+}
+
+impl<'a> TransversalService<'a> {
+    pub fn new(repository: &'a HyperGraphRepository) -> Self {
+        TransversalService { repository }
+    }
+
+    pub fn minimal_transversals(&self, key: &str, max_size: Option<usize>) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+        let incidence = self.incidence(key, None)?;
+        Transversals::new(&incidence).minimal_transversals(max_size)
+    }
+
+    /// Hitting set weighted by the `weight_key` property of the nodes (nodes without it weigh 1)
+    pub fn hitting_set(&self, key: &str, weight_key: Option<&str>, mode: SolverMode) -> Result<Selection, Box<dyn Error>> {
+        let incidence = self.incidence(key, weight_key)?;
+        Transversals::new(&incidence).hitting_set(mode)
+    }
+
+    /// Set cover weighted by the `weight_key` entry of the hyperedges' `main_properties` (edges without it weigh 1)
+    pub fn set_cover(&self, key: &str, weight_key: Option<&str>, mode: SolverMode) -> Result<Selection, Box<dyn Error>> {
+        let incidence = self.incidence(key, weight_key)?;
+        Transversals::new(&incidence).set_cover(mode)
+    }
+
+    fn incidence(&self, key: &str, weight_key: Option<&str>) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        let graph = self.repository.get_by_key(key)?
+            .ok_or_else(|| format!("Hypergraph '{}' not found", key))?;
+        Ok(IncidenceMatrix::from_graph(&graph, weight_key))
+    }
+}
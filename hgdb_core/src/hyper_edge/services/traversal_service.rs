@@ -0,0 +1,214 @@
+use crate::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use crate::hyper_edge::repository::light_h_edge_repository::LightHyperEdgeRepository;
+use crate::hyper_edge::entity::structure::structure::Traverse;
+use crate::hyper_edge::services::h_graph_service::HyperPath;
+use crate::hyper_edge::services::incidence_service::IncidenceService;
+use crate::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraversalOrder {
+    BreadthFirst,
+    DepthFirst,
+}
+
+/// What the visitor wants the traversal to do after seeing a node
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VisitControl {
+    Continue,
+    /// Keep the node but do not expand it
+    Skip,
+    Stop,
+}
+
+/// A node reached by a traversal, with the hyperedge and node it was reached from
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraversalStep<'g> {
+    pub node: &'g str,
+    pub depth: usize,
+    pub via_edge: Option<&'g str>,
+    pub parent: Option<&'g str>,
+}
+
+/// BFS and DFS over the incidence of a hypergraph. Only traversable hyperedges are crossed; an undirected
+/// hyperedge leads from any member to every other member, a directed one only from its tail nodes to its head
+/// nodes
+pub struct TraversalEngine<'g> {
+    incidence: &'g IncidenceMatrix,
+}
+
+impl<'g> TraversalEngine<'g> {
+    pub fn new(incidence: &'g IncidenceMatrix) -> Self {
+        TraversalEngine { incidence }
+    }
+
+    /// Returns the (edge, node) positions reachable from `node` in one step
+    pub fn successors(&self, node: usize) -> Vec<(usize, usize)> {
+        let mut successors = Vec::new();
+        for &edge in self.incidence.node_edges(node) {
+            if !self.incidence.is_traversable(edge) {
+                continue;
+            }
+            let targets = if self.incidence.is_directed(edge) {
+                if !self.incidence.edge_tail(edge).contains(&node) {
+                    continue;
+                }
+                self.incidence.edge_head(edge)
+            } else {
+                self.incidence.edge_nodes(edge)
+            };
+            successors.extend(targets.iter().filter(|&&next| next != node).map(|&next| (edge, next)));
+        }
+        successors
+    }
+
+    /// Visits the nodes reachable from `start` (which is visited first, at depth 0) and returns the visited steps
+    /// in visit order. Nodes deeper than `max_depth` are not visited. Unknown start nodes give no steps
+    pub fn traverse<F>(&self, start: &str, order: TraversalOrder, max_depth: Option<usize>, mut visitor: F) -> Vec<TraversalStep<'g>>
+    where
+        F: FnMut(&TraversalStep<'g>) -> VisitControl,
+    {
+        let Some(start) = self.incidence.nodes.position(start) else {
+            return Vec::new();
+        };
+
+        let mut visited = vec![false; self.incidence.node_count()];
+        let mut steps = Vec::new();
+        // (node, depth, edge, parent) entries waiting to be visited
+        let mut pending: VecDeque<(usize, usize, Option<usize>, Option<usize>)> = VecDeque::from([(start, 0, None, None)]);
+        if order == TraversalOrder::BreadthFirst {
+            visited[start] = true;
+        }
+
+        while let Some((node, depth, edge, parent)) = match order {
+            TraversalOrder::BreadthFirst => pending.pop_front(),
+            TraversalOrder::DepthFirst => pending.pop_back(),
+        } {
+            if order == TraversalOrder::DepthFirst {
+                if visited[node] {
+                    continue;
+                }
+                visited[node] = true;
+            }
+
+            let step = TraversalStep {
+                node: self.incidence.nodes.id(node),
+                depth,
+                via_edge: edge.map(|edge| self.incidence.edges.id(edge)),
+                parent: parent.map(|parent| self.incidence.nodes.id(parent)),
+            };
+            let control = visitor(&step);
+            steps.push(step);
+
+            match control {
+                VisitControl::Stop => break,
+                VisitControl::Skip => continue,
+                VisitControl::Continue => {}
+            }
+            if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+                continue;
+            }
+
+            let successors = self.successors(node);
+            match order {
+                TraversalOrder::BreadthFirst => {
+                    for (next_edge, next) in successors {
+                        if !visited[next] {
+                            visited[next] = true;
+                            pending.push_back((next, depth + 1, Some(next_edge), Some(node)));
+                        }
+                    }
+                }
+                // Pushed in reverse so the first successor is explored first
+                TraversalOrder::DepthFirst => {
+                    for (next_edge, next) in successors.into_iter().rev() {
+                        if !visited[next] {
+                            pending.push_back((next, depth + 1, Some(next_edge), Some(node)));
+                        }
+                    }
+                }
+            }
+        }
+
+        steps
+    }
+
+    /// Finds a path with the fewest hyperedges from `from` to `to` that respects the traversal rules
+    pub fn find_path(&self, from: &str, to: &str, max_depth: Option<usize>) -> Option<HyperPath> {
+        let steps = self.traverse(from, TraversalOrder::BreadthFirst, max_depth, |step| {
+            if step.node == to { VisitControl::Stop } else { VisitControl::Continue }
+        });
+        let reached: HashMap<&str, &TraversalStep> = steps.iter().map(|step| (step.node, step)).collect();
+        let mut current = *reached.get(to)?;
+
+        let mut path = HyperPath { nodes: vec![to.to_string()], edges: Vec::new() };
+        while let (Some(parent), Some(edge)) = (current.parent, current.via_edge) {
+            path.nodes.push(parent.to_string());
+            path.edges.push(edge.to_string());
+            current = reached[parent];
+        }
+        path.nodes.reverse();
+        path.edges.reverse();
+
+        Some(path)
+    }
+}
+
+/// Runs traversals over the stored hyperedges
+pub struct TraversalService<'a> {
+    repository: &'a SimpleHyperEdgeRepository,
+}
+
+impl<'a> TraversalService<'a> {
+    pub fn new(repository: &'a SimpleHyperEdgeRepository) -> Self {
+        TraversalService { repository }
+    }
+
+    // method to build the incidence the traversals run on
+    pub fn incidence(&self) -> Result<IncidenceMatrix, Box<dyn Error>> {
+        IncidenceService::new(self.repository).build(None)
+    }
+
+    /// Breadth-first traversal from `start`, returning the visited node ids in visit order
+    pub fn bfs(&self, start: &str, max_depth: Option<usize>) -> Result<Vec<String>, Box<dyn Error>> {
+        self.visit(start, TraversalOrder::BreadthFirst, max_depth)
+    }
+
+    /// Depth-first traversal from `start`, returning the visited node ids in visit order
+    pub fn dfs(&self, start: &str, max_depth: Option<usize>) -> Result<Vec<String>, Box<dyn Error>> {
+        self.visit(start, TraversalOrder::DepthFirst, max_depth)
+    }
+
+    pub fn find_path(&self, from: &str, to: &str) -> Result<Option<HyperPath>, Box<dyn Error>> {
+        let incidence = self.incidence()?;
+        Ok(TraversalEngine::new(&incidence).find_path(from, to, None))
+    }
+
+    /// Computes a path from `from` to `to` and writes its node ids into the `traverse` path of the LightHyperEdge
+    /// stored under `light_key`. Returns None (and leaves the edge untouched) when `to` cannot be reached
+    pub fn save_traverse(&self, lights: &LightHyperEdgeRepository, light_key: &str, from: &str, to: &str) -> Result<Option<HyperPath>, Box<dyn Error>> {
+        let mut light_edge = lights.get_by_key(light_key)?
+            .ok_or_else(|| format!("LightHyperEdge '{}' not found", light_key))?;
+
+        let path = match self.find_path(from, to)? {
+            Some(path) => path,
+            None => {
+                println!("⚠️ No traversable path from '{}' to '{}'", from, to);
+                return Ok(None);
+            }
+        };
+
+        light_edge.traverse = Traverse { path: path.nodes.clone() };
+        lights.update(light_key, &light_edge)?;
+        println!("✅ Saved traverse path {:?} on '{}'", path.nodes, light_key);
+
+        Ok(Some(path))
+    }
+
+    fn visit(&self, start: &str, order: TraversalOrder, max_depth: Option<usize>) -> Result<Vec<String>, Box<dyn Error>> {
+        let incidence = self.incidence()?;
+        let steps = TraversalEngine::new(&incidence).traverse(start, order, max_depth, |_| VisitControl::Continue);
+        Ok(steps.into_iter().map(|step| step.node.to_string()).collect())
+    }
+}
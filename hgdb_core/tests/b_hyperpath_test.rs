@@ -0,0 +1,118 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::b_hyperpath_service::{BConnectivity, BHyperPathService, BPathCost};
+use hgdb_core::hyper_edge::services::generator_service::{Generator, Model};
+use hgdb_core::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use common::{edge, strings, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/b-hyperpath"; // RocksDB path
+
+    fn reaction(id: &str, tail: &[&str], head: &[&str], cost: &str) -> SimpleHyperEdge<String, String, String> {
+        edge(id, head, Some(tail)).with_name(&id.replace("test_edge_", "r")).with_property("cost", &[cost])
+    }
+
+    #[test]
+    fn test_b_connectivity() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let undirected = SimpleHyperEdge {
+            id: "test_edge_7".to_string(),
+            name: "u7".to_string(),
+            main_properties: Vec::new(),
+            traversable: true,
+            directed: false,
+            head_hyper_nodes: Box::new(strings(&["a", "z"])),
+            tail_hyper_nodes: None,
+        };
+        for edge in [
+            reaction("test_edge_1", &["a"], &["b"], "1"),
+            reaction("test_edge_2", &["a"], &["c"], "2"),
+            reaction("test_edge_3", &["b", "c"], &["d"], "1"),
+            reaction("test_edge_4", &["d"], &["e"], "1"),
+            reaction("test_edge_5", &["x"], &["d"], "10"),
+            reaction("test_edge_6", &["d"], &["a"], "1"),
+            undirected,
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = BHyperPathService::new(&repository);
+
+        // A head is only reached once its whole tail is reached
+        assert_eq!(service.reachable(&["a"])?, strings(&["b", "a", "c", "d", "e"]), "❌ Closure from a mismatch");
+        assert_eq!(service.reachable(&["b"])?, strings(&["b"]), "❌ d needs both b and c");
+        assert_eq!(service.reachable(&["b", "c"])?.len(), 5);
+        assert!(!service.reachable(&["a"])?.contains(&"z".to_string()), "❌ Undirected edges are not B-arcs");
+
+        // Mutual B-reachability
+        let components = service.components()?;
+        assert!(components.contains(&strings(&["a", "d"])), "❌ a and d reach each other: {:?}", components);
+        assert!(components.contains(&strings(&["b"])));
+        assert_eq!(components.iter().map(|component| component.len()).sum::<usize>(), 7, "❌ Every node belongs to one component");
+
+        // Minimum-cost hyperpaths with weights from the `cost` property
+        let sum = service.shortest_hyperpath(&["a"], "e", "cost", BPathCost::Sum)?.expect("❌ e should be B-reachable");
+        assert_eq!(sum.cost, 5.0, "❌ Traversal cost should be 1 + (1 + (1 + 2))");
+        assert_eq!(sum.edges, strings(&["test_edge_1", "test_edge_2", "test_edge_3", "test_edge_4"]));
+        assert_eq!(sum.nodes, strings(&["b", "a", "c", "d", "e"]));
+
+        let max = service.shortest_hyperpath(&["a"], "e", "cost", BPathCost::Max)?.unwrap();
+        assert_eq!(max.cost, 4.0, "❌ Rank cost should be 1 + (1 + max(1, 2))");
+
+        // A costlier alternative is not taken, a cheaper one is
+        let from_x = service.shortest_hyperpath(&["a", "x"], "d", "cost", BPathCost::Sum)?.unwrap();
+        assert_eq!(from_x.edges.last(), Some(&"test_edge_3".to_string()));
+        let unweighted = service.shortest_hyperpath(&["a", "x"], "d", "missing_key", BPathCost::Sum)?.unwrap();
+        assert_eq!((unweighted.cost, unweighted.edges.clone()), (1.0, strings(&["test_edge_5"])), "❌ Missing weights count as 1");
+
+        assert!(service.shortest_hyperpath(&["b"], "e", "cost", BPathCost::Sum)?.is_none());
+        assert!(service.shortest_hyperpath(&["a"], "unknown", "cost", BPathCost::Sum)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_components_match_pairwise_reachability() -> Result<(), Box<dyn Error>> {
+        for (seed, tail_size) in [(1, 1), (2, 1), (3, 2)] {
+            let model = Model::Directed { nodes: 40, edges: 70, head_size: 2, tail_size };
+            let graph = HyperGraph {
+                id: "random".to_string(),
+                name: "random".to_string(),
+                properties: Vec::new(),
+                hyper_nodes: Vec::new(),
+                hyper_edges: Generator::new(&model, seed, "test_edge_")?.collect(),
+            };
+            let incidence = IncidenceMatrix::from_graph(&graph, None);
+            let connectivity = BConnectivity::new(&incidence);
+
+            let ids = incidence.nodes.ids();
+            let reached: Vec<Vec<String>> = ids.iter().map(|id| connectivity.reachable(&[id.as_str()])).collect();
+            let mut expected: Vec<Vec<String>> = Vec::new();
+            for (node, id) in ids.iter().enumerate() {
+                if expected.iter().any(|component| component.contains(id)) {
+                    continue;
+                }
+                expected.push(ids.iter().enumerate()
+                    .filter(|&(other, other_id)| reached[node].contains(other_id) && reached[other].contains(id))
+                    .map(|(_, other_id)| other_id.clone())
+                    .collect());
+            }
+            assert_eq!(connectivity.components(), expected, "❌ Components should follow mutual reachability (seed {})", seed);
+            assert!(tail_size > 1 || expected.iter().any(|component| component.len() > 1), "❌ Single tails should give larger components");
+        }
+        Ok(())
+    }
+}
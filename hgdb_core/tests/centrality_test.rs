@@ -0,0 +1,103 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::centrality_service::{Centrality, CentralityService, CentralityTarget};
+use hgdb_core::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use hgdb_core::hyper_edge::services::generator_service::{Generator, Model};
+use common::undirected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/centrality"; // RocksDB path
+
+    fn graph(name: &str, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        HyperGraph { id: name.to_string(), name: name.to_string(), properties: Vec::new(), hyper_nodes: Vec::new(), hyper_edges: edges }
+    }
+
+    #[test]
+    fn test_centrality_suite() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("hub", &graph("hub", vec![
+            undirected("test_edge_1", &["a", "b", "c"]),
+            undirected("test_edge_2", &["c", "d"]),
+            undirected("test_edge_3", &["d", "e"]),
+            undirected("test_edge_4", &["c", "f"]),
+        ]))?;
+        let service = CentralityService::new(&repository);
+        let incidence = service.incidence("hub", None)?;
+        let centrality = Centrality::new(&incidence);
+
+        let degree = centrality.degree(CentralityTarget::Nodes);
+        assert_eq!(degree.top(2), vec!["c", "d"], "❌ Degree ranking mismatch");
+        assert_eq!(degree.get("c"), Some(3.0));
+        assert_eq!(centrality.degree(CentralityTarget::Edges).top(1), vec!["test_edge_1"]);
+
+        // test_edge_2 is the only bridge between test_edge_3 and the rest: 2 of the 3 pairs not involving it
+        let betweenness = centrality.s_betweenness(CentralityTarget::Edges, 1);
+        assert!((betweenness.get("test_edge_2").unwrap() - 2.0 / 3.0).abs() < 1e-9, "❌ s-betweenness mismatch: {:?}", betweenness);
+        assert_eq!(betweenness.get("test_edge_1"), Some(0.0));
+        assert_eq!(centrality.s_betweenness(CentralityTarget::Nodes, 1).top(1), vec!["c"]);
+
+        for table in [centrality.s_closeness(CentralityTarget::Nodes, 1), centrality.s_harmonic(CentralityTarget::Nodes, 1)] {
+            assert_eq!(table.top(1), vec!["c"], "❌ {} should rank c first", table.measure);
+            assert!(table.scores.windows(2).all(|pair| pair[0].1 >= pair[1].1), "❌ Tables must be sorted");
+        }
+        let harmonic = centrality.s_harmonic(CentralityTarget::Edges, 2);
+        assert!(harmonic.scores.iter().all(|(_, score)| *score == 0.0), "❌ No edges share two nodes");
+
+        let eigenvector = centrality.eigenvector(CentralityTarget::Nodes, 1e-10, 1000)?;
+        assert_eq!(eigenvector.top(1), vec!["c"]);
+        assert!((eigenvector.scores.iter().map(|(_, score)| score).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert_eq!(centrality.nonlinear_eigenvector(CentralityTarget::Nodes, 1e-10, 5000)?.top(1), vec!["c"]);
+        assert!(centrality.eigenvector(CentralityTarget::Nodes, 1e-30, 2).is_err(), "❌ Non-convergence should be reported");
+
+        // On a 3-uniform hypergraph the nonlinear variant solves Benson's H-eigenvector equation:
+        // x1^2 = (x2 x3 + x4 x5) / l and y^2 = x1 y / l, so x1 / y = 2^(1/3)
+        let uniform = IncidenceMatrix::from_graph(&graph("uniform", vec![undirected("test_edge_1", &["1", "2", "3"]), undirected("test_edge_2", &["1", "4", "5"])]), None);
+        let benson = Centrality::new(&uniform).nonlinear_eigenvector(CentralityTarget::Nodes, 1e-12, 10000)?;
+        let ratio = benson.get("1").unwrap() / benson.get("4").unwrap();
+        assert!((ratio - 2f64.powf(1.0 / 3.0)).abs() < 1e-6, "❌ H-eigenvector ratio mismatch: {}", ratio);
+
+        // Scores can be written back as properties
+        service.save_scores("hub", &betweenness, CentralityTarget::Edges)?;
+        service.save_scores("hub", &degree, CentralityTarget::Nodes)?;
+        let stored = repository.get_by_key("hub")?.unwrap();
+        let bridge = stored.hyper_edges.iter().find(|edge| edge.id == "test_edge_2").unwrap();
+        let saved = bridge.main_properties.iter().find(|property| property.key == "centrality.s1_betweenness").unwrap();
+        assert!((saved.value[0].parse::<f64>()? - 2.0 / 3.0).abs() < 1e-9);
+        let c = stored.hyper_nodes.iter().find(|node| node.id == "c").unwrap();
+        assert_eq!(c.properties[0].value, vec!["3".to_string()], "❌ Node degree was not saved");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_million_incidences() -> Result<(), Box<dyn Error>> {
+        // 250k hyperedges of 4 members out of 200k nodes
+        let model = Model::Uniform { nodes: 200_000, edges: 250_000, size: 4 };
+        let large = graph("large", Generator::new(&model, 7, "test_edge_")?.collect());
+        let incidence = IncidenceMatrix::from_graph(&large, None);
+        assert_eq!(incidence.edge_sizes().iter().sum::<usize>(), 1_000_000);
+        let centrality = Centrality::new(&incidence);
+
+        let degree = centrality.degree(CentralityTarget::Nodes);
+        let hub = degree.top(1)[0].to_string();
+        assert_eq!(degree.get(&hub), Some(degree.scores[0].1), "❌ Lookup by id should find the top score");
+        let eigenvector = centrality.eigenvector(CentralityTarget::Nodes, 1e-6, 200)?;
+        assert!((eigenvector.scores.iter().map(|(_, score)| score).sum::<f64>() - 1.0).abs() < 1e-6, "❌ Eigenvector scores should sum to 1");
+        assert!(eigenvector.get(&hub).is_some_and(|score| score > 1.0 / 200_000.0), "❌ The highest degree node should score above average");
+        Ok(())
+    }
+}
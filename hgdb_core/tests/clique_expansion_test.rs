@@ -0,0 +1,85 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::repository::relationship_repository::RelationshipRepository;
+use hgdb_core::hyper_edge::services::clique_expansion_service::{CliqueExpansionService, CliqueWeighting};
+use hgdb_core::hyper_edge::services::sparse::adjacency::format_weight;
+use common::edge;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/clique-expansion"; // RocksDB path
+    const RELATIONSHIP_DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/clique-expansion-relationships";
+
+    #[test]
+    fn test_clique_expansion() -> Result<(), Box<dyn Error>> {
+        for path in [DB_PATH, RELATIONSHIP_DB_PATH] {
+            if let Err(e) = remove_dir_all(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+                }
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        for edge in [
+            edge("test_edge_1", &["v2", "v3"], Some(&["v1"])),
+            edge("test_edge_2", &["v2", "v3", "v4"], None),
+            edge("test_edge_3", &["v4", "v5"], None),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = CliqueExpansionService::new(&repository);
+
+        // Co-occurrence counts
+        let counts = service.expand(CliqueWeighting::Count, None)?;
+        assert_eq!(counts.weight("v2", "v3"), 2.0, "❌ v2 and v3 share two hyperedges");
+        assert_eq!(counts.weight("v3", "v2"), 2.0, "❌ The undirected expansion should be symmetric");
+        assert_eq!(counts.weight("v1", "v2"), 1.0);
+        assert_eq!(counts.weight("v1", "v5"), 0.0);
+        assert_eq!(counts.weight("v2", "v2"), 0.0, "❌ No self links");
+        assert_eq!(counts.link_count(), 6, "❌ Link count mismatch");
+        assert_eq!(counts.neighbors("v5"), vec![("v4".to_string(), 1.0)]);
+
+        // Normalized weights: every member of a 3-node edge gets 1/2 per neighbor
+        let normalized = service.expand(CliqueWeighting::Normalized, None)?;
+        assert_eq!(normalized.weight("v2", "v3"), 1.0);
+        assert_eq!(normalized.weight("v1", "v2"), 0.5);
+        assert_eq!(normalized.weight("v4", "v5"), 1.0);
+        // Stored weights are rounded to three decimals, never leaving a bare point
+        assert_eq!([1.0004, 1.9999, 1.0 / 3.0, 2.5, 2.0, -0.0001].map(format_weight), ["1", "2", "0.333", "2.5", "2", "0"]);
+
+        // Head -> tail expansion
+        let directed = service.expand_directed(CliqueWeighting::Count, None)?;
+        assert!(directed.directed);
+        assert_eq!(directed.weight("v2", "v1"), 1.0, "❌ Head nodes should link to tail nodes");
+        assert_eq!(directed.weight("v1", "v2"), 0.0, "❌ No link from tail to head");
+        assert_eq!(directed.weight("v2", "v3"), 1.0, "❌ Only the undirected edge links v2 and v3");
+        assert_eq!(directed.link_count(), 10, "❌ Directed link count mismatch");
+
+        // Readable as a matrix
+        let dense = counts.to_dense();
+        assert_eq!(dense.len(), 5);
+        assert_eq!(dense[0], vec![0.0, 2.0, 1.0, 1.0, 0.0], "❌ Row of v2 mismatch");
+        assert!(counts.format_matrix().lines().next().unwrap().contains("v1"), "❌ Header should list the nodes");
+
+        // Writable as pairwise relationships
+        let relationships = RelationshipRepository::new(RELATIONSHIP_DB_PATH)?;
+        assert_eq!(service.save_relationships(&counts, CliqueWeighting::Count, &relationships)?, 6);
+        assert_eq!(service.save_relationships(&directed, CliqueWeighting::Count, &relationships)?, 10);
+
+        let pair = relationships.get_by_key("v2--v3")?.expect("❌ Undirected relationship was not stored");
+        assert!(!pair.directed);
+        assert_eq!(pair.edge_properties[0].value, vec!["2".to_string()], "❌ Weight property mismatch");
+        let arc = relationships.get_by_key("v2->v1")?.expect("❌ Directed relationship was not stored");
+        assert!(arc.directed && arc.node_1 == "v2" && arc.node_2 == "v1");
+        assert!(relationships.get_by_key("v1->v2")?.is_none());
+        assert_eq!(relationships.get_all()?.len(), 16);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::services::coloring_service::{ColoringKind, ColoringService, Independence};
+use hgdb_core::hyper_edge::services::incidence_service::IncidenceService;
+use common::{edge, strings};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/coloring"; // RocksDB path
+
+    #[test]
+    fn test_coloring_and_matching() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let mut heavy = edge("test_edge_3", &["c", "d"], None);
+        heavy.main_properties.push(Property { key: "weight".to_string(), value: vec!["5".to_string()] });
+        for edge in [
+            edge("test_edge_1", &["a", "b", "c"], None),
+            edge("test_edge_2", &["c"], Some(&["a", "e"])),
+            heavy,
+            edge("test_edge_4", &["e", "f"], None),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = ColoringService::new(&repository);
+        let incidence = IncidenceService::new(&repository).build(None)?;
+
+        let weak = service.coloring(ColoringKind::Weak)?;
+        assert!(weak.is_valid(&incidence), "❌ Weak coloring leaves a monochromatic edge: {:?}", weak.classes());
+        assert_eq!(weak.color_count(), 2, "❌ Two colors suffice for a weak coloring");
+
+        let strong = service.coloring(ColoringKind::Strong)?;
+        assert!(strong.is_valid(&incidence), "❌ Strong coloring repeats a color inside an edge: {:?}", strong.classes());
+        assert_eq!(strong.color_count(), 3, "❌ The three members of test_edge_1 need three colors");
+        assert_ne!(strong.color_of("a"), strong.color_of("e"), "❌ Tail members share the edge with the head");
+
+        let maximal = service.maximal_independent_set(Independence::Weak)?;
+        assert_eq!(maximal, strings(&["a", "b", "e", "d"]), "❌ Maximal weakly independent set mismatch");
+        let strongly = service.maximum_independent_set(Independence::Strong)?;
+        assert_eq!(strongly, strings(&["b", "d", "f"]), "❌ Approximate maximum strongly independent set mismatch");
+
+        // The heavy edge goes first and blocks test_edge_1 and test_edge_2
+        let matching = service.matching(Some("weight"))?;
+        assert_eq!(matching.edges, strings(&["test_edge_3", "test_edge_4"]), "❌ Matching mismatch");
+        assert_eq!(matching.weight, 6.0);
+
+        service.save_coloring(&strong)?;
+        service.save_matching(&matching)?;
+        let stored = repository.get_by_key("test_edge_2")?.expect("❌ Edge should exist");
+        let colors = stored.main_properties.iter().find(|property| property.key == "coloring.strong").expect("❌ Colors should be stored");
+        assert_eq!(colors.value.len(), 3, "❌ Every member should get its color");
+        let matched = stored.main_properties.iter().find(|property| property.key == "matching").expect("❌ Matching should be stored");
+        assert_eq!(matched.value, strings(&["false"]));
+        let matched = repository.get_by_key("test_edge_3")?.expect("❌ Edge should exist").main_properties;
+        assert!(matched.iter().any(|property| property.key == "matching" && property.value == strings(&["true"])));
+
+        // A hyperedge added after the coloring has uncolored members: nothing should be saved
+        repository.create("test_edge_9", &edge("test_edge_9", &["a", "z"], None))?;
+        let stale = service.coloring(ColoringKind::Weak)?;
+        repository.create("test_edge_10", &edge("test_edge_10", &["a", "y"], None))?;
+        assert!(service.save_coloring(&stale).is_err(), "❌ Uncolored members should be rejected");
+        let stored = repository.get_by_key("test_edge_9")?.expect("❌ Edge should exist");
+        assert!(stored.main_properties.iter().all(|property| property.key != "coloring.weak"), "❌ A rejected coloring must not write");
+        Ok(())
+    }
+}
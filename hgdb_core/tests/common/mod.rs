@@ -0,0 +1,54 @@
+// Fixtures shared by the integration tests. Every test crate compiles its own copy and uses only part of it
+#![allow(dead_code)]
+
+use hgdb_core::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+
+pub fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+pub fn property(key: &str, values: &[&str]) -> Property<String, String> {
+    Property { key: key.to_string(), value: strings(values) }
+}
+
+/// A traversable hyperedge without properties, named `e<n>` after its `test_edge_<n>` id. It is directed when it
+/// has a tail
+pub fn edge(id: &str, head: &[&str], tail: Option<&[&str]>) -> SimpleHyperEdge<String, String, String> {
+    SimpleHyperEdge {
+        id: id.to_string(),
+        name: id.replace("test_edge_", "e"),
+        main_properties: Vec::new(),
+        traversable: true,
+        directed: tail.is_some(),
+        head_hyper_nodes: Box::new(strings(head)),
+        tail_hyper_nodes: tail.map(|tail| Box::new(strings(tail))),
+    }
+}
+
+pub fn undirected(id: &str, head: &[&str]) -> SimpleHyperEdge<String, String, String> {
+    edge(id, head, None)
+}
+
+/// Chained setters for the fixture hyperedges, e.g. `edge(..).with_property("type", &["linked"])`
+pub trait EdgeBuilder {
+    fn with_property(self, key: &str, values: &[&str]) -> Self;
+    fn with_traversable(self, traversable: bool) -> Self;
+    fn with_name(self, name: &str) -> Self;
+}
+
+impl EdgeBuilder for SimpleHyperEdge<String, String, String> {
+    fn with_property(mut self, key: &str, values: &[&str]) -> Self {
+        self.main_properties.push(property(key, values));
+        self
+    }
+
+    fn with_traversable(mut self, traversable: bool) -> Self {
+        self.traversable = traversable;
+        self
+    }
+
+    fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+}
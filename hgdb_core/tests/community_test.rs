@@ -0,0 +1,100 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::community_service::{CommunityService, EdgeContribution, HypergraphModularity, Partition};
+use hgdb_core::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use common::undirected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/community"; // RocksDB path
+
+    fn graph(name: &str, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        HyperGraph { id: name.to_string(), name: name.to_string(), properties: Vec::new(), hyper_nodes: Vec::new(), hyper_edges: edges }
+    }
+
+    // Two tight triangles of hyperedges joined by a single bridge
+    fn clusters() -> HyperGraph<String, String, String> {
+        graph("clusters", vec![
+            undirected("test_edge_1", &["a", "b", "c"]),
+            undirected("test_edge_2", &["a", "b"]),
+            undirected("test_edge_3", &["b", "c"]),
+            undirected("test_edge_4", &["x", "y", "z"]),
+            undirected("test_edge_5", &["x", "y"]),
+            undirected("test_edge_6", &["y", "z"]),
+            undirected("test_edge_7", &["c", "x"]),
+        ])
+    }
+
+    #[test]
+    fn test_modularity() -> Result<(), Box<dyn Error>> {
+        let incidence = IncidenceMatrix::from_graph(&graph("pairs", vec![undirected("test_edge_1", &["a", "b"]), undirected("test_edge_2", &["c", "d"])]), None);
+        let strict = HypergraphModularity::new(&incidence, EdgeContribution::Strict);
+
+        // Observed 2 of 2, expected 2 * (0.5^2 + 0.5^2) = 1
+        let split = Partition::from_groups(&incidence, &[vec!["a", "b"], vec!["c", "d"]]);
+        assert!((strict.modularity(&split) - 0.5).abs() < 1e-9, "❌ Modularity mismatch: {}", strict.modularity(&split));
+        let whole = Partition::from_groups(&incidence, &[vec!["a", "b", "c", "d"]]);
+        assert!(strict.modularity(&whole).abs() < 1e-9, "❌ A single community should have zero modularity");
+
+        let incidence = IncidenceMatrix::from_graph(&clusters(), None);
+        let groups = [vec!["a", "b", "c"], vec!["x", "y", "z"]];
+        let partition = Partition::from_groups(&incidence, &groups);
+        for contribution in [EdgeContribution::Strict, EdgeContribution::Majority] {
+            let modularity = HypergraphModularity::new(&incidence, contribution);
+            assert!(modularity.modularity(&partition) > modularity.modularity(&Partition::singletons(&incidence)), "❌ The clusters should beat singletons");
+        }
+
+        // A 3-member edge with 2 members inside counts for majority but not for strict
+        let skewed = Partition::from_groups(&incidence, &[vec!["a", "b"], vec!["c", "x", "y", "z"]]);
+        let strict = HypergraphModularity::new(&incidence, EdgeContribution::Strict).modularity(&skewed);
+        let majority = HypergraphModularity::new(&incidence, EdgeContribution::Majority).modularity(&skewed);
+        assert!(strict != majority, "❌ Strict and majority contributions should differ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_community_detection() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("clusters", &clusters())?;
+        let service = CommunityService::new(&repository);
+        let expected = vec![vec!["a", "b", "c"], vec!["x", "y", "z"]];
+
+        let partition = service.louvain("clusters", None, EdgeContribution::Strict)?;
+        assert_eq!(partition.communities(), expected, "❌ Strict Louvain should find both clusters");
+
+        // Pairs only count under majority when both members agree, so splitting off the bridge scores higher
+        let majority = service.louvain("clusters", None, EdgeContribution::Majority)?;
+        let communities = majority.communities();
+        let groups: Vec<Vec<&str>> = communities.iter().map(|group| group.iter().map(String::as_str).collect()).collect();
+        let found = service.modularity("clusters", &groups, None, EdgeContribution::Majority)?;
+        let clustered = service.modularity("clusters", &[vec!["a", "b", "c"], vec!["x", "y", "z"]], None, EdgeContribution::Majority)?;
+        assert!(found >= clustered, "❌ Majority Louvain should do at least as well as the clusters: {:?}", communities);
+        let propagated = service.label_propagation("clusters", None, 100)?;
+        assert_eq!(propagated.communities(), expected, "❌ Label propagation should find both clusters");
+        assert_eq!(propagated.community_of("z"), Some(1));
+
+        let reported = service.modularity("clusters", &[vec!["a", "b", "c"], vec!["x", "y", "z"]], None, EdgeContribution::Strict)?;
+        let saved = service.save_communities("clusters", &propagated, None, EdgeContribution::Strict)?;
+        assert!((reported - saved).abs() < 1e-12, "❌ Saved modularity should match the reported one");
+
+        let graph = repository.get_by_key("clusters")?.expect("❌ Graph should exist");
+        assert!(graph.properties.iter().any(|property| property.key == "modularity.strict"), "❌ Modularity should be stored on the graph");
+        assert_eq!(service.stored_partition("clusters")?.communities(), expected, "❌ Stored communities should round-trip");
+
+        assert!(service.louvain("missing", None, EdgeContribution::Strict).is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+}
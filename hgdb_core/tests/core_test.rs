@@ -0,0 +1,74 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::core_service::CoreService;
+use common::{edge, strings};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/core"; // RocksDB path
+
+    #[test]
+    fn test_core_decomposition() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("fringe", &HyperGraph {
+            id: "fringe".to_string(),
+            name: "fringe".to_string(),
+            properties: Vec::new(),
+            hyper_nodes: Vec::new(),
+            hyper_edges: vec![
+                edge("test_edge_1", &["a", "b", "c"], None),
+                edge("test_edge_2", &["a", "b", "c"], None),
+                edge("test_edge_3", &["a", "b", "c"], None),
+                edge("test_edge_4", &["b", "d"], Some(&["a"])),
+                edge("test_edge_5", &["c", "d"], None),
+                edge("test_edge_6", &["d", "e"], None),
+            ],
+        })?;
+        let service = CoreService::new(&repository);
+
+        // Peeling e drops test_edge_6, peeling d drops test_edge_4 and test_edge_5
+        let strict = service.decompose("fringe", None)?;
+        let expected: Vec<(String, usize)> = vec![("a", 3), ("b", 3), ("c", 3), ("d", 2), ("e", 1)]
+            .into_iter().map(|(id, core)| (id.to_string(), core)).collect();
+        assert_eq!(strict.core_numbers(), expected, "❌ k-core numbers mismatch");
+        assert_eq!(strict.core_nodes(2), strings(&["a", "b", "c", "d"]));
+
+        // With m = 2, test_edge_4 keeps a and b after d is gone, which lifts them above c
+        let km = service.save_core_numbers("fringe", Some(2))?;
+        assert_eq!(km.core_number("a"), Some(4), "❌ (k,m)-core number of a mismatch");
+        assert_eq!(km.core_number("c"), Some(3), "❌ (k,m)-core number of c mismatch");
+        assert_eq!(km.max_core(), 4);
+        let stored = repository.get_by_key("fringe")?.expect("❌ Graph should exist");
+        let d = stored.hyper_nodes.iter().find(|node| node.id == "d").expect("❌ Node d should be listed");
+        assert_eq!(d.properties[0].key, "core_number.m2");
+        assert_eq!(d.properties[0].value, strings(&["2"]));
+
+        let core = service.extract_core("fringe", 3, None, "fringe_core")?;
+        assert_eq!(core.hyper_nodes.len(), 3, "❌ The 3-core should keep a, b and c");
+        assert_eq!(core.hyper_edges.iter().map(|edge| edge.id.as_str()).collect::<Vec<_>>(), vec!["test_edge_1", "test_edge_2", "test_edge_3"]);
+
+        service.extract_core("fringe", 4, Some(2), "fringe_km_core")?;
+        let km_core = repository.get_by_key("fringe_km_core")?.expect("❌ Extracted core should be stored");
+        assert_eq!(km_core.hyper_edges.len(), 4, "❌ Every edge keeping two of a and b should survive");
+        let shrunk = km_core.hyper_edges.iter().find(|edge| edge.id == "test_edge_4").expect("❌ test_edge_4 should survive");
+        assert_eq!(*shrunk.head_hyper_nodes, strings(&["b"]), "❌ Head should be restricted to the core");
+        assert_eq!(shrunk.tail_hyper_nodes.as_deref(), Some(&strings(&["a"])), "❌ Tail role should be kept");
+        let provenance: Vec<(&str, &str)> = km_core.properties.iter().map(|property| (property.key.as_str(), property.value[0].as_str())).collect();
+        assert_eq!(provenance, vec![("core_of", "fringe"), ("core.k", "4"), ("core.m", "2")]);
+
+        assert!(service.decompose("missing", None).is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::datalog_service::DatalogService;
+use hgdb_core::hyper_edge::services::datalog::program::parse_program;
+use common::{edge, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/datalog"; // RocksDB path
+
+    #[test]
+    fn test_datalog_reachability_and_materialization() -> Result<(), Box<dyn Error>> {
+        // Delete the database folder before running the test
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let edges = vec![
+            edge("test_edge_1", &["v2"], Some(&["v1"])).with_property("type", &["linked"]),
+            edge("test_edge_2", &["v3", "v4"], Some(&["v2"])).with_property("type", &["linked"]),
+            edge("test_edge_3", &["v5"], Some(&["v4"])).with_property("type", &["not-linked"]),
+            edge("test_edge_4", &["v6", "v7"], None).with_property("type", &["linked"]),
+        ];
+        for edge in &edges {
+            repository.create(&edge.id, edge)?;
+        }
+
+        let service = DatalogService::new(&repository);
+        let text = "
+            % transitive reachability from tail to head
+            reach(X, Y) :- directed(E), tail(E, X), head(E, Y).
+            reach(X, Z) :- reach(X, Y), reach(Y, Z).
+            % role inference
+            source(N) :- tail(_, N), node(N), N != 'v4'.
+            linked_member(N) :- member(E, N), property(E, type, linked).
+            start(v1).
+        ";
+        let result = service.run(text)?;
+
+        assert!(result.contains("reach", &["v1", "v5"]), "❌ v5 should be reachable from v1");
+        assert!(!result.contains("reach", &["v5", "v1"]), "❌ Reachability must follow the edge direction");
+        assert!(!result.contains("reach", &["v6", "v7"]), "❌ Undirected edges are not used by the rule");
+        assert_eq!(result.facts("reach").len(), 8, "❌ Transitive closure size mismatch");
+        assert_eq!(result.facts("source"), vec![vec!["v1".to_string()], vec!["v2".to_string()]], "❌ Role inference mismatch");
+        assert_eq!(result.facts("linked_member").len(), 6, "❌ Property join mismatch");
+        assert!(result.contains("start", &["v1"]), "❌ Program fact missing");
+        assert!(result.iterations > 2, "❌ Recursive rule should need several rounds");
+
+        // Provenance points at the recursive rule for derived long-range facts
+        let derived = result.derived_facts("reach");
+        let (_, rule) = derived.iter().find(|(tuple, _)| tuple == &vec!["v1".to_string(), "v5".to_string()]).unwrap();
+        assert_eq!(*rule, 1, "❌ Provenance rule mismatch");
+
+        // Derived facts can be written back as hyperedges
+        let program = parse_program(text)?;
+        let materialized = service.materialize(&result, &program, &["reach"])?;
+        assert_eq!(materialized.len(), 8, "❌ Materialized edge count mismatch");
+
+        let stored = repository.get_by_key("reach(v1,v5)")?.expect("❌ Derived edge not stored");
+        assert!(stored.directed, "❌ Binary facts should be directed");
+        assert_eq!(stored.tail_hyper_nodes.as_deref(), Some(&vec!["v1".to_string()]));
+        assert_eq!(*stored.head_hyper_nodes, vec!["v5".to_string()]);
+        let provenance = stored.main_properties.iter().find(|property| property.key == "provenance").unwrap();
+        assert_eq!(provenance.value, vec!["reach(X, Z) :- reach(X, Y), reach(Y, Z).".to_string()]);
+
+        // Unsafe rules and arity clashes are rejected
+        assert!(service.run("bad(X, Y) :- node(X).").is_err(), "❌ Unsafe rule should fail");
+        assert!(service.run("bad(X) :- head(X).").is_err(), "❌ Arity clash should fail");
+
+        Ok(())
+    }
+}
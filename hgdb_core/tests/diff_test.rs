@@ -0,0 +1,129 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::diff_service::{DiffService, EdgeChange, FieldChange, HyperGraphDiffService, NodeChange, Patch};
+use common::{edge, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/diff"; // RocksDB path
+    const REPLICA_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/diff-replica"; // RocksDB path
+    const GRAPH_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/diff-graph"; // RocksDB path
+
+    fn clean(path: &str) {
+        if let Err(e) = remove_dir_all(path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+    }
+
+    fn sorted(mut edges: Vec<SimpleHyperEdge<String, String, String>>) -> Vec<SimpleHyperEdge<String, String, String>> {
+        edges.sort_by(|a, b| a.id.cmp(&b.id));
+        edges
+    }
+
+    #[test]
+    fn test_diff_and_patch_flat_store() -> Result<(), Box<dyn Error>> {
+        clean(DB_PATH);
+        clean(REPLICA_PATH);
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let replica = SimpleHyperEdgeRepository::new(REPLICA_PATH)?;
+        for edge in [edge("test_edge_1", &["a", "b"], None).with_property("status", &["open"]), edge("test_edge_2", &["c"], Some(&["d"])).with_property("status", &["open"]), edge("test_edge_3", &["e", "f"], None).with_property("status", &["open"])] {
+            repository.create(&edge.id, &edge)?;
+            replica.create(&edge.id, &edge)?;
+        }
+        let service = DiffService::new(&repository);
+        let snapshot = service.snapshot()?;
+
+        let mut first = edge("test_edge_1", &["b", "x"], None).with_property("status", &["open"]);
+        first.name = "renamed".to_string();
+        first.main_properties = vec![
+            Property { key: "status".to_string(), value: vec!["closed".to_string()] },
+            Property { key: "owner".to_string(), value: vec!["ana".to_string()] },
+        ];
+        repository.update("test_edge_1", &first)?;
+        let mut second = edge("test_edge_2", &["c"], None).with_property("status", &["open"]);
+        second.traversable = false;
+        repository.update("test_edge_2", &second)?;
+        repository.delete("test_edge_3")?;
+        let added = edge("test_edge_4", &["a", "f"], None).with_property("status", &["open"]);
+        repository.create(&added.id, &added)?;
+
+        let patch = service.diff_since(&snapshot)?;
+        assert_eq!(patch.edges, vec![
+            EdgeChange::Modified { id: "test_edge_1".to_string(), fields: vec![
+                FieldChange::Name { from: "e1".to_string(), to: "renamed".to_string() },
+                FieldChange::Property { key: "status".to_string(), from: Some(vec!["open".to_string()]), to: Some(vec!["closed".to_string()]) },
+                FieldChange::Property { key: "owner".to_string(), from: None, to: Some(vec!["ana".to_string()]) },
+                FieldChange::Head { added: vec!["x".to_string()], removed: vec!["a".to_string()] },
+            ] },
+            EdgeChange::Modified { id: "test_edge_2".to_string(), fields: vec![
+                FieldChange::Traversable { from: true, to: false },
+                FieldChange::Directed { from: true, to: false },
+                FieldChange::Tail { added: vec![], removed: vec!["d".to_string()] },
+            ] },
+            EdgeChange::Removed { edge: edge("test_edge_3", &["e", "f"], None).with_property("status", &["open"]) },
+            EdgeChange::Added { edge: added.clone() },
+        ], "❌ Unexpected field-level diff");
+
+        // The patch travels as JSON and turns the replica into the current store
+        let patch = Patch::from_json(&patch.to_json()?)?;
+        DiffService::new(&replica).apply(&patch)?;
+        assert_eq!(sorted(replica.get_all()?), sorted(repository.get_all()?), "❌ Replica should match the store");
+        assert!(DiffService::new(&replica).diff_since(&repository.get_all()?)?.is_empty());
+
+        // Applying it again conflicts and leaves the replica as it is
+        assert!(DiffService::new(&replica).apply(&patch).is_err(), "❌ A patch applies only to the old version");
+        assert_eq!(sorted(replica.get_all()?), sorted(repository.get_all()?));
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_and_patch_named_graphs() -> Result<(), Box<dyn Error>> {
+        clean(GRAPH_PATH);
+
+        let node = |id: &str, color: &str| HyperNode { id: id.to_string(), properties: vec![Property { key: "color".to_string(), value: vec![color.to_string()] }] };
+        let before = HyperGraph {
+            id: "v1".to_string(),
+            name: "v1".to_string(),
+            properties: Vec::new(),
+            hyper_nodes: vec![node("a", "red"), node("b", "red"), node("z", "blue")],
+            hyper_edges: vec![edge("test_edge_1", &["a", "b"], None).with_property("status", &["open"])],
+        };
+        let mut after = before.clone();
+        after.id = "v2".to_string();
+        after.hyper_nodes = vec![node("a", "green"), node("b", "red"), node("c", "red")];
+        after.hyper_edges[0].head_hyper_nodes.push("c".to_string());
+
+        let repository = HyperGraphRepository::new(GRAPH_PATH)?;
+        repository.create("v1", &before)?;
+        repository.create("v2", &after)?;
+        repository.create("copy", &before)?;
+        let service = HyperGraphDiffService::new(&repository);
+
+        let patch = service.diff("v1", "v2")?;
+        assert_eq!(patch.edges, vec![EdgeChange::Modified { id: "test_edge_1".to_string(), fields: vec![FieldChange::Head { added: vec!["c".to_string()], removed: vec![] }] }]);
+        assert_eq!(patch.nodes, vec![
+            NodeChange::Modified { id: "a".to_string(), fields: vec![FieldChange::Property { key: "color".to_string(), from: Some(vec!["red".to_string()]), to: Some(vec!["green".to_string()]) }] },
+            NodeChange::Removed { node: node("z", "blue") },
+            NodeChange::Added { node: node("c", "red") },
+        ]);
+
+        let patched = service.apply("copy", &patch)?;
+        assert_eq!(patched.hyper_nodes, after.hyper_nodes, "❌ Nodes should match the new version");
+        assert_eq!(patched.hyper_edges, after.hyper_edges, "❌ Hyperedges should match the new version");
+        assert_eq!(repository.get_by_key("copy")?.expect("❌ Copy should exist").id, "v1", "❌ Graph identity is not part of the patch");
+        assert!(service.apply("copy", &patch).is_err(), "❌ Conflicting patch should be rejected");
+        assert!(service.diff("v1", "missing").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::dual_h_graph_service::DualHyperGraphService;
+use common::{edge, strings, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/dual-h-graph"; // RocksDB path
+
+    fn test_graph() -> HyperGraph<String, String, String> {
+        let edge = |id: &str, head: &[&str], tail: Option<&[&str]>| edge(id, head, tail)
+            .with_property("type", &["linked"])
+            .with_traversable(id != "test_edge_2");
+
+        HyperGraph {
+            id: "snapshot".to_string(),
+            name: "snapshot".to_string(),
+            properties: vec![Property { key: "owner".to_string(), value: vec!["team-a".to_string()] }],
+            hyper_nodes: ["v1", "v2", "v3", "v4", "v5", "v6"].iter()
+                .map(|id| HyperNode {
+                    id: id.to_string(),
+                    properties: vec![Property { key: "label".to_string(), value: vec![id.to_uppercase()] }],
+                })
+                .collect(),
+            hyper_edges: vec![
+                edge("test_edge_1", &["v2", "v3"], Some(&["v1"])),
+                edge("test_edge_2", &["v3", "v4"], None),
+                edge("test_edge_3", &["v4", "v5"], None),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_h_graph_repository_crud() -> Result<(), Box<dyn Error>> {
+        let path = format!("{}-crud", DB_PATH);
+        if let Err(e) = remove_dir_all(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(&path)?;
+        let graph = test_graph();
+        repository.create("snapshot", &graph)?;
+        repository.create("snapshot2", &graph)?;
+
+        let stored = repository.get_by_key("snapshot")?.expect("❌ Graph was not found");
+        assert_eq!(stored, graph, "❌ Stored graph mismatch");
+        assert_eq!(repository.get_names()?, vec!["snapshot", "snapshot2"], "❌ Graph names mismatch");
+
+        let mut updated_edge = graph.hyper_edges[0].clone();
+        updated_edge.name = "e1_updated".to_string();
+        repository.update_edge("snapshot", &updated_edge)?;
+        let stored = repository.get_by_key("snapshot")?.unwrap();
+        assert_eq!(stored.hyper_edges[0].name, "e1_updated", "❌ Edge update mismatch");
+        assert_eq!(stored.hyper_edges.len(), 3, "❌ Edge update should not add edges");
+
+        repository.delete("snapshot")?;
+        assert!(repository.get_by_key("snapshot")?.is_none(), "❌ Graph was not deleted");
+        assert_eq!(repository.get_all()?.len(), 1, "❌ Delete must not touch other graphs");
+        assert_eq!(repository.get_by_key("snapshot2")?.unwrap().hyper_nodes.len(), 6);
+
+        assert!(repository.create("bad/key", &graph).is_err(), "❌ Keys with '/' should be rejected");
+        assert!(repository.update_edge("missing", &updated_edge).is_err(), "❌ Unknown graph should fail");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_whole_graph_dual() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        let service = DualHyperGraphService::new(&repository);
+        let graph = test_graph();
+        repository.create("snapshot", &graph)?;
+
+        let dual = service.create_dual("snapshot")?;
+
+        // Nodes become hyperedges and hyperedges become nodes
+        assert_eq!(dual.hyper_edges.len(), 6, "❌ Every node should become a dual hyperedge");
+        assert_eq!(dual.hyper_nodes.len(), 3, "❌ Every hyperedge should become a dual node");
+
+        let v3 = dual.hyper_edges.iter().find(|edge| edge.id == "v3").unwrap();
+        assert_eq!(*v3.head_hyper_nodes, strings(&["test_edge_1", "test_edge_2"]), "❌ Dual edge v3 members mismatch");
+        assert!(!v3.directed, "❌ v3 is never in a tail");
+
+        let v1 = dual.hyper_edges.iter().find(|edge| edge.id == "v1").unwrap();
+        assert!(v1.directed && v1.head_hyper_nodes.is_empty(), "❌ v1 only occurs in a tail");
+        assert_eq!(v1.tail_hyper_nodes.as_deref(), Some(&strings(&["test_edge_1"])), "❌ Tail role not preserved");
+
+        let v6 = dual.hyper_edges.iter().find(|edge| edge.id == "v6").unwrap();
+        assert!(v6.head_hyper_nodes.is_empty(), "❌ Isolated node should give an empty dual edge");
+
+        // The dual is persisted as a hypergraph of its own that points back to the original
+        let stored = repository.get_by_key("dual_snapshot")?.expect("❌ Dual was not stored");
+        assert_eq!(stored.hyper_edges.len(), 6);
+        assert!(stored.properties.iter().any(|property| property.key == "dual_of" && property.value == strings(&["snapshot"])));
+
+        // dual(dual(H)) = H
+        let dual_of_dual = service.compute_dual(&dual);
+        assert_eq!(dual_of_dual, graph, "❌ The dual of the dual should be the original hypergraph");
+
+        assert!(service.create_dual("missing").is_err(), "❌ Unknown graph should fail");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::services::generator_service::{Generator, GeneratorService, Model};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/generator"; // RocksDB path
+
+    fn generate(model: &Model, seed: u64) -> Vec<SimpleHyperEdge<String, String, String>> {
+        Generator::new(model, seed, "test_edge_").expect("❌ Model should be valid").collect()
+    }
+
+    fn members(edge: &SimpleHyperEdge<String, String, String>) -> BTreeSet<String> {
+        edge.head_hyper_nodes.iter().chain(edge.tail_hyper_nodes.iter().flat_map(|tail| tail.iter())).cloned().collect()
+    }
+
+    fn degrees(edges: &[SimpleHyperEdge<String, String, String>]) -> HashMap<String, usize> {
+        let mut degrees = HashMap::new();
+        for edge in edges {
+            for node in members(edge) {
+                *degrees.entry(node).or_insert(0) += 1;
+            }
+        }
+        degrees
+    }
+
+    #[test]
+    fn test_models() {
+        let uniform = Model::Uniform { nodes: 50, edges: 200, size: 4 };
+        let edges = generate(&uniform, 1);
+        assert_eq!(edges.len(), 200);
+        assert!(edges.iter().all(|edge| members(edge).len() == 4 && !edge.directed), "❌ Every hyperedge has 4 distinct members");
+        assert_eq!(edges, generate(&uniform, 1), "❌ Same seed, same hypergraph");
+        assert_ne!(edges, generate(&uniform, 2), "❌ Other seed, other hypergraph");
+        assert_eq!(edges[7].id, "test_edge_7");
+
+        // 4060 possible triples, each kept with probability 0.1
+        let random = generate(&Model::ErdosRenyi { nodes: 30, size: 3, probability: 0.1 }, 3);
+        assert!((330..=480).contains(&random.len()), "❌ Unexpected number of hyperedges: {}", random.len());
+        assert_eq!(random.iter().map(members).collect::<HashSet<_>>().len(), random.len(), "❌ Every triple at most once");
+        assert!(random.iter().all(|edge| members(edge).len() == 3));
+        assert_eq!(generate(&Model::ErdosRenyi { nodes: 6, size: 3, probability: 1.0 }, 0).len(), 20, "❌ Probability 1 gives the complete hypergraph");
+        assert!(generate(&Model::ErdosRenyi { nodes: 6, size: 3, probability: 0.0 }, 0).is_empty());
+
+        let mut weights = vec![1.0; 40];
+        weights[0] = 40.0;
+        let chung_lu = generate(&Model::ChungLu { degrees: weights, sizes: vec![3; 100] }, 4);
+        let chung_lu_degrees = degrees(&chung_lu);
+        assert!(chung_lu.iter().all(|edge| members(edge).len() == 3));
+        assert!(chung_lu_degrees["n0"] > 50, "❌ The heavy node should be in most hyperedges, found {}", chung_lu_degrees["n0"]);
+
+        let configuration = generate(&Model::Configuration { degrees: vec![3; 20], sizes: vec![2, 3, 4, 5, 6, 5, 4, 3, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2] }, 5);
+        assert_eq!(configuration.len(), 22);
+        let configuration_degrees = degrees(&configuration);
+        assert!(configuration_degrees.values().all(|&degree| degree <= 3), "❌ No node exceeds its degree");
+        assert!(configuration_degrees.values().sum::<usize>() > 50, "❌ Only repeated stubs may be lost");
+
+        let directed = generate(&Model::Directed { nodes: 10, edges: 50, head_size: 2, tail_size: 3 }, 6);
+        assert!(directed.iter().all(|edge| edge.directed && edge.head_hyper_nodes.len() == 2 && edge.tail_hyper_nodes.as_ref().is_some_and(|tail| tail.len() == 3)));
+        assert!(directed.iter().all(|edge| members(edge).len() == 5), "❌ Head and tail are disjoint");
+    }
+
+    #[test]
+    fn test_invalid_models() {
+        assert!(Generator::new(&Model::Uniform { nodes: 3, edges: 1, size: 4 }, 0, "e").is_err());
+        assert!(Generator::new(&Model::ErdosRenyi { nodes: 10, size: 2, probability: 1.5 }, 0, "e").is_err());
+        assert!(Generator::new(&Model::ErdosRenyi { nodes: 1_000_000_000, size: 20, probability: 0.1 }, 0, "e").is_err(), "❌ Overflowing combination counts are rejected");
+        assert!(Generator::new(&Model::Configuration { degrees: vec![2, 2], sizes: vec![3] }, 0, "e").is_err());
+        assert!(Generator::new(&Model::Directed { nodes: 4, edges: 1, head_size: 2, tail_size: 3 }, 0, "e").is_err());
+    }
+
+    #[test]
+    fn test_large_streams() {
+        // Hyperedges are streamed, so large hypergraphs never sit in memory at once
+        let sparse = Generator::new(&Model::ErdosRenyi { nodes: 100_000, size: 3, probability: 1e-9 }, 9, "e").expect("❌ Model should be valid");
+        let count = sparse.inspect(|edge| assert_eq!(edge.head_hyper_nodes.len(), 3)).count();
+        assert!((140_000..=195_000).contains(&count), "❌ About 166,000 hyperedges expected, got {}", count);
+        assert_eq!(Generator::new(&Model::Uniform { nodes: 1_000_000, edges: 250_000, size: 5 }, 9, "e").expect("❌ Model should be valid").count(), 250_000);
+    }
+
+    #[test]
+    fn test_skewed_weights() {
+        // One node dwarfs the others: distinct draws must not wait for the light nodes by rejection
+        let model = Model::ChungLu { degrees: vec![1e9, 1.0, 1.0], sizes: vec![3; 1000] };
+        let edges = generate(&model, 3);
+        assert!(edges.iter().all(|edge| members(edge).len() == 3), "❌ Every hyperedge should hold all three nodes");
+        let model = Model::ChungLu { degrees: vec![1e20, 1.0, 0.0, 1.0], sizes: vec![3; 10] };
+        assert!(generate(&model, 3).iter().all(|edge| members(edge) == ["n0", "n1", "n3"].iter().map(|node| node.to_string()).collect()), "❌ Zero weights are never drawn");
+    }
+
+    #[test]
+    fn test_generate_into_repository() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let service = GeneratorService::new(&repository);
+        let model = Model::Uniform { nodes: 1000, edges: 25_000, size: 3 };
+        assert_eq!(service.generate(&model, 42, "u")?, 25_000);
+        assert_eq!(service.generate(&Model::Directed { nodes: 100, edges: 10, head_size: 1, tail_size: 2 }, 42, "d")?, 10);
+
+        let stored = repository.get_all()?;
+        assert_eq!(stored.len(), 25_010, "❌ Every hyperedge should be stored");
+        let expected = Generator::new(&model, 42, "u")?.nth(12_345).expect("❌ Hyperedge should exist");
+        assert_eq!(repository.get_by_key("u12345")?, Some(expected.clone()), "❌ Stored hyperedges follow the seed");
+        assert!(service.generate(&model, 7, "u").is_err(), "❌ Generated ids must not overwrite stored hyperedges");
+        assert_eq!(repository.get_by_key("u12345")?, Some(expected));
+        assert_eq!(service.generate(&Model::Uniform { nodes: 10, edges: 5, size: 2 }, 7, "u-")?, 5, "❌ Other prefixes are free");
+        Ok(())
+    }
+}
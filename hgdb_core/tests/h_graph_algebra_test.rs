@@ -0,0 +1,125 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::h_graph_algebra_service::{ConflictPolicy, EdgeMatching, HyperGraphAlgebraService};
+use common::{undirected, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/h-graph-algebra"; // RocksDB path
+
+    fn snapshot(name: &str, isolated: &str, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        HyperGraph {
+            id: name.to_string(),
+            name: name.to_string(),
+            properties: Vec::new(),
+            hyper_nodes: vec![HyperNode { id: isolated.to_string(), properties: Vec::new() }],
+            hyper_edges: edges,
+        }
+    }
+
+    fn ids(graph: &HyperGraph<String, String, String>) -> Vec<&str> {
+        graph.hyper_edges.iter().map(|edge| edge.id.as_str()).collect()
+    }
+
+    fn status<'g>(graph: &'g HyperGraph<String, String, String>, id: &str) -> &'g [String] {
+        let edge = graph.hyper_edges.iter().find(|edge| edge.id == id).expect("❌ Edge should exist");
+        &edge.main_properties.iter().find(|property| property.key == "status").expect("❌ Status should exist").value
+    }
+
+    #[test]
+    fn test_set_algebra() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("monday", &snapshot("monday", "x", vec![
+            undirected("test_edge_1", &["a", "b"]).with_property("status", &["open"]),
+            undirected("test_edge_2", &["b", "c"]).with_property("status", &["open"]),
+            undirected("test_edge_3", &["c", "d"]).with_property("status", &["open"]),
+        ]))?;
+        repository.create("tuesday", &snapshot("tuesday", "y", vec![
+            undirected("test_edge_1", &["a", "b"]).with_property("status", &["closed"]),
+            undirected("test_edge_9", &["c", "b"]).with_property("status", &["open"]),
+            undirected("test_edge_3", &["d", "e"]).with_property("status", &["open"]),
+        ]))?;
+        let service = HyperGraphAlgebraService::new(&repository);
+
+        let union = service.union("monday", "tuesday", EdgeMatching::ById, ConflictPolicy::Merge, "union")?;
+        assert_eq!(ids(&union), vec!["test_edge_1", "test_edge_2", "test_edge_3", "test_edge_9"]);
+        assert_eq!(status(&union, "test_edge_1"), ["open", "closed"], "❌ Merge should unite conflicting values");
+        assert!(union.hyper_nodes.iter().any(|node| node.id == "x") && union.hyper_nodes.iter().any(|node| node.id == "y"));
+
+        // By node set, test_edge_2 and test_edge_9 are the same edge (keeping the left id) while the two test_edge_3 differ
+        let by_nodes = service.union("monday", "tuesday", EdgeMatching::ByNodes, ConflictPolicy::KeepRight, "union_nodes")?;
+        assert_eq!(ids(&by_nodes), vec!["test_edge_1", "test_edge_2", "test_edge_3", "test_edge_3@tuesday"]);
+        assert_eq!(status(&by_nodes, "test_edge_1"), ["closed"], "❌ KeepRight should take the right values");
+
+        let common = service.intersection("monday", "tuesday", EdgeMatching::ByNodes, ConflictPolicy::KeepLeft, "common")?;
+        assert_eq!(ids(&common), vec!["test_edge_1", "test_edge_2"]);
+        assert_eq!(status(&common, "test_edge_1"), ["open"]);
+        assert!(common.hyper_nodes.iter().all(|node| node.id != "x"), "❌ Isolated nodes of one side are not common");
+        assert!(service.intersection("monday", "tuesday", EdgeMatching::ById, ConflictPolicy::Fail, "failed").is_err(), "❌ Fail should reject conflicts");
+        assert!(repository.get_by_key("failed")?.is_none(), "❌ A failed operation must not write");
+
+        let removed = service.difference("monday", "tuesday", EdgeMatching::ById, "removed")?;
+        assert_eq!(ids(&removed), vec!["test_edge_2"]);
+        let changed = service.symmetric_difference("monday", "tuesday", EdgeMatching::ByNodes, "changed")?;
+        assert_eq!(ids(&changed), vec!["test_edge_3", "test_edge_3@tuesday"]);
+
+        let stored = repository.get_by_key("changed")?.expect("❌ Result should be stored");
+        let provenance: Vec<(&str, &str)> = stored.properties.iter().map(|property| (property.key.as_str(), property.value[0].as_str())).collect();
+        assert_eq!(provenance, vec![("algebra.operation", "symmetric_difference"), ("algebra.left", "monday"), ("algebra.right", "tuesday")]);
+        assert!(service.difference("monday", "missing", EdgeMatching::ById, "none").is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn test_matched_ids_do_not_collide() -> Result<(), Box<dyn Error>> {
+        let path = format!("{}-ids", DB_PATH);
+        if let Err(e) = remove_dir_all(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(&path)?;
+        repository.create("left", &snapshot("left", "x", vec![
+            undirected("e1", &["a", "b"]).with_property("status", &["open"]),
+            undirected("e2", &["c", "d"]).with_property("status", &["open"]),
+        ]))?;
+        repository.create("right", &snapshot("right", "y", vec![undirected("e2", &["a", "b"]).with_property("status", &["closed"])]))?;
+        let service = HyperGraphAlgebraService::new(&repository);
+
+        // The right e2 matches the left e1 by nodes; taking its id would overwrite the left e2 on save
+        service.union("left", "right", EdgeMatching::ByNodes, ConflictPolicy::KeepRight, "union")?;
+        let stored = repository.get_by_key("union")?.expect("❌ Result should be stored");
+        let mut stored_ids = ids(&stored);
+        stored_ids.sort_unstable();
+        assert_eq!(stored_ids, vec!["e1", "e2"], "❌ Both hyperedges should be stored");
+        assert_eq!(status(&stored, "e1"), ["closed"]);
+        assert_eq!(stored.hyper_edges.iter().find(|edge| edge.id == "e2").map(|edge| edge.head_hyper_nodes.to_vec()), Some(vec!["c".to_string(), "d".to_string()]));
+
+        // Renamed right hyperedges stay clear of left ids that already look renamed
+        repository.create("taken", &snapshot("taken", "x", vec![
+            undirected("e1", &["a", "b"]).with_property("status", &["open"]),
+            undirected("e1@right", &["c", "d"]).with_property("status", &["open"]),
+        ]))?;
+        repository.create("right", &snapshot("right", "y", vec![undirected("e1", &["e", "f"]).with_property("status", &["open"])]))?;
+        service.union("taken", "right", EdgeMatching::ByNodes, ConflictPolicy::KeepLeft, "renamed")?;
+        let stored = repository.get_by_key("renamed")?.expect("❌ Result should be stored");
+        let mut stored_ids = ids(&stored);
+        stored_ids.sort_unstable();
+        assert_eq!(stored_ids, vec!["e1", "e1@right", "e1@right.2"], "❌ Every hyperedge should be stored under its own id");
+        Ok(())
+    }
+}
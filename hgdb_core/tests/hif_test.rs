@@ -0,0 +1,153 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::hif_service::{from_hif, to_hif, Hif, HifService};
+use common::{edge, property, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/hif"; // RocksDB path
+
+    // Per-node values come back in incidence order, so compare them as sets
+    fn sorted(properties: &[Property<String, String>]) -> BTreeSet<(String, BTreeSet<String>)> {
+        properties.iter().map(|property| (property.key.clone(), property.value.iter().cloned().collect())).collect()
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let mut catalysis = edge("test_edge_1", &["c", "d"], Some(&["a", "b"])).with_property("label", &["reaction"]);
+        catalysis.main_properties.push(property("vertex_weights", &["a:2", "c:0.5"]));
+        catalysis.main_properties.push(property("incidence.role", &["a:substrate", "c:product"]));
+        let mut complex = edge("test_edge_2", &["a", "e"], None).with_property("label", &["reaction"]);
+        complex.traversable = false;
+        complex.main_properties.push(property("tags", &["x", "y"]));
+        let graph = HyperGraph {
+            id: "pathway".to_string(),
+            name: "Glycolysis".to_string(),
+            properties: vec![property("source", &["kegg"])],
+            hyper_nodes: vec![
+                HyperNode { id: "a".to_string(), properties: vec![property("kind", &["metabolite"])] },
+                HyperNode { id: "z".to_string(), properties: Vec::new() },
+            ],
+            hyper_edges: vec![catalysis, complex],
+        };
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("pathway", &graph)?;
+        let service = HifService::new(&repository);
+        let mut json = Vec::new();
+        let hif = service.export("pathway", &mut json)?;
+
+        assert_eq!(hif.network_type.as_deref(), Some("directed"), "❌ A directed hyperedge makes the network directed");
+        assert_eq!(hif.incidences.len(), 6);
+        let tail_a = hif.incidences.iter().find(|incidence| incidence.edge.to_string() == "test_edge_1" && incidence.node.to_string() == "a").expect("❌ Incidence should exist");
+        assert_eq!((tail_a.direction.as_deref(), tail_a.weight), (Some("tail"), Some(2.0)));
+        assert_eq!(tail_a.attrs["role"], "substrate");
+        let exported: serde_json::Value = serde_json::from_slice(&json)?;
+        assert_eq!(exported["edges"][1]["attrs"]["hgdb.directed"], false, "❌ The undirected hyperedge is marked");
+        assert_eq!(exported["edges"][1]["attrs"]["tags"], serde_json::json!(["x", "y"]));
+
+        let imported = service.import(json.as_slice(), "copy")?;
+        assert_eq!(imported.name, "Glycolysis");
+        assert_eq!(sorted(&imported.properties), sorted(&graph.properties));
+        assert_eq!(imported.hyper_nodes.iter().map(|node| node.id.as_str()).collect::<Vec<_>>(), vec!["a", "z", "b", "c", "d", "e"]);
+        assert_eq!(imported.hyper_nodes[0], graph.hyper_nodes[0]);
+        for (copy, original) in imported.hyper_edges.iter().zip(&graph.hyper_edges) {
+            assert_eq!(sorted(&copy.main_properties), sorted(&original.main_properties), "❌ main_properties should survive");
+            assert_eq!((&copy.id, &copy.name, copy.traversable, copy.directed), (&original.id, &original.name, original.traversable, original.directed));
+            assert_eq!((&copy.head_hyper_nodes, &copy.tail_hyper_nodes), (&original.head_hyper_nodes, &original.tail_hyper_nodes), "❌ Roles should survive");
+        }
+        assert_eq!(repository.get_by_key("copy")?.map(|stored| stored.hyper_edges.len()), Some(2));
+        assert!(service.export("missing", &mut Vec::new()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_import_foreign_hif() -> Result<(), Box<dyn Error>> {
+        // As written by other libraries: integer ids, weights, typed attributes and implicit edges
+        let hif: Hif = serde_json::from_str(r#"{
+            "network-type": "undirected",
+            "metadata": {"creator": "xgi", "year": 2024},
+            "nodes": [{"node": 1, "weight": 1.5, "attrs": {"age": 31, "name": "Ann"}}],
+            "edges": [{"edge": 10, "weight": 2.0, "attrs": {"topics": ["a", "b"], "open": true}}],
+            "incidences": [
+                {"edge": 10, "node": 1},
+                {"edge": 10, "node": 2, "weight": 0.25},
+                {"edge": "extra", "node": 2}
+            ]
+        }"#)?;
+        let graph = from_hif(&hif, "imported")?;
+
+        assert_eq!(graph.name, "imported", "❌ Without a name the key is used");
+        assert_eq!(sorted(&graph.properties), sorted(&[property("creator", &["xgi"]), property("year", &["2024"])]));
+        assert_eq!(sorted(&graph.hyper_nodes[0].properties), sorted(&[property("age", &["31"]), property("name", &["Ann"]), property("weight", &["1.5"])]));
+        assert_eq!(graph.hyper_edges.len(), 2, "❌ Edges named by incidences only are created");
+        let first = &graph.hyper_edges[0];
+        assert_eq!((first.id.as_str(), first.directed, first.tail_hyper_nodes.is_none()), ("10", false, true));
+        assert_eq!(*first.head_hyper_nodes, vec!["1".to_string(), "2".to_string()]);
+        assert_eq!(sorted(&first.main_properties), sorted(&[property("topics", &["a", "b"]), property("open", &["true"]), property("weight", &["2"]), property("vertex_weights", &["2:0.25"])]));
+
+        assert_eq!(to_hif(&graph)?.network_type.as_deref(), Some("undirected"));
+        assert!(to_hif(&graph)?.incidences.iter().all(|incidence| incidence.direction.is_none()));
+        let unknown: Hif = serde_json::from_str(r#"{"network-type": "bipartite", "incidences": []}"#)?;
+        assert!(from_hif(&unknown, "bad").is_err(), "❌ Unknown network types are rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn test_colons_and_reserved_names() -> Result<(), Box<dyn Error>> {
+        // Values and ids with colons, and properties named like the hyperedge fields
+        let hif: Hif = serde_json::from_str(r#"{
+            "network-type": "directed",
+            "edges": [{"edge": "meeting", "attrs": {"name": "standup", "directed": "sometimes", "traversable": "maybe"}}],
+            "incidences": [
+                {"edge": "meeting", "node": "urn:ann", "direction": "tail", "weight": 2.0, "attrs": {"at": "10:30", "meta": {"k": 1}}},
+                {"edge": "meeting", "node": "bob", "attrs": {"url": "https://example.org/bob"}}
+            ]
+        }"#)?;
+        let graph = from_hif(&hif, "calendar")?;
+        let meeting = &graph.hyper_edges[0];
+        assert_eq!((meeting.name.as_str(), meeting.directed, meeting.traversable), ("meeting", true, true), "❌ Plain attributes are no fields");
+        assert_eq!(sorted(&meeting.main_properties), sorted(&[
+            property("name", &["standup"]),
+            property("directed", &["sometimes"]),
+            property("traversable", &["maybe"]),
+            property("vertex_weights", &["urn:ann:2"]),
+            property("incidence.at", &["urn:ann:10:30"]),
+            property("incidence.meta", &[r#"urn:ann:{"k":1}"#]),
+            property("incidence.url", &["bob:https://example.org/bob"]),
+        ]));
+
+        let exported = to_hif(&graph)?;
+        let ann = exported.incidences.iter().find(|incidence| incidence.node.to_string() == "urn:ann").expect("❌ Incidence should exist");
+        assert_eq!((ann.direction.as_deref(), ann.weight), (Some("tail"), Some(2.0)));
+        assert_eq!(ann.attrs["at"], "10:30", "❌ Colons in values should survive");
+        assert_eq!(ann.attrs["meta"], r#"{"k":1}"#);
+        let bob = exported.incidences.iter().find(|incidence| incidence.node.to_string() == "bob").expect("❌ Incidence should exist");
+        assert_eq!(bob.attrs["url"], "https://example.org/bob");
+        assert_eq!(exported.edges[0].attrs["directed"], "sometimes", "❌ A directed property is not the field");
+        assert_eq!(exported.edges[0].attrs["name"], "standup");
+
+        let copy = from_hif(&exported, "calendar")?;
+        assert_eq!(sorted(&copy.hyper_edges[0].main_properties), sorted(&meeting.main_properties));
+        assert_eq!((&copy.hyper_edges[0].head_hyper_nodes, &copy.hyper_edges[0].tail_hyper_nodes), (&meeting.head_hyper_nodes, &meeting.tail_hyper_nodes));
+
+        let mut reserved = graph.clone();
+        reserved.hyper_edges[0].main_properties.push(property("hgdb.directed", &["true"]));
+        assert!(to_hif(&reserved).is_err(), "❌ Properties under the reserved prefix are rejected");
+        Ok(())
+    }
+}
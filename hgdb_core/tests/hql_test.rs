@@ -0,0 +1,82 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::hql_service::HqlService;
+use hgdb_core::hyper_edge::services::hql::ast::Value;
+use common::{edge, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/hql"; // RocksDB path
+
+    fn strings(value: &[&str]) -> Vec<Value> {
+        value.iter().map(|value| Value::Str(value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_hql_queries() -> Result<(), Box<dyn Error>> {
+        // Delete the database folder before running the test
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let edges = vec![
+            edge("test_edge_1", &["v1", "v2"], Some(&["v3"])).with_property("type", &["linked"]).with_property("weight", &["5"]),
+            edge("test_edge_2", &["v4", "v5"], None).with_property("type", &["not-linked"]).with_property("weight", &["2"]).with_traversable(false),
+            edge("test_edge_3", &["v3", "v6"], Some(&["v1"])).with_property("type", &["linked"]).with_property("weight", &["10"]),
+        ];
+        for edge in &edges {
+            repository.create(&edge.id, edge)?;
+        }
+        let service = HqlService::new(&repository);
+
+        // Property predicate, projection and ordering
+        let result = service.query("MATCH (e:Edge) WHERE e.type = 'linked' RETURN e.id, e.weight ORDER BY e.weight DESC")?;
+        assert_eq!(result.columns, vec!["e.id", "e.weight"]);
+        assert_eq!(result.rows, vec![strings(&["test_edge_3", "10"]), strings(&["test_edge_1", "5"])], "❌ Numeric ordering mismatch");
+
+        // Flags and numeric comparison on properties
+        let result = service.query("match (e) where not e.directed or e.weight > 7 return e.name order by e.name")?;
+        assert_eq!(result.rows, vec![strings(&["e2"]), strings(&["e3"])], "❌ Flag filter mismatch");
+
+        // Role constraints through the incidence pattern
+        let result = service.query("MATCH (n:Node)-[:TAIL]-(e:Edge) RETURN n.id, e.id ORDER BY n.id")?;
+        assert_eq!(result.rows, vec![strings(&["v1", "test_edge_3"]), strings(&["v3", "test_edge_1"])], "❌ Tail role mismatch");
+
+        // Aggregates grouped by a property
+        let result = service.query("MATCH (e:Edge) RETURN e.type AS kind, COUNT(*) AS edges, SUM(e.weight) AS total ORDER BY edges DESC LIMIT 1")?;
+        assert_eq!(result.rows, vec![vec![Value::Str("linked".to_string()), Value::Int(2), Value::Float(15.0)]], "❌ Aggregate mismatch");
+
+        let result = service.query("MATCH (n:Node)-[]-(e:Edge) WHERE n.id = 'v1' RETURN COUNT(e)")?;
+        assert_eq!(result.rows, vec![vec![Value::Int(2)]], "❌ Node degree count mismatch");
+
+        // The planner pushes id and membership predicates into index lookups
+        let plan = service.explain("MATCH (e:Edge) WHERE 'v3' IN e.head AND e.traversable RETURN e.id")?;
+        assert!(plan.contains("EdgesOfNodes e nodes=['v3'] role=HEAD (node incidence index)"), "❌ Plan mismatch:\n{}", plan);
+        assert!(plan.contains("Filter e.traversable"), "❌ Remaining predicate missing:\n{}", plan);
+
+        let plan = service.explain("MATCH (n:Node)-[:HEAD]-(e:Edge) WHERE n.id = 'v3' RETURN e.id")?;
+        assert!(plan.contains("NodeLookup n ids=['v3'] (node id index)") && !plan.contains("Filter"), "❌ Plan mismatch:\n{}", plan);
+
+        let result = service.query("EXPLAIN MATCH (e) WHERE e.id = 'test_edge_2' RETURN e.name")?;
+        assert_eq!(result.columns, vec!["plan"]);
+        assert!(result.rows[1][0].to_string().contains("EdgeLookup e ids=['test_edge_2']"), "❌ EXPLAIN rows mismatch");
+
+        let result = service.query("MATCH (e) WHERE e.id = 'test_edge_3' RETURN e.head, e.size")?;
+        assert_eq!(result.rows, vec![vec![Value::List(strings(&["v3", "v6"])), Value::Int(3)]], "❌ Lookup result mismatch");
+
+        // Errors
+        assert!(service.query("MATCH (e) WHERE x.id = 'a'").is_err(), "❌ Unknown variable should fail");
+        assert!(service.query("MATCH (e) RETURN e.id LIMIT").is_err(), "❌ Incomplete LIMIT should fail");
+        assert!(service.query("MATCH (e) WHERE COUNT(*) > 1").is_err(), "❌ Aggregate in WHERE should fail");
+
+        Ok(())
+    }
+}
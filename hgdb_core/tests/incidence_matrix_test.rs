@@ -0,0 +1,103 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::h_graph_service::HyperGraphService;
+use hgdb_core::hyper_edge::services::incidence_service::IncidenceService;
+use hgdb_core::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use hgdb_core::hyper_edge::services::sparse::matrix::SparseMatrix;
+use common::{edge, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/incidence-matrix"; // RocksDB path
+
+    #[test]
+    fn test_sparse_matrix_operations() {
+        let matrix = SparseMatrix::from_triplets(3, 4, vec![(0, 1, 1.0), (2, 3, 2.0), (0, 0, 1.0), (2, 3, 0.5), (1, 2, 3.0)]);
+
+        assert_eq!(matrix.nnz(), 4, "❌ Duplicate entries should be summed");
+        assert_eq!(matrix.row(0), (&[0, 1][..], &[1.0, 1.0][..]), "❌ Row entries should be sorted by column");
+        assert_eq!(matrix.get(2, 3), 2.5);
+        assert_eq!(matrix.get(1, 0), 0.0);
+
+        let transposed = matrix.transpose();
+        assert_eq!((transposed.rows(), transposed.cols()), (4, 3));
+        assert_eq!(transposed.get(3, 2), 2.5, "❌ Transpose mismatch");
+        assert_eq!(transposed.transpose(), matrix, "❌ Double transpose should be the identity");
+
+        let rows = matrix.row_slice(&[2, 0]);
+        assert_eq!(rows.to_dense(), vec![vec![false, false, false, true], vec![true, true, false, false]]);
+        let cols = matrix.column_slice(&[3, 1]);
+        assert_eq!(cols.to_dense(), vec![vec![false, true], vec![false, false], vec![true, false]]);
+
+        assert_eq!(matrix.row_counts(), vec![2, 1, 1]);
+        assert_eq!(matrix.column_sums(), vec![1.0, 1.0, 3.0, 2.5]);
+        assert_eq!(matrix.mul_vec(&[1.0, 2.0, 0.0, 2.0]), vec![3.0, 0.0, 5.0]);
+        assert_eq!(matrix.transpose_mul_vec(&[1.0, 1.0, 2.0]), transposed.mul_vec(&[1.0, 1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_incidence_from_storage() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let edges = vec![
+            edge("test_edge_1", &["v2", "v3"], Some(&["v1"])).with_property("weight", &["2"]),
+            edge("test_edge_2", &["v3", "v4", "v3"], None).with_property("weight", &["0.5"]),
+            edge("test_edge_3", &["v5"], None).with_property("weight", &["heavy"]),
+        ];
+        for edge in &edges {
+            repository.create(&edge.id, edge)?;
+        }
+
+        let service = IncidenceService::new(&repository);
+        let incidence = service.build(Some("weight"))?;
+
+        // Stable index maps: edges in key order, nodes in order of first appearance (head before tail)
+        assert_eq!(incidence.edges.ids(), &["test_edge_1", "test_edge_2", "test_edge_3"]);
+        assert_eq!(incidence.nodes.ids(), &["v2", "v3", "v1", "v4", "v5"]);
+        let v3 = incidence.nodes.position("v3").unwrap();
+
+        assert_eq!(incidence.node_edges(v3), &[0, 1], "❌ v3 should be in the first two edges");
+        assert_eq!(incidence.edge_sizes(), vec![3, 2, 1], "❌ Repeated members should count once");
+        assert_eq!(incidence.node_degrees(), vec![1, 2, 1, 1, 1]);
+        assert_eq!(incidence.weighted_node_degrees()[v3], 2.5, "❌ Weighted degree mismatch");
+        assert_eq!(incidence.edge_weight(2), 1.0, "❌ Non-numeric weights should count as 1");
+
+        // Roles
+        assert!(incidence.is_directed(0) && !incidence.is_directed(1));
+        assert_eq!(incidence.edge_tail(0), &[incidence.nodes.position("v1").unwrap()]);
+        assert_eq!(incidence.edge_head(0), &[0, 1]);
+        assert!(incidence.edge_tail(1).is_empty(), "❌ Undirected edges have no tail");
+
+        // Slicing
+        let column = incidence.edge_columns(&[1]);
+        assert_eq!((column.rows(), column.cols()), (5, 1));
+        assert_eq!(column.column_counts(), vec![2]);
+        assert_eq!(incidence.node_rows(&[v3]).row(0).0, &[0, 1]);
+
+        // The storage build matches the in-memory build of the same graph
+        let graph = HyperGraphService::new(&repository).load_graph("incidence")?;
+        assert_eq!(IncidenceMatrix::from_graph(&graph, Some("weight")), incidence, "❌ Storage and graph builds differ");
+
+        // Transpose gives the dual incidence and keeps roles
+        let dual = incidence.transpose();
+        assert_eq!(dual.nodes.ids(), incidence.edges.ids());
+        assert_eq!(dual.csr().to_dense(), incidence.csc().to_dense(), "❌ Dual rows should be the original edges");
+        assert_eq!(dual.edge_weights(), incidence.node_weights(), "❌ Node weights become dual edge weights");
+        let v1 = dual.edges.position("v1").unwrap();
+        assert!(dual.is_directed(v1), "❌ A tail node should give a directed dual edge");
+        assert_eq!(dual.edge_tail(v1), &[0]);
+        assert_eq!(dual.transpose().csr(), incidence.csr(), "❌ Double transpose should restore the incidence");
+
+        Ok(())
+    }
+}
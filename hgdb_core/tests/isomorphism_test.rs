@@ -0,0 +1,117 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::isomorphism_service::{canonical_form, isomorphism, IsomorphismService};
+use common::{edge, undirected};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/isomorphism"; // RocksDB path
+
+    type Graph = HyperGraph<String, String, String>;
+
+    fn graph(id: &str, nodes: &[&str], edges: Vec<SimpleHyperEdge<String, String, String>>) -> Graph {
+        HyperGraph {
+            id: id.to_string(),
+            name: id.to_string(),
+            properties: Vec::new(),
+            hyper_nodes: nodes.iter().map(|node| HyperNode { id: node.to_string(), properties: Vec::new() }).collect(),
+            hyper_edges: edges,
+        }
+    }
+
+    // Every hyperedge of `left`, renamed through the node mapping, as (directed, head, tail) sets
+    fn shapes(graph: &Graph, rename: &HashMap<String, String>) -> BTreeSet<(bool, BTreeSet<String>, BTreeSet<String>)> {
+        graph.hyper_edges.iter()
+            .map(|edge| {
+                let map = |nodes: &[String]| nodes.iter().map(|node| rename.get(node).unwrap_or(node).clone()).collect();
+                (edge.directed, map(&edge.head_hyper_nodes), map(edge.tail_hyper_nodes.as_deref().map_or(&[][..], |tail| tail)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_isomorphism_and_fingerprints() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let original = graph("original", &["lonely"], vec![
+            undirected("test_edge_1", &["a", "b", "c"]),
+            edge("test_edge_2", &["c"], Some(&["d", "e"])),
+            undirected("test_edge_3", &["a", "d"]),
+        ]);
+        // Same structure, other ids and listing order
+        let relabeled = graph("relabeled", &["q"], vec![
+            undirected("x3", &["w", "t"]),
+            undirected("x1", &["v", "u", "w"]),
+            edge("x2", &["v"], Some(&["t", "s"])),
+        ]);
+        // Head and tail of the directed hyperedge swapped
+        let reversed = graph("reversed", &["lonely"], vec![
+            undirected("test_edge_1", &["a", "b", "c"]),
+            edge("test_edge_2", &["d", "e"], Some(&["c"])),
+            undirected("test_edge_3", &["a", "d"]),
+        ]);
+
+        let found = isomorphism(&original, &relabeled, &[]).expect("❌ Relabeled graph should be isomorphic");
+        let rename: HashMap<String, String> = found.nodes.iter().cloned().collect();
+        assert_eq!(shapes(&original, &rename), shapes(&relabeled, &HashMap::new()), "❌ Mapping should carry every hyperedge over");
+        assert_eq!(rename["lonely"], "q");
+        assert!(found.edges.contains(&("test_edge_2".to_string(), "x2".to_string())));
+        assert!(isomorphism(&original, &reversed, &[]).is_none(), "❌ Head and tail roles must be respected");
+
+        // Two triangles and a hexagon look alike to color refinement but are not isomorphic
+        let triangles = graph("triangles", &[], ["ab", "bc", "ca", "de", "ef", "fd"].iter()
+            .map(|pair| undirected(pair, &[&pair[..1], &pair[1..]])).collect());
+        let hexagon = graph("hexagon", &[], ["ab", "bc", "cd", "de", "ef", "fa"].iter()
+            .map(|pair| undirected(pair, &[&pair[..1], &pair[1..]])).collect());
+        let hexagon_shifted = graph("hexagon_shifted", &[], ["fa", "cd", "bc", "ab", "de", "ef"].iter()
+            .map(|pair| undirected(pair, &[&pair[1..], &pair[..1]])).collect());
+        assert_ne!(canonical_form(&triangles, &[]), canonical_form(&hexagon, &[]), "❌ Triangles are no hexagon");
+        assert_eq!(canonical_form(&hexagon, &[]).fingerprint(), canonical_form(&hexagon_shifted, &[]).fingerprint());
+        assert_eq!(canonical_form(&hexagon, &[]).fingerprint().len(), 16);
+
+        // Labels only count when asked for
+        let mut colored = relabeled.clone();
+        colored.id = "colored".to_string();
+        colored.hyper_edges[0].main_properties.push(Property { key: "color".to_string(), value: vec!["red".to_string()] });
+        assert!(isomorphism(&original, &colored, &[]).is_some());
+        assert!(isomorphism(&original, &colored, &["color"]).is_none(), "❌ Property labels should be compared");
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        for graph in [&original, &relabeled, &reversed, &triangles, &hexagon, &hexagon_shifted, &colored] {
+            repository.create(&graph.id, graph)?;
+        }
+        let service = IsomorphismService::new(&repository);
+        assert!(service.are_isomorphic("original", "relabeled", &[])?);
+        assert_eq!(service.fingerprint("original", &[])?, service.fingerprint("relabeled", &[])?);
+        assert_eq!(service.duplicates(&["color"])?, vec![
+            vec!["hexagon".to_string(), "hexagon_shifted".to_string()],
+            vec!["original".to_string(), "relabeled".to_string()],
+        ], "❌ Unexpected duplicate groups");
+        assert!(service.fingerprint("missing", &[]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_symmetric_hypergraph() {
+        // Many interchangeable hyperedges: automorphism pruning keeps the search small
+        let pairs: Vec<String> = (0..24).map(|pair| format!("p{}", pair)).collect();
+        let edges = |order: &mut dyn Iterator<Item = usize>| order
+            .map(|pair| undirected(&pairs[pair], &[&format!("{}a", pairs[pair]), &format!("{}b", pairs[pair])]))
+            .collect();
+        let forward = graph("forward", &[], edges(&mut (0..24)));
+        let backward = graph("backward", &[], edges(&mut (0..24).rev()));
+        assert_eq!(canonical_form(&forward, &[]), canonical_form(&backward, &[]), "❌ Disjoint pairs should be isomorphic");
+    }
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::SimpleHyperEdge;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::sparse::incidence::IncidenceMatrix;
+use hgdb_core::hyper_edge::services::motif_service::{motif_catalog, Counting, MotifCounter, MotifService};
+use common::undirected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/motif"; // RocksDB path
+
+    fn graph(id: &str, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        HyperGraph { id: id.to_string(), name: id.to_string(), properties: Vec::new(), hyper_nodes: Vec::new(), hyper_edges: edges }
+    }
+
+    #[test]
+    fn test_motif_catalog() {
+        let catalog = motif_catalog();
+        assert_eq!(catalog.iter().filter(|motif| motif.hyperedges == 2).count(), 2, "❌ Two pair motifs: containment and overlap");
+        assert_eq!(catalog.iter().filter(|motif| motif.hyperedges == 3).count(), 26, "❌ Lee et al. list 26 h-motifs");
+        assert_eq!(catalog.iter().filter(|motif| motif.is_open()).count(), 6, "❌ Six of them are open");
+        assert_eq!(catalog.last().map(|motif| motif.name()), Some("h26".to_string()));
+    }
+
+    #[test]
+    fn test_exact_and_sampled_counts() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let small = graph("small", vec![
+            undirected("test_edge_1", &["a", "b", "c"]),
+            undirected("test_edge_2", &["c", "d"]),
+            undirected("test_edge_3", &["b", "c", "e"]),
+            undirected("test_edge_4", &["e", "f"]),
+        ]);
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("small", &small)?;
+        let counts = MotifService::new(&repository).count("small", Counting::Exact, 0)?;
+
+        assert_eq!(counts.get("p1"), Some(0.0), "❌ No hyperedge contains another");
+        assert_eq!(counts.get("p2"), Some(4.0), "❌ Four overlapping pairs");
+        let triples: Vec<(bool, f64)> = counts.iter().filter(|(motif, count)| motif.hyperedges == 3 && *count > 0.0).map(|(motif, count)| (motif.is_open(), count)).collect();
+        assert_eq!(triples.iter().map(|(_, count)| count).sum::<f64>(), 3.0, "❌ Three connected triples");
+        assert_eq!(triples.iter().filter(|(open, _)| !open).map(|(_, count)| count).sum::<f64>(), 1.0, "❌ One closed triple");
+
+        // Estimates from hyperwedge samples stay close to the exact counts
+        let larger = graph("larger", (0..60).map(|e| {
+            let members: Vec<String> = (0..3).map(|k| format!("n{}", (e * 7 + k * k * 5 + e / 4) % 45)).collect();
+            undirected(&format!("test_edge_{}", e), &members.iter().map(String::as_str).collect::<Vec<_>>())
+        }).collect());
+        let counter = MotifCounter::new(&IncidenceMatrix::from_graph(&larger, None));
+        let exact: f64 = counter.count(Counting::Exact, 0).counts.iter().sum();
+        let sampled: f64 = counter.count(Counting::Sampled { samples: 3000 }, 7).counts.iter().sum();
+        assert!(exact > 0.0);
+        assert!((sampled - exact).abs() / exact < 0.1, "❌ Sampled total {} too far from exact {}", sampled, exact);
+        assert_eq!(counter.count(Counting::Sampled { samples: 100 }, 3), counter.count(Counting::Sampled { samples: 100 }, 3), "❌ Same seed, same estimate");
+        Ok(())
+    }
+
+    #[test]
+    fn test_null_model_comparison() {
+        let clustered = graph("clustered", (0..30).map(|e| {
+            let group = e % 5;
+            undirected(&format!("test_edge_{}", e), &[&format!("g{}a", group), &format!("g{}b", group), &format!("x{}", e)])
+        }).collect());
+        let counter = MotifCounter::new(&IncidenceMatrix::from_graph(&clustered, None));
+        let significance = counter.compare(Counting::Exact, 5, 11);
+
+        assert_eq!(significance.len(), motif_catalog().len());
+        assert!(significance.iter().all(|entry| (-1.0..=1.0).contains(&entry.relative)));
+        assert_eq!(significance, counter.compare(Counting::Exact, 5, 11), "❌ Same seed, same randomizations");
+        // Every triple of the real graph lies in one group; the null model spreads hyperedges over many more patterns
+        let triples: Vec<_> = significance.iter().filter(|entry| entry.motif.hyperedges == 3).collect();
+        assert_eq!(triples.iter().filter(|entry| entry.count > 0.0).count(), 1, "❌ One motif should hold every real triple");
+        assert!(triples.iter().filter(|entry| entry.count == 0.0 && entry.null_mean > 0.0).count() > 3);
+        assert!(triples.iter().filter(|entry| entry.count == 0.0 && entry.null_mean > 0.0).all(|entry| entry.relative < 0.0), "❌ Missing motifs should be under-represented");
+    }
+}
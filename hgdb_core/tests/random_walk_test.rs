@@ -0,0 +1,128 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::services::random_walk_service::{RandomWalk, RandomWalkService};
+use common::edge;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/random-walk"; // RocksDB path
+
+    fn close(left: f64, right: f64) -> bool {
+        (left - right).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_random_walk_transitions() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let mut weighted = edge("test_edge_1", &["a", "b", "c"], None);
+        weighted.main_properties.push(Property { key: "vertex_weights".to_string(), value: vec!["a:2".to_string()] });
+        for edge in [weighted, edge("test_edge_2", &["c", "d"], None), edge("test_edge_3", &["y"], Some(&["d"]))] {
+            repository.create(&edge.id, &edge)?;
+        }
+
+        let (incidence, gamma) = RandomWalkService::new(&repository).load(None)?;
+        let plain = RandomWalk::new(&incidence);
+        assert!(close(plain.probability("a", "b"), 1.0 / 3.0), "❌ Uniform vertex weights should split the edge evenly");
+        assert!(close(plain.probability("c", "d"), 0.25), "❌ Node c should pick each of its two edges half the time");
+
+        let walk = RandomWalk::with_vertex_weights(&incidence, &gamma);
+        assert!(close(walk.probability("b", "a"), 0.5), "❌ Vertex weight 2 on a should double its share of e1");
+        assert!(close(walk.probability("a", "b"), 0.25), "❌ The other members of e1 should share the rest");
+
+        // The directed edge only leads from its tail d to its head y; y cannot leave
+        assert!(close(walk.probability("d", "y"), 0.5), "❌ Node d should leave through e3 half the time");
+        assert!(close(walk.probability("y", "d"), 0.0), "❌ A directed edge should not be walked from head to tail");
+        assert!(walk.transitions().row(incidence.nodes.position("y").unwrap()).0.is_empty(), "❌ Node y should be dangling");
+
+        for row in 0..incidence.node_count() {
+            let (_, values) = walk.transitions().row(row);
+            let total: f64 = values.iter().sum();
+            assert!(values.is_empty() || close(total, 1.0), "❌ Every non-dangling row should be stochastic");
+        }
+
+        let after: f64 = walk.distribution_after("a", 5).iter().map(|(_, mass)| mass).sum();
+        assert!(close(after, 1.0), "❌ Walking should preserve probability mass");
+        Ok(())
+    }
+
+    #[test]
+    fn test_pagerank() -> Result<(), Box<dyn Error>> {
+        let path = format!("{}-pagerank", DB_PATH);
+        if let Err(e) = remove_dir_all(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(&path)?;
+        for edge in [
+            edge("test_edge_1", &["a", "b", "c"], None),
+            edge("test_edge_2", &["c", "d"], None),
+            edge("test_edge_3", &["y"], Some(&["x"])),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = RandomWalkService::new(&repository);
+
+        let ranks = service.pagerank(None, 0.85, 1e-10)?;
+        let total: f64 = ranks.scores.iter().map(|(_, score)| score).sum();
+        assert!(close(total, 1.0), "❌ PageRank should be a distribution");
+        assert_eq!(ranks.top(1), vec!["c"], "❌ The node bridging both edges should rank first");
+        assert!(close(ranks.get("a").unwrap(), ranks.get("b").unwrap()), "❌ Symmetric nodes should rank equally");
+        assert!(ranks.get("y").unwrap() > ranks.get("x").unwrap(), "❌ The head of a directed edge should collect the tail's rank");
+
+        let personalized = service.personalized_pagerank(&["d"], None, 0.85, 1e-10)?;
+        assert_eq!(personalized.top(1), vec!["d"], "❌ The seed should rank first");
+        assert!(close(personalized.get("x").unwrap(), 0.0), "❌ Nodes unreachable from the seeds should get no rank");
+        assert!(personalized.get("a").unwrap() > 0.0, "❌ Nodes reachable from the seeds should get some rank");
+
+        assert!(service.personalized_pagerank(&["missing"], None, 0.85, 1e-10).is_err(), "❌ Unknown seeds should be rejected");
+        assert!(service.pagerank(None, 1.0, 1e-10).is_err(), "❌ A damping factor of 1 should be rejected");
+        Ok(())
+    }
+
+    #[test]
+    fn test_edges_without_head() -> Result<(), Box<dyn Error>> {
+        let path = format!("{}-headless", DB_PATH);
+        if let Err(e) = remove_dir_all(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(&path)?;
+        // Directed hyperedges with a tail but no head lead nowhere
+        for edge in [
+            edge("test_edge_1", &["a", "b"], None),
+            edge("test_edge_2", &[], Some(&["b"])),
+            edge("test_edge_3", &[], Some(&["z"])),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = RandomWalkService::new(&repository);
+
+        let (incidence, _) = service.load(None)?;
+        let walk = RandomWalk::new(&incidence);
+        assert!(close(walk.probability("b", "a") + walk.probability("b", "b"), 1.0), "❌ No mass should leave b through the headless edge");
+        let z = incidence.nodes.position("z").expect("❌ z should be indexed");
+        assert!(walk.transitions().row(z).0.is_empty(), "❌ A node with only headless edges is dangling");
+
+        let ranks = service.pagerank(None, 0.85, 1e-10)?;
+        assert!(close(ranks.scores.iter().map(|(_, score)| score).sum(), 1.0), "❌ PageRank should be a distribution");
+        let personalized = service.personalized_pagerank(&["b"], None, 0.85, 1e-10)?;
+        assert!(close(personalized.scores.iter().map(|(_, score)| score).sum(), 1.0), "❌ Personalized PageRank should be a distribution");
+        Ok(())
+    }
+}
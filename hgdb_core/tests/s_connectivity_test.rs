@@ -0,0 +1,85 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::HyperGraph;
+use hgdb_core::hyper_edge::services::s_connectivity_service::SConnectivityService;
+use common::{strings, undirected};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/s-connectivity"; // RocksDB path
+
+    fn property<'p>(properties: &'p [hgdb_core::hyper_edge::entity::simple_h_edge::Property<String, String>], key: &str) -> Option<&'p str> {
+        properties.iter().find(|property| property.key == key).map(|property| property.value[0].as_str())
+    }
+
+    #[test]
+    fn test_s_connectivity() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        let graph = HyperGraph {
+            id: "chain".to_string(),
+            name: "chain".to_string(),
+            properties: Vec::new(),
+            hyper_nodes: Vec::new(),
+            hyper_edges: vec![
+                undirected("test_edge_1", &["a", "b", "c"]),
+                undirected("test_edge_2", &["b", "c", "d"]),
+                undirected("test_edge_3", &["c", "d", "e"]),
+                undirected("test_edge_4", &["e", "f"]),
+                undirected("test_edge_5", &["x", "y"]),
+            ],
+        };
+        repository.create("chain", &graph)?;
+        let service = SConnectivityService::new(&repository);
+
+        // s = 1
+        let one = service.edge_connectivity("chain", 1)?;
+        assert_eq!(one.components(), vec![strings(&["test_edge_1", "test_edge_2", "test_edge_3", "test_edge_4"]), strings(&["test_edge_5"])]);
+        assert_eq!(one.distance("test_edge_1", "test_edge_4"), Some(2), "❌ e1 -c- e3 -e- e4");
+        assert_eq!(one.distance("test_edge_1", "test_edge_5"), None);
+        assert_eq!(one.diameter(), None, "❌ A disconnected hypergraph has no s-diameter");
+        assert_eq!(one.component_diameters(), vec![2, 0]);
+        assert_eq!(one.eccentricity("test_edge_4"), Some(2));
+
+        // s = 2
+        let two = service.edge_connectivity("chain", 2)?;
+        assert_eq!(two.component_count(), 3);
+        assert_eq!(two.members_of("test_edge_3"), strings(&["test_edge_1", "test_edge_2", "test_edge_3"]));
+        assert_eq!(two.component_of("test_edge_4"), Some(1));
+        assert_eq!(two.distance("test_edge_1", "test_edge_3"), Some(2), "❌ e1 and e3 share a single node");
+        assert_eq!(two.component_of("unknown"), None);
+
+        let nodes = service.node_connectivity("chain", 2)?;
+        assert_eq!(nodes.members_of("b"), strings(&["b", "c", "d"]), "❌ Node 2-component mismatch");
+        assert_eq!(nodes.distance("b", "d"), Some(2));
+        assert_eq!(service.node_connectivity("chain", 1)?.component_count(), 2);
+
+        // Persist the results as properties
+        service.save_components("chain", 2)?;
+        let stored = repository.get_by_key("chain")?.unwrap();
+        let edge_component = |id: &str| property(&stored.hyper_edges.iter().find(|edge| edge.id == id).unwrap().main_properties, "s2_component").map(str::to_string);
+        assert_eq!(edge_component("test_edge_2"), Some("0".to_string()));
+        assert_eq!(edge_component("test_edge_5"), Some("2".to_string()));
+
+        let node_component = |id: &str| property(&stored.hyper_nodes.iter().find(|node| node.id == id).unwrap().properties, "s2_component").map(str::to_string);
+        assert_eq!(stored.hyper_nodes.len(), 8, "❌ Every node should now be stored");
+        assert_eq!(node_component("b"), node_component("d"));
+        assert_ne!(node_component("a"), node_component("b"));
+        assert_eq!(property(&stored.properties, "s2_edge_components"), Some("3"));
+        assert_eq!(property(&stored.properties, "s2_diameter"), None);
+
+        assert!(service.save_components("missing", 1).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::incidence_service::IncidenceService;
+use hgdb_core::hyper_edge::services::s_line_graph_service::SLineGraphService;
+use hgdb_core::hyper_edge::services::sparse::line_graph::{dual_s_line_graph, s_line_graph};
+use common::undirected;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/s-line-graph"; // RocksDB path
+
+    #[test]
+    fn test_s_line_graphs() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        for edge in [
+            undirected("test_edge_1", &["a", "b", "c"]),
+            undirected("test_edge_2", &["b", "c", "d"]),
+            undirected("test_edge_3", &["c", "d", "e", "f"]),
+            undirected("test_edge_4", &["x"]),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = SLineGraphService::new(&repository);
+
+        let one = service.line_graph(1)?;
+        assert_eq!(one.index.len(), 4, "❌ Every hyperedge is a vertex of the line graph");
+        assert_eq!(one.link_count(), 3);
+        assert_eq!(one.weight("test_edge_1", "test_edge_2"), 2.0, "❌ Weight should be the number of shared nodes");
+        assert_eq!(one.weight("test_edge_1", "test_edge_3"), 1.0);
+        assert!(one.neighbors("test_edge_4").is_empty(), "❌ test_edge_4 shares no node");
+
+        let two = service.line_graph(2)?;
+        assert_eq!(two.link_count(), 2);
+        assert_eq!(two.weight("test_edge_1", "test_edge_3"), 0.0, "❌ A single shared node is below s = 2");
+        assert_eq!(two.weight("test_edge_3", "test_edge_2"), 2.0);
+        assert_eq!(service.line_graph(3)?.link_count(), 0);
+
+        let dual_two = service.dual_line_graph(2)?;
+        assert_eq!(dual_two.link_count(), 2, "❌ Only b-c and c-d share two hyperedges");
+        assert_eq!(dual_two.weight("b", "c"), 2.0);
+        assert_eq!(dual_two.weight("c", "d"), 2.0);
+        assert_eq!(service.dual_line_graph(1)?.weight("a", "b"), 1.0);
+
+        // The dual variant is the s-line graph of the dual hypergraph
+        let incidence = IncidenceService::new(&repository).build(None)?;
+        for s in 1..4 {
+            assert_eq!(dual_s_line_graph(&incidence, s), s_line_graph(&incidence.transpose(), s), "❌ Dual mismatch for s = {}", s);
+        }
+
+        Ok(())
+    }
+}
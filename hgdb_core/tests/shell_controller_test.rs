@@ -0,0 +1,77 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::h_graph_service::HyperGraphService;
+use hgdb_core::hyper_edge::controller::shell_controller::{ShellCompleter, ShellController, ShellResponse};
+use common::{edge, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/shell-controller"; // RocksDB path
+
+    #[test]
+    fn test_shell_commands() -> Result<(), Box<dyn Error>> {
+        // Delete the database folder before running the test
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        let edges = vec![
+            edge("test_edge_1", &["v1", "v2"], Some(&["v3"])).with_property("type", &["linked"]),
+            edge("test_edge_2", &["v3", "v4"], None).with_property("type", &["linked"]),
+            edge("test_edge_3", &["v4", "v5"], None).with_property("type", &["linked"]),
+            edge("test_edge_4", &["v6"], None).with_property("type", &["linked"]),
+        ];
+        for edge in &edges {
+            repository.create(&edge.id, edge)?;
+        }
+
+        // Graph queries used by the shell
+        let service = HyperGraphService::new(&repository);
+        let graph = service.load_graph("shell")?;
+        assert_eq!(graph.hyper_nodes.len(), 6, "❌ Node count mismatch");
+        assert_eq!(service.edges_of(&graph, "v3").len(), 2, "❌ v3 should be in two edges");
+
+        let neighbors: Vec<String> = service.neighbors(&graph, "v3").into_iter().map(|(node, _)| node).collect();
+        assert_eq!(neighbors, vec!["v1", "v2", "v4"], "❌ Neighbors of v3 mismatch");
+
+        let path = service.shortest_path(&graph, "v1", "v5").expect("❌ No path from v1 to v5");
+        assert_eq!(path.nodes, vec!["v1", "v3", "v4", "v5"], "❌ Path nodes mismatch");
+        assert_eq!(path.edges, vec!["test_edge_1", "test_edge_2", "test_edge_3"], "❌ Path edges mismatch");
+        assert!(service.shortest_path(&graph, "v1", "v6").is_none(), "❌ v6 should be unreachable");
+
+        // Commands executed through the controller
+        let mut shell = ShellController::new(&repository, "shell")?;
+
+        let ShellResponse::Output(output) = shell.execute("neighbors v3")? else { panic!("❌ Expected output") };
+        assert!(output.contains("v4") && output.contains("test_edge_2"), "❌ Neighbors table incomplete: {}", output);
+
+        let ShellResponse::Output(output) = shell.execute("edges-of v1")? else { panic!("❌ Expected output") };
+        assert!(output.contains("(1 rows)"), "❌ Edges of v1 table incomplete: {}", output);
+
+        let ShellResponse::Output(output) = shell.execute("dual e1")? else { panic!("❌ Expected output") };
+        assert!(output.contains("Matrix [3x1]") && output.contains("Matrix [1x3]"), "❌ Dual matrices missing: {}", output);
+
+        let ShellResponse::Output(output) = shell.execute("path v1 v5")? else { panic!("❌ Expected output") };
+        assert!(output.contains("(3 hops)"), "❌ Path output mismatch: {}", output);
+
+        assert!(shell.execute("neighbors v42").is_err(), "❌ Unknown node should fail");
+        assert!(shell.execute("path v1").is_err(), "❌ Missing argument should fail");
+        assert_eq!(shell.execute("quit")?, ShellResponse::Quit);
+
+        // Tab completion over commands, node ids and edge ids
+        let completer = ShellCompleter::new(shell.graph());
+        assert_eq!(completer.candidates("ne", 2), (0, vec!["neighbors".to_string()]));
+        assert_eq!(completer.candidates("path v1 v", 9).1.len(), 6, "❌ Node completion mismatch");
+        assert_eq!(completer.candidates("dual test_edge_", 15).1.len(), 4, "❌ Edge completion mismatch");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::simplify_service::{Merge, Simplification, SimplifyService};
+use common::{edge, strings, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/simplify"; // RocksDB path
+
+    fn merge(representative: &str, merged: &[&str]) -> Merge {
+        Merge { representative: representative.to_string(), merged: strings(merged) }
+    }
+
+    #[test]
+    fn test_simplification() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("imported", &HyperGraph {
+            id: "imported".to_string(),
+            name: "imported".to_string(),
+            properties: Vec::new(),
+            hyper_nodes: vec![
+                HyperNode { id: "b".to_string(), properties: vec![Property { key: "label".to_string(), value: strings(&["B"]) }] },
+                HyperNode { id: "c".to_string(), properties: vec![Property { key: "label".to_string(), value: strings(&["C"]) }] },
+            ],
+            hyper_edges: vec![
+                edge("test_edge_1", &["a", "b", "c"], None).with_property("source", &["import_1"]),
+                edge("test_edge_2", &["c", "b", "a"], None).with_property("source", &["import_2"]),
+                edge("test_edge_3", &["b", "c"], Some(&["a"])).with_property("source", &["import_1"]),
+                edge("test_edge_4", &["d", "b", "c"], None).with_property("source", &["import_1"]),
+                edge("test_edge_5", &["d"], None).with_property("source", &["import_1"]),
+                edge("test_edge_6", &[], None).with_property("source", &["import_1"]),
+            ],
+        })?;
+        let service = SimplifyService::new(&repository);
+        let steps = [Simplification::CollapseEdges, Simplification::CollapseNodes, Simplification::RemoveSingletons];
+
+        let preview = service.preview("imported", &steps)?;
+        assert_eq!(preview.merged_edges, vec![merge("test_edge_1", &["test_edge_2"])], "❌ Only same-member, same-direction edges are duplicates");
+        assert_eq!(preview.merged_nodes, vec![merge("b", &["c"])], "❌ b and c always occur together");
+        assert_eq!(preview.removed_edges, strings(&["test_edge_5", "test_edge_6"]));
+        assert_eq!(repository.get_by_key("imported")?.expect("❌ Graph should exist").hyper_edges.len(), 6, "❌ A preview must not write");
+
+        let report = service.apply("imported", &steps)?;
+        assert_eq!(report, preview, "❌ Applying should do what the preview announced");
+
+        let graph = repository.get_by_key("imported")?.expect("❌ Graph should exist");
+        assert_eq!(graph.hyper_edges.len(), 3);
+        let collapsed = graph.hyper_edges.iter().find(|edge| edge.id == "test_edge_1").expect("❌ Representative should be kept");
+        assert_eq!(*collapsed.head_hyper_nodes, strings(&["a", "b"]), "❌ c should be replaced by its representative");
+        let sources = collapsed.main_properties.iter().find(|property| property.key == "source").expect("❌ Properties should be merged");
+        assert_eq!(sources.value, strings(&["import_1", "import_2"]));
+        let merged = collapsed.main_properties.iter().find(|property| property.key == "merged_ids").expect("❌ Merged ids should be listed");
+        assert_eq!(merged.value, strings(&["test_edge_2"]));
+
+        let node = graph.hyper_nodes.iter().find(|node| node.id == "b").expect("❌ Representative node should be kept");
+        assert!(graph.hyper_nodes.iter().all(|node| node.id != "c"), "❌ Merged nodes should be removed");
+        assert_eq!(node.properties[0].value, strings(&["B", "C"]), "❌ Node properties should be merged");
+
+        assert!(service.apply("imported", &steps)?.is_empty(), "❌ A simplified graph should stay as is");
+        assert!(service.preview("missing", &steps).is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::services::star_expansion_service::{BipartiteVertex, IncidenceRole, StarExpansion, StarExpansionService};
+use common::edge;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, VecDeque};
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/star-expansion"; // RocksDB path
+
+    #[test]
+    fn test_star_expansion() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        for edge in [
+            edge("test_edge_1", &["v2", "v3"], Some(&["v1"])),
+            edge("test_edge_2", &["v3", "v4"], None),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+
+        let edges = repository.get_all()?;
+        let star = StarExpansion::new(&edges);
+
+        assert_eq!(star.node_count(), 4);
+        assert_eq!(star.edge_count(), 2);
+        assert_eq!(star.arcs().count(), 5, "❌ One arc per incidence");
+        assert_eq!(star.vertices().count(), 6);
+
+        // The view borrows the stored ids instead of copying them
+        let first = star.arcs().next().unwrap();
+        assert!(std::ptr::eq(first.edge, edges[0].id.as_str()), "❌ Arcs should point into the hyperedges");
+
+        // Head and tail labels
+        let v1_arcs = star.arcs_of(BipartiteVertex::Node("v1"));
+        assert_eq!(v1_arcs.len(), 1);
+        assert_eq!(v1_arcs[0].role, IncidenceRole::Tail);
+        assert_eq!(v1_arcs[0].source(), BipartiteVertex::Node("v1"), "❌ Tail arcs go from the node to the edge");
+        assert_eq!(star.arcs_of(BipartiteVertex::Edge("test_edge_1")).iter().filter(|arc| arc.role == IncidenceRole::Head).count(), 2);
+
+        // Orientation follows the directed flag
+        assert_eq!(star.out_neighbors(BipartiteVertex::Node("v1")), vec![BipartiteVertex::Edge("test_edge_1")]);
+        assert!(star.out_neighbors(BipartiteVertex::Node("v2")).is_empty(), "❌ v2 is only a head of a directed edge");
+        assert_eq!(star.out_neighbors(BipartiteVertex::Node("v3")), vec![BipartiteVertex::Edge("test_edge_2")]);
+        assert_eq!(star.neighbors(BipartiteVertex::Node("v3")).len(), 2);
+        assert_eq!(star.degree(BipartiteVertex::Edge("test_edge_2")), 2);
+
+        // A bipartite algorithm: BFS distances from v1 alternate between node and edge vertices
+        let mut distances: HashMap<BipartiteVertex, usize> = HashMap::new();
+        let mut queue = VecDeque::from([BipartiteVertex::Node("v1")]);
+        distances.insert(BipartiteVertex::Node("v1"), 0);
+        while let Some(vertex) = queue.pop_front() {
+            for neighbor in star.out_neighbors(vertex) {
+                if !distances.contains_key(&neighbor) {
+                    distances.insert(neighbor, distances[&vertex] + 1);
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        assert_eq!(distances[&BipartiteVertex::Node("v4")], 4, "❌ v1 -> e1 -> v3 -> e2 -> v4");
+        assert!(distances.iter().all(|(vertex, distance)| matches!(vertex, BipartiteVertex::Node(_)) == (distance % 2 == 0)));
+
+        // Streaming straight from the repository gives the same arcs
+        let mut streamed = Vec::new();
+        StarExpansionService::new(&repository).for_each_arc(|arc| streamed.push((arc.node.to_string(), arc.edge.to_string(), arc.role)))?;
+        let expected: Vec<_> = star.arcs().map(|arc| (arc.node.to_string(), arc.edge.to_string(), arc.role)).collect();
+        assert_eq!(streamed, expected, "❌ Streamed arcs mismatch");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::Property;
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::sub_h_graph_service::{property_equals, touching, SubHyperGraphService};
+use common::{edge, strings, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/sub-h-graph"; // RocksDB path
+
+    fn property<'g>(properties: &'g [Property<String, String>], key: &str) -> Option<&'g str> {
+        properties.iter().find(|property| property.key == key).map(|property| property.value[0].as_str())
+    }
+
+    #[test]
+    fn test_sub_hypergraphs() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        repository.create("source", &HyperGraph {
+            id: "source".to_string(),
+            name: "source".to_string(),
+            properties: Vec::new(),
+            hyper_nodes: vec![HyperNode { id: "a".to_string(), properties: vec![Property { key: "label".to_string(), value: strings(&["A"]) }] }],
+            hyper_edges: vec![
+                edge("test_edge_1", &["a", "b", "c"], None).with_property("type", &["linked"]),
+                edge("test_edge_2", &["c", "d"], Some(&["a"])).with_property("type", &["plain"]),
+                edge("test_edge_3", &["d", "e"], None).with_property("type", &["linked"]),
+            ],
+        })?;
+        let service = SubHyperGraphService::new(&repository);
+
+        // Induced on {a, c}: edges shrink to their members in the set, test_edge_3 disappears
+        let induced = service.induced("source", &["a", "c"])?;
+        assert_eq!(induced.hyper_edges.len(), 2, "❌ Only edges meeting the node set should remain");
+        let shrunk = &induced.hyper_edges[1];
+        assert_eq!(*shrunk.head_hyper_nodes, strings(&["c"]));
+        assert_eq!(shrunk.tail_hyper_nodes.as_deref(), Some(&strings(&["a"])), "❌ Tail role should be kept");
+        assert_eq!(induced.hyper_nodes.iter().map(|node| node.id.as_str()).collect::<Vec<_>>(), vec!["a", "c"]);
+        assert_eq!(property(&induced.hyper_nodes[0].properties, "label"), Some("A"), "❌ Node properties should be kept");
+        assert_eq!(property(&induced.properties, "subgraph_of"), Some("source"));
+        assert_eq!(property(&induced.properties, "subgraph.operation"), Some("induced"));
+
+        let linked = service.partial("source", property_equals("type", "linked"))?;
+        assert_eq!(linked.hyper_edges.iter().map(|edge| edge.id.as_str()).collect::<Vec<_>>(), vec!["test_edge_1", "test_edge_3"]);
+        assert_eq!(linked.hyper_nodes.len(), 5, "❌ Partial sub-hypergraphs keep every member of their edges");
+        assert_eq!(property(&linked.hyper_edges[1].main_properties, "source.id"), Some("test_edge_3"), "❌ Edges should point back to their source");
+
+        let touching_e = service.partial("source", touching(&["e"]))?;
+        assert_eq!(touching_e.hyper_edges.len(), 1);
+
+        let large = service.min_size("source", 3)?;
+        assert_eq!(large.hyper_edges.iter().map(|edge| edge.id.as_str()).collect::<Vec<_>>(), vec!["test_edge_1", "test_edge_2"]);
+        assert_eq!(property(&large.properties, "subgraph.operation"), Some("min_size 3"));
+
+        service.save_as(&large, "source_large")?;
+        let stored = repository.get_by_key("source_large")?.expect("❌ Sub-hypergraph should be stored");
+        assert_eq!(stored.hyper_edges.len(), 2);
+        assert_eq!(stored.name, "source_large");
+        assert!(repository.get_by_key("source")?.expect("❌ Source should be kept").hyper_edges.len() == 3, "❌ The source must not change");
+
+        let mut export = Vec::new();
+        assert_eq!(service.export(&linked, "linked", &mut export)?, 2);
+        let parsed: serde_json::Value = serde_json::from_slice(&export)?;
+        assert_eq!(parsed["linked"].as_array().map(|edges| edges.len()), Some(2), "❌ Export should list the edges under the key");
+        assert_eq!(parsed["linked"][0]["id"], "test_edge_1");
+
+        assert!(service.induced("missing", &["a"]).is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+}
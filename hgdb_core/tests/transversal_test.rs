@@ -0,0 +1,76 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::h_graph_repository::HyperGraphRepository;
+use hgdb_core::hyper_edge::entity::simple_h_edge::{SimpleHyperEdge, Property};
+use hgdb_core::hyper_edge::entity::h_graph::h_graph::{HyperGraph, HyperNode};
+use hgdb_core::hyper_edge::services::transversal_service::{SolverMode, TransversalService};
+use common::{strings, undirected};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/transversal"; // RocksDB path
+
+    fn graph(name: &str, hyper_nodes: Vec<HyperNode<String, String, String>>, edges: Vec<SimpleHyperEdge<String, String, String>>) -> HyperGraph<String, String, String> {
+        HyperGraph { id: name.to_string(), name: name.to_string(), properties: Vec::new(), hyper_nodes, hyper_edges: edges }
+    }
+
+    fn weighted(id: &str, weight: &str) -> HyperNode<String, String, String> {
+        HyperNode { id: id.to_string(), properties: vec![Property { key: "cost".to_string(), value: vec![weight.to_string()] }] }
+    }
+
+    #[test]
+    fn test_transversals_and_covers() -> Result<(), Box<dyn Error>> {
+        if let Err(e) = remove_dir_all(DB_PATH) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+            }
+        }
+
+        let repository = HyperGraphRepository::new(DB_PATH)?;
+        let service = TransversalService::new(&repository);
+
+        repository.create("chain", &graph("chain", Vec::new(), vec![
+            undirected("test_edge_1", &["a", "b"]),
+            undirected("test_edge_2", &["b", "c"]),
+            undirected("test_edge_3", &["c", "d"]),
+        ]))?;
+        let transversals = service.minimal_transversals("chain", None)?;
+        assert_eq!(transversals, vec![strings(&["a", "c"]), strings(&["b", "c"]), strings(&["b", "d"])], "❌ Minimal transversals mismatch");
+        assert!(service.minimal_transversals("chain", Some(1))?.is_empty(), "❌ No single node meets every edge");
+
+        // The greedy cover takes test_edge_3 first and then still needs both others
+        repository.create("cover", &graph("cover", vec![HyperNode { id: "lonely".to_string(), properties: Vec::new() }], vec![
+            undirected("test_edge_1", &["n1", "n2", "n3"]),
+            undirected("test_edge_2", &["n4", "n5", "n6"]),
+            undirected("test_edge_3", &["n1", "n2", "n4", "n5"]),
+        ]))?;
+        let greedy = service.set_cover("cover", None, SolverMode::Greedy)?;
+        assert_eq!(greedy.ids.len(), 3, "❌ Greedy cover should use three edges: {:?}", greedy);
+        let exact = service.set_cover("cover", None, SolverMode::Exact)?;
+        assert_eq!(exact.ids, strings(&["test_edge_1", "test_edge_2"]), "❌ Exact cover mismatch");
+        assert_eq!(exact.cost, 2.0);
+
+        // Hitting the three edges through the heavy node costs more than taking both light ones
+        repository.create("weighted", &graph("weighted", vec![weighted("a", "3"), weighted("b", "1"), weighted("c", "1.5")], vec![
+            undirected("test_edge_1", &["a", "b"]),
+            undirected("test_edge_2", &["a", "c"]),
+            undirected("test_edge_3", &["a", "b", "c"]),
+        ]))?;
+        let unweighted = service.hitting_set("weighted", None, SolverMode::Exact)?;
+        assert_eq!(unweighted.ids, strings(&["a"]), "❌ Without weights a single node suffices");
+        let hitting = service.hitting_set("weighted", Some("cost"), SolverMode::Exact)?;
+        assert_eq!(hitting.ids, strings(&["b", "c"]), "❌ Weighted hitting set mismatch");
+        assert_eq!(hitting.cost, 2.5);
+        let greedy = service.hitting_set("weighted", Some("cost"), SolverMode::Greedy)?;
+        assert!(greedy.cost >= hitting.cost, "❌ Greedy cannot beat the exact solution");
+
+        repository.create("broken", &graph("broken", Vec::new(), vec![undirected("test_edge_1", &["a"]), undirected("test_edge_2", &[])]))?;
+        assert!(service.hitting_set("broken", None, SolverMode::Greedy).is_err(), "❌ An empty edge cannot be hit");
+        assert!(service.minimal_transversals("missing", None).is_err(), "❌ Unknown graphs should be rejected");
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use hgdb_core::hyper_edge::repository::simple_h_edge_repository::SimpleHyperEdgeRepository;
+use hgdb_core::hyper_edge::repository::light_h_edge_repository::LightHyperEdgeRepository;
+use hgdb_core::hyper_edge::entity::light_h_edge::LightHyperEdge;
+use hgdb_core::hyper_edge::entity::structure::structure::Traverse;
+use hgdb_core::hyper_edge::entity::relationship::relationship::Relationship;
+use hgdb_core::hyper_edge::services::traversal_service::{TraversalEngine, TraversalOrder, TraversalService, VisitControl};
+use common::{edge, strings, EdgeBuilder};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+    use std::fs::remove_dir_all;
+
+    const DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/traversal"; // RocksDB path
+    const LIGHT_DB_PATH: &str = "/users/gigin/documents/mydbs/rocksdb/traversal-light";
+
+    #[test]
+    fn test_traversal_flags_and_path_write_back() -> Result<(), Box<dyn Error>> {
+        for path in [DB_PATH, LIGHT_DB_PATH] {
+            if let Err(e) = remove_dir_all(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    eprintln!("⚠️ Failed to remove DB directory: {:?}", e);
+                }
+            }
+        }
+
+        let repository = SimpleHyperEdgeRepository::new(DB_PATH)?;
+        for edge in [
+            edge("test_edge_1", &["b", "c"], Some(&["a"])),
+            edge("test_edge_2", &["c", "d"], None),
+            edge("test_edge_3", &["d", "e"], None).with_traversable(false),
+            edge("test_edge_4", &["a"], Some(&["e"])),
+            edge("test_edge_5", &["d", "f"], None),
+            edge("test_edge_6", &["b", "g"], None),
+        ] {
+            repository.create(&edge.id, &edge)?;
+        }
+        let service = TraversalService::new(&repository);
+
+        assert_eq!(service.bfs("a", None)?, strings(&["a", "b", "c", "g", "d", "f"]), "❌ BFS order mismatch");
+        assert_eq!(service.dfs("a", None)?, strings(&["a", "b", "g", "c", "d", "f"]), "❌ DFS order mismatch");
+        assert_eq!(service.bfs("a", Some(1))?, strings(&["a", "b", "c"]), "❌ Depth limit ignored");
+        assert_eq!(service.bfs("c", None)?, strings(&["c", "d", "f"]), "❌ Directed edges must not be crossed from head to tail");
+        assert!(!service.bfs("d", None)?.contains(&"e".to_string()), "❌ Non-traversable edges must not be crossed");
+        assert!(service.bfs("unknown", None)?.is_empty());
+
+        // Visitor callbacks can prune and stop the traversal
+        let incidence = service.incidence()?;
+        let engine = TraversalEngine::new(&incidence);
+        let mut seen = Vec::new();
+        let steps = engine.traverse("a", TraversalOrder::BreadthFirst, None, |step| {
+            seen.push((step.node.to_string(), step.depth));
+            if step.node == "c" { VisitControl::Skip } else { VisitControl::Continue }
+        });
+        assert_eq!(steps.len(), 4, "❌ Skipping c should hide d and f");
+        assert_eq!(seen[3], ("g".to_string(), 2));
+        assert_eq!(steps[3].via_edge, Some("test_edge_6"));
+        let stopped = engine.traverse("a", TraversalOrder::DepthFirst, None, |step| {
+            if step.node == "g" { VisitControl::Stop } else { VisitControl::Continue }
+        });
+        assert_eq!(stopped.last().unwrap().node, "g");
+        assert_eq!(stopped.len(), 3);
+
+        // Paths follow the same rules
+        let path = service.find_path("a", "f")?.expect("❌ f should be reachable from a");
+        assert_eq!(path.nodes, strings(&["a", "c", "d", "f"]));
+        assert_eq!(path.edges, strings(&["test_edge_1", "test_edge_2", "test_edge_5"]));
+        assert!(service.find_path("f", "a")?.is_none(), "❌ a is only reachable through a tail");
+        assert!(engine.find_path("a", "f", Some(2)).is_none(), "❌ Depth limit should apply to paths");
+
+        // Write the path back into a LightHyperEdge
+        let lights = LightHyperEdgeRepository::new(LIGHT_DB_PATH)?;
+        let light_edge = LightHyperEdge {
+            id: "l1".to_string(),
+            simple_hyper_edge: edge("test_edge_1", &["b", "c"], Some(&["a"])),
+            structural_properties: Vec::new(),
+            relationship: Relationship { node_1: "a".to_string(), node_2: "f".to_string(), directed: true, edge_properties: Vec::new() },
+            traverse: Traverse { path: Vec::new() },
+        };
+        lights.create("l1", &light_edge)?;
+
+        service.save_traverse(&lights, "l1", "a", "f")?;
+        let stored = lights.get_by_key("l1")?.unwrap();
+        assert_eq!(stored.traverse.path, strings(&["a", "c", "d", "f"]), "❌ Traverse path was not written back");
+
+        assert!(service.save_traverse(&lights, "l1", "f", "a")?.is_none());
+        assert_eq!(lights.get_by_key("l1")?.unwrap().traverse.path.len(), 4, "❌ A missing path must not clear the stored one");
+        assert!(service.save_traverse(&lights, "missing", "a", "f").is_err());
+
+        Ok(())
+    }
+}